@@ -0,0 +1,148 @@
+//! Block hash resolution of `reth debug execution --cl-endpoint`.
+
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use reth_primitives::{BlockNumber, Header, SealedHeader, B256};
+use reth_rpc_api::clients::EthApiClient;
+use reth_rpc_types::{BlockNumberOrTag, RichBlock};
+
+/// Resolves the headers of target blocks from a trusted RPC endpoint instead of from peers.
+///
+/// Headers are requested with `eth_getBlockByNumber` and `eth_getBlockByHash`, e.g. from the
+/// execution client that a known consensus client drives. Every header is hashed locally and
+/// rejected if its hash doesn't match the hash reported by the endpoint.
+#[derive(Debug, Clone)]
+pub struct EndpointHeaderClient {
+    url: String,
+    client: HttpClient,
+}
+
+impl EndpointHeaderClient {
+    /// Creates a new client for the HTTP endpoint at the given URL.
+    pub fn new(url: &str) -> eyre::Result<Self> {
+        let client = HttpClientBuilder::default().build(url)?;
+        Ok(Self { url: url.to_string(), client })
+    }
+
+    /// Returns the header of the block with the given number.
+    pub async fn header_by_number(&self, number: BlockNumber) -> eyre::Result<SealedHeader> {
+        let block =
+            EthApiClient::block_by_number(&self.client, BlockNumberOrTag::Number(number), false)
+                .await?;
+        let header = self.seal(block, || format!("block {number}"))?;
+        if header.number != number {
+            eyre::bail!("Endpoint {} returned block {} for block {number}", self.url, header.number)
+        }
+        Ok(header)
+    }
+
+    /// Returns the header of the block with the given hash.
+    pub async fn header_by_hash(&self, hash: B256) -> eyre::Result<SealedHeader> {
+        let block = EthApiClient::block_by_hash(&self.client, hash, false).await?;
+        let header = self.seal(block, || format!("block {hash}"))?;
+        if header.hash() != hash {
+            eyre::bail!("Endpoint {} returned block {} for block {hash}", self.url, header.hash())
+        }
+        Ok(header)
+    }
+
+    /// Converts the header of the returned block, and checks that it hashes to the reported hash.
+    fn seal(
+        &self,
+        block: Option<RichBlock>,
+        block_id: impl Fn() -> String,
+    ) -> eyre::Result<SealedHeader> {
+        let Some(block) = block else {
+            eyre::bail!("Endpoint {} doesn't know {}", self.url, block_id())
+        };
+        let reported_hash = block.header.hash;
+        let header = Header::try_from(block.inner.header)
+            .map_err(|err| eyre::eyre!("Invalid header of {}: {err}", block_id()))?
+            .seal_slow();
+        if reported_hash.is_some_and(|hash| hash != header.hash()) {
+            eyre::bail!(
+                "Header of {} hashes to {}, but endpoint {} reported {reported_hash:?}",
+                block_id(),
+                header.hash(),
+                self.url
+            )
+        }
+        Ok(header)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{
+        server::{ServerBuilder, ServerHandle},
+        types::ErrorObjectOwned,
+        RpcModule,
+    };
+    use reth_primitives::{Block, BlockWithSenders, Bytes, U256};
+    use reth_rpc_types_compat::block::from_block_with_tx_hashes;
+
+    fn rich_block(number: BlockNumber) -> RichBlock {
+        let block = Block {
+            header: Header { number, gas_limit: 30_000_000, ..Default::default() },
+            ..Default::default()
+        };
+        from_block_with_tx_hashes(BlockWithSenders { block, senders: vec![] }, U256::ZERO, None)
+            .into()
+    }
+
+    /// Serves `eth_getBlockByNumber` and `eth_getBlockByHash` for the given blocks.
+    async fn serve(blocks: Vec<RichBlock>) -> (EndpointHeaderClient, ServerHandle) {
+        let mut module = RpcModule::new(blocks);
+        module
+            .register_method("eth_getBlockByNumber", |params, blocks, _| {
+                let (number, _): (BlockNumberOrTag, bool) = params.parse()?;
+                Ok::<_, ErrorObjectOwned>(
+                    blocks.iter().find(|block| block.header.number == number.as_number()).cloned(),
+                )
+            })
+            .unwrap();
+        module
+            .register_method("eth_getBlockByHash", |params, blocks, _| {
+                let (hash, _): (B256, bool) = params.parse()?;
+                Ok::<_, ErrorObjectOwned>(
+                    blocks.iter().find(|block| block.header.hash == Some(hash)).cloned(),
+                )
+            })
+            .unwrap();
+
+        let server = ServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        (EndpointHeaderClient::new(&url).unwrap(), server.start(module))
+    }
+
+    #[tokio::test]
+    async fn resolves_headers_by_number_and_hash() {
+        let blocks = vec![rich_block(1), rich_block(2)];
+        let hash = blocks[1].header.hash.unwrap();
+        let (client, _handle) = serve(blocks).await;
+
+        let header = client.header_by_number(2).await.unwrap();
+        assert_eq!(header.number, 2);
+        assert_eq!(header.hash(), hash);
+
+        let header = client.header_by_hash(hash).await.unwrap();
+        assert_eq!(header.number, 2);
+        assert_eq!(header.hash(), hash);
+
+        let err = client.header_by_number(3).await.unwrap_err();
+        assert!(err.to_string().contains("doesn't know block 3"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn rejects_headers_that_do_not_match_the_reported_hash() {
+        let mut block = rich_block(1);
+        block.inner.header.extra_data = Bytes::from_static(b"tampered");
+        let hash = block.header.hash.unwrap();
+        let (client, _handle) = serve(vec![block]).await;
+
+        let err = client.header_by_number(1).await.unwrap_err();
+        assert!(err.to_string().contains("hashes to"), "{err}");
+        let err = client.header_by_hash(hash).await.unwrap_err();
+        assert!(err.to_string().contains("hashes to"), "{err}");
+    }
+}
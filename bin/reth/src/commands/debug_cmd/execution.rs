@@ -1,9 +1,16 @@
 //! Command for debugging execution.
 
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    ops::RangeInclusive,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use clap::Parser;
-use futures::{stream::select as stream_select, StreamExt};
+use clap::{builder::RangedU64ValueParser, Parser};
+use futures::{stream::select as stream_select, Stream, StreamExt};
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
 use reth_cli_runner::CliContext;
@@ -16,17 +23,51 @@ use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
     headers::reverse_headers::ReverseHeadersDownloaderBuilder,
 };
+use reth_evm::{
+    execute::{BlockExecutorProvider, Executor},
+    noop::NoopBlockExecutorProvider,
+};
 use reth_exex::ExExManagerHandle;
+use reth_metrics::{
+    metrics::{Gauge, Histogram},
+    Metrics,
+};
 use reth_network::{BlockDownloaderProvider, NetworkEventListenerProvider, NetworkHandle};
 use reth_network_api::NetworkInfo;
-use reth_network_p2p::{headers::client::HeadersClient, BlockClient};
-use reth_primitives::{BlockHashOrNumber, BlockNumber, B256};
+use reth_network_p2p::{
+    headers::client::{HeadersClient, HeadersDirection, HeadersRequest},
+    BlockClient,
+};
+use reth_node_core::{
+    dirs::{ChainPath, DataDirPath},
+    version::{
+        BUILD_PROFILE_NAME, CARGO_PKG_VERSION, VERGEN_BUILD_TIMESTAMP, VERGEN_CARGO_FEATURES,
+        VERGEN_CARGO_TARGET_TRIPLE, VERGEN_GIT_SHA,
+    },
+};
+use reth_node_metrics::{
+    hooks::Hooks,
+    server::{MetricServer, MetricServerConfig},
+    version::VersionInfo,
+};
+use reth_primitives::{
+    revm_primitives::SpecId, BlockHashOrNumber, BlockNumber, BlockWithSenders, SealedHeader, B256,
+    U256,
+};
 use reth_provider::{
-    BlockExecutionWriter, ChainSpecProvider, ProviderFactory, StageCheckpointReader,
+    BlockExecutionWriter, BlockNumReader, BlockReader, ChainSpecProvider, HeaderProvider,
+    LatestStateProviderRef, ProviderFactory, ProviderResult, StageCheckpointReader,
+    StageCheckpointWriter, StaticFileCompression, StaticFileProviderFactory, StaticFileWriter,
+    TransactionVariant,
 };
 use reth_prune::PruneModes;
+use reth_revm::{database::StateProviderDatabase, db::CacheDB};
+use reth_rpc_eth_types::revm_utils::apply_state_overrides;
+use reth_rpc_types::state::StateOverride;
 use reth_stages::{
-    sets::DefaultStages, stages::ExecutionStage, ExecutionStageThresholds, Pipeline, StageId,
+    sets::{DefaultStages, OfflineStages},
+    stages::ExecutionStage,
+    ControlFlow, ExecutionStageThresholds, Pipeline, PipelineEvent, StageCheckpoint, StageId,
     StageSet,
 };
 use reth_static_file::StaticFileProducer;
@@ -34,7 +75,18 @@ use reth_tasks::TaskExecutor;
 use tokio::sync::watch;
 use tracing::*;
 
-use crate::{args::NetworkArgs, macros::block_executor, utils::get_single_header};
+use crate::{
+    args::NetworkArgs,
+    commands::debug_cmd::{
+        endpoint::EndpointHeaderClient,
+        inspect::{ExecutionStatsInspector, InspectKind, InspectorEvmConfig},
+        outcome::{BlockOutcome, OutcomeExporter},
+        report::{CountingClient, DownloadStats, ReportWriter, RunRecorder},
+        spec::{commit_bundle, parse_spec_id, ForcedSpecEvmConfig},
+    },
+    macros::block_executor,
+    utils::get_single_header,
+};
 
 /// `reth debug execution` command
 #[derive(Debug, Parser)]
@@ -46,17 +98,188 @@ pub struct Command {
     network: NetworkArgs,
 
     /// The maximum block height.
-    #[arg(long)]
-    pub to: u64,
+    #[arg(
+        long,
+        required_unless_present_any = ["to_hash", "single"],
+        conflicts_with_all = ["to_hash", "single"]
+    )]
+    pub to: Option<u64>,
+
+    /// The hash of the maximum block, as an alternative to `--to`.
+    ///
+    /// The hash is resolved to the block number from the database, or from the network if the
+    /// block is not stored locally.
+    #[arg(long, value_name = "HASH", conflicts_with = "single")]
+    pub to_hash: Option<B256>,
+
+    /// Re-executes only the given block and reports whether it passed, along with the time it
+    /// took.
+    ///
+    /// The block passes if it executes without a validation error, and, with
+    /// `--verify-state-root`, if the resulting state root matches its header. Blocks must be
+    /// executed up to the parent of the given block.
+    #[arg(long, value_name = "BLOCK", conflicts_with_all = ["interval", "adaptive_interval"])]
+    pub single: Option<u64>,
 
     /// The block interval for sync and unwind.
     /// Defaults to `1000`.
     #[arg(long, default_value = "1000")]
     pub interval: u64,
+
+    /// Sizes each pipeline run by cumulative gas used instead of by block count.
+    ///
+    /// Each run ends at the first block at which the gas used since the start of the run reaches
+    /// the given target. This keeps the work per run roughly constant across the chain.
+    /// Takes precedence over `--interval` if set.
+    #[arg(long, value_name = "GAS")]
+    pub adaptive_interval: Option<u64>,
+
+    /// Unwinds each pipeline run in batches of at most this many blocks, starting from the highest
+    /// block, instead of all at once.
+    ///
+    /// All batches share one write transaction, since every batch verifies the state root against
+    /// the state left behind by the batches above it, but the blocks and execution outputs taken
+    /// from the database are only held for one batch at a time. As before, unwinds are never
+    /// committed.
+    #[arg(long, value_name = "BLOCKS", value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
+    pub unwind_batch: Option<u64>,
+
+    /// Overrides the maximum number of concurrent header requests of the configured
+    /// `stages.headers` downloader settings.
+    #[arg(long, value_name = "REQUESTS", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub header_concurrency: Option<usize>,
+
+    /// Overrides the maximum number of concurrent body requests of the configured
+    /// `stages.bodies` downloader settings.
+    #[arg(long, value_name = "REQUESTS", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub body_concurrency: Option<usize>,
+
+    /// Removes the execution outputs of all processed blocks once the command finished.
+    ///
+    /// Only data derived by executing the blocks is unwound (e.g. state, receipts, changesets and
+    /// indices), downloaded headers and bodies are kept. This allows re-running the execution of
+    /// the same blocks without downloading them again.
+    #[arg(long)]
+    pub prune_execution_only: bool,
+
+    /// Re-executes blocks that are already stored in the database, without starting the network.
+    ///
+    /// Execution outputs are kept between pipeline runs. Fails if any block up to `--to` is not
+    /// stored locally.
+    #[arg(long)]
+    pub no_network: bool,
+
+    /// Enable Prometheus metrics.
+    ///
+    /// The metrics will be served at the given interface and port. Besides the usual node
+    /// metrics, the duration of every stage run is recorded per stage.
+    #[arg(long, value_name = "SOCKET")]
+    pub metrics: Option<SocketAddr>,
+
+    /// Stops at the first block whose state root doesn't match the state root of its canonical
+    /// header, instead of unwinding and retrying it.
+    ///
+    /// The merkle stage compares the state root with the canonical header of the last block of
+    /// every pipeline run. The canonical header is fetched from the network, or read from the
+    /// database with `--no-network`.
+    #[arg(long)]
+    pub verify_state_root: bool,
+
+    /// Executes the locally stored blocks up to `--to` on top of the given state overrides,
+    /// without writing anything to the database.
+    ///
+    /// The overrides are given as JSON in the format of the `eth_call` state override set, i.e.
+    /// balance, nonce, code and storage overrides keyed by account address. They are applied to
+    /// an in-memory overlay on top of the latest executed state. Since the overridden state
+    /// diverges from the canonical one, blocks are not validated against their headers. The
+    /// network is not started.
+    #[arg(long, value_name = "JSON", value_parser = parse_state_override)]
+    pub state_override: Option<StateOverride>,
+
+    /// The compression of static files created while executing, one of `default`,
+    /// `uncompressed`, `lz4`, `zstd` or `zstd:<level>`.
+    ///
+    /// Only applies to newly created static files, existing ones keep their compression. The
+    /// time spent appending to and committing static files is recorded in the static file
+    /// metrics, if enabled.
+    #[arg(long, value_name = "COMPRESSION")]
+    pub static_file_compression: Option<StaticFileCompression>,
+
+    /// Attaches a built-in inspector to the execution of every block and logs its aggregated
+    /// stats once the command finished.
+    ///
+    /// `opcodes` counts the executions of every opcode, `storage` counts `SLOAD` and `SSTORE`
+    /// executions. Blocks are executed without an inspector if unset.
+    #[arg(long, value_name = "KIND", value_enum)]
+    pub inspect: Option<InspectKind>,
+
+    /// Writes a report of every pipeline run to the given file, as one JSON object per line.
+    ///
+    /// A report holds the range and duration of the run, the time spent in each stage, and the
+    /// number of headers and bodies downloaded during the run along with their RLP encoded size in
+    /// bytes.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["no_network", "state_override", "force_spec"]
+    )]
+    pub report_path: Option<PathBuf>,
+
+    /// Executes the locally stored blocks up to `--to` with the given EVM spec, e.g. `cancun` or
+    /// `prague`, regardless of the spec that is active for each block.
+    ///
+    /// The results are NOT canonical: blocks are executed in memory on top of the latest executed
+    /// state, like with `--state-override`, and are not validated against their headers. The gas
+    /// used by every block is logged next to the gas used of its header. Nothing is written to
+    /// the database and the network is not started.
+    #[arg(long, value_name = "NAME", value_parser = parse_spec_id, conflicts_with = "verify_state_root")]
+    pub force_spec: Option<SpecId>,
+
+    /// Resolves the hashes of the target blocks of the pipeline runs, and the block of
+    /// `--to-hash`, from the given trusted HTTP endpoint instead of from peers.
+    ///
+    /// The endpoint must serve `eth_getBlockByNumber` and `eth_getBlockByHash`, e.g. the
+    /// execution client that a known consensus client drives. Block headers and bodies are still
+    /// downloaded from peers.
+    #[arg(
+        long,
+        value_name = "URL",
+        conflicts_with_all = ["no_network", "state_override", "force_spec"]
+    )]
+    pub cl_endpoint: Option<String>,
+
+    /// Exports the execution outcome of every block to the given directory, as one RLP encoded
+    /// file per block named `<number>.rlp`.
+    ///
+    /// An outcome holds the number, hash and gas used of the block, along with the receipts root,
+    /// logs bloom and receipts computed by this node. The last block of every pipeline run also
+    /// holds the state root of its header, which is only validated against the executed state
+    /// with `--verify-state-root`. Use `--interval 1` to export the state root of every block. The
+    /// export is deterministic, e.g. to compare the outcomes with those of another client.
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["state_override", "force_spec"])]
+    pub export_outcome: Option<PathBuf>,
+}
+
+/// Parses a JSON encoded [`StateOverride`].
+fn parse_state_override(value: &str) -> eyre::Result<StateOverride> {
+    Ok(serde_json::from_str(value)?)
 }
 
+/// The maximum number of headers requested at once when sizing adaptive intervals.
+const ADAPTIVE_INTERVAL_HEADERS_BATCH: u64 = 1000;
+
+/// The maximum number of consecutive header requests that may fail or return no usable headers
+/// when sizing adaptive intervals, before giving up.
+const ADAPTIVE_INTERVAL_MAX_RETRIES: u32 = 10;
+
+/// The delay before retrying a failed header request when sizing adaptive intervals, multiplied
+/// by the number of consecutive failures.
+const ADAPTIVE_INTERVAL_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
 impl Command {
-    fn build_pipeline<DB, Client>(
+    /// Builds the pipeline that downloads and executes blocks with the given executor.
+    #[allow(clippy::too_many_arguments)]
+    fn build_pipeline<DB, Client, E>(
         &self,
         config: &Config,
         client: Client,
@@ -64,17 +287,32 @@ impl Command {
         provider_factory: ProviderFactory<DB>,
         task_executor: &TaskExecutor,
         static_file_producer: StaticFileProducer<DB>,
+        executor: E,
     ) -> eyre::Result<Pipeline<DB>>
     where
         DB: Database + Unpin + Clone + 'static,
         Client: BlockClient + 'static,
+        E: BlockExecutorProvider,
     {
         // building network downloaders using the fetch client
-        let header_downloader = ReverseHeadersDownloaderBuilder::new(config.stages.headers)
+        let mut header_downloader = ReverseHeadersDownloaderBuilder::new(config.stages.headers);
+        if let Some(concurrency) = self.header_concurrency {
+            let min = config.stages.headers.downloader_min_concurrent_requests;
+            header_downloader = header_downloader
+                .min_concurrent_requests(min.min(concurrency))
+                .max_concurrent_requests(concurrency);
+        }
+        let header_downloader = header_downloader
             .build(client.clone(), Arc::clone(&consensus))
             .into_task_with(task_executor);
 
-        let body_downloader = BodiesDownloaderBuilder::new(config.stages.bodies)
+        let mut body_downloader = BodiesDownloaderBuilder::new(config.stages.bodies);
+        if let Some(concurrency) = self.body_concurrency {
+            let min = config.stages.bodies.downloader_min_concurrent_requests;
+            body_downloader =
+                body_downloader.with_concurrent_requests_range(min.min(concurrency)..=concurrency);
+        }
+        let body_downloader = body_downloader
             .build(client, Arc::clone(&consensus), provider_factory.clone())
             .into_task_with(task_executor);
 
@@ -82,7 +320,6 @@ impl Command {
         let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
 
         let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
-        let executor = block_executor!(provider_factory.chain_spec());
 
         let pipeline = Pipeline::builder()
             .with_tip_sender(tip_tx)
@@ -115,6 +352,71 @@ impl Command {
         Ok(pipeline)
     }
 
+    /// Builds a pipeline that executes blocks that are already stored in the database with the
+    /// given executor, up to the given block.
+    fn build_offline_pipeline<DB, E>(
+        &self,
+        config: &Config,
+        provider_factory: ProviderFactory<DB>,
+        max_block: BlockNumber,
+        executor: E,
+    ) -> Pipeline<DB>
+    where
+        DB: Database + Unpin + Clone + 'static,
+        E: BlockExecutorProvider,
+    {
+        let stage_conf = &config.stages;
+        let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
+
+        Pipeline::builder()
+            .with_max_block(max_block)
+            .add_stages(
+                OfflineStages::new(executor.clone(), stage_conf.clone(), prune_modes.clone())
+                    .builder()
+                    .set(ExecutionStage::new(
+                        executor,
+                        ExecutionStageThresholds {
+                            max_blocks: None,
+                            max_changes: None,
+                            max_cumulative_gas: None,
+                            max_duration: None,
+                        },
+                        stage_conf.execution_external_clean_threshold(),
+                        prune_modes,
+                        ExExManagerHandle::empty(),
+                    )),
+            )
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory, PruneModes::default()),
+            )
+    }
+
+    /// Builds a pipeline that only unwinds data derived from executing blocks, keeping headers,
+    /// bodies and senders.
+    fn build_execution_unwind_pipeline<DB>(
+        &self,
+        config: &Config,
+        provider_factory: ProviderFactory<DB>,
+    ) -> Pipeline<DB>
+    where
+        DB: Database + Unpin + Clone + 'static,
+    {
+        // Unwinding does not require a valid executor
+        let executor = NoopBlockExecutorProvider::default();
+
+        Pipeline::builder()
+            .add_stages(
+                OfflineStages::new(executor, config.stages.clone(), PruneModes::default())
+                    .builder()
+                    .disable(StageId::SenderRecovery),
+            )
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory, PruneModes::default()),
+            )
+    }
+
     async fn build_network(
         &self,
         config: &Config,
@@ -136,17 +438,17 @@ impl Command {
         Ok(network)
     }
 
-    async fn fetch_block_hash<Client: HeadersClient>(
+    async fn fetch_block_header<Client: HeadersClient>(
         &self,
         client: Client,
         block: BlockNumber,
-    ) -> eyre::Result<B256> {
+    ) -> eyre::Result<SealedHeader> {
         info!(target: "reth::cli", ?block, "Fetching block from the network.");
         loop {
             match get_single_header(&client, BlockHashOrNumber::Number(block)).await {
                 Ok(tip_header) => {
                     info!(target: "reth::cli", ?block, "Successfully fetched block");
-                    return Ok(tip_header.hash())
+                    return Ok(tip_header)
                 }
                 Err(error) => {
                     error!(target: "reth::cli", ?block, %error, "Failed to fetch the block. Retrying...");
@@ -155,10 +457,475 @@ impl Command {
         }
     }
 
+    /// Returns the last block of the pipeline run starting at `next_block`, such that the
+    /// cumulative gas used of the run reaches `gas_target`, capped at the `target` block.
+    ///
+    /// Fails if [`ADAPTIVE_INTERVAL_MAX_RETRIES`] consecutive requests fail or return no headers
+    /// of the requested range.
+    async fn fetch_adaptive_target_block<Client: HeadersClient>(
+        &self,
+        client: Client,
+        next_block: BlockNumber,
+        target: BlockNumber,
+        gas_target: u64,
+    ) -> eyre::Result<BlockNumber> {
+        let mut cumulative_gas = 0u64;
+        let mut block = next_block;
+        let mut retries = 0;
+        while block < target {
+            let request = HeadersRequest {
+                start: BlockHashOrNumber::Number(block),
+                limit: ADAPTIVE_INTERVAL_HEADERS_BATCH.min(target - block + 1),
+                direction: HeadersDirection::Rising,
+            };
+            let request_start = block;
+            match client.get_headers(request).await {
+                Ok(response) => {
+                    for header in response.into_data() {
+                        // skip the rest of the response if the peer sent unexpected headers
+                        if header.number != block {
+                            break
+                        }
+
+                        cumulative_gas = cumulative_gas.saturating_add(header.gas_used);
+                        if cumulative_gas >= gas_target {
+                            return Ok(block)
+                        }
+                        block += 1;
+                    }
+                }
+                Err(error) => {
+                    error!(target: "reth::cli", ?block, %error, "Failed to fetch headers");
+                }
+            }
+
+            if block > request_start {
+                retries = 0;
+                continue
+            }
+
+            retries += 1;
+            if retries > ADAPTIVE_INTERVAL_MAX_RETRIES {
+                eyre::bail!(
+                    "failed to fetch header {block} after {ADAPTIVE_INTERVAL_MAX_RETRIES} retries"
+                )
+            }
+            warn!(target: "reth::cli", ?block, retries, "No headers received. Retrying...");
+            tokio::time::sleep(ADAPTIVE_INTERVAL_RETRY_BACKOFF * retries).await;
+        }
+
+        Ok(target)
+    }
+
+    /// Returns the last block of the pipeline run starting at `next_block`, like
+    /// [`Self::fetch_adaptive_target_block`], but reads the headers from the database.
+    fn local_adaptive_target_block<DB: Database>(
+        &self,
+        provider_factory: &ProviderFactory<DB>,
+        next_block: BlockNumber,
+        target: BlockNumber,
+        gas_target: u64,
+    ) -> eyre::Result<BlockNumber> {
+        let mut cumulative_gas = 0u64;
+        for block in next_block..target {
+            let header = provider_factory
+                .header_by_number(block)?
+                .ok_or_else(|| eyre::eyre!("Block {block} is not stored locally"))?;
+
+            cumulative_gas = cumulative_gas.saturating_add(header.gas_used);
+            if cumulative_gas >= gas_target {
+                return Ok(block)
+            }
+        }
+
+        Ok(target)
+    }
+
+    /// Re-executes blocks that are already stored in the database up to the `target` block,
+    /// without starting the network.
+    ///
+    /// Returns the last block that was executed before the command started.
+    async fn execute_offline<E: BlockExecutorProvider>(
+        &self,
+        config: &Config,
+        provider_factory: ProviderFactory<Arc<DatabaseEnv>>,
+        task_executor: &TaskExecutor,
+        target: BlockNumber,
+        executor: E,
+    ) -> eyre::Result<Option<BlockNumber>> {
+        let latest_block_number =
+            provider_factory.get_stage_checkpoint(StageId::Execution)?.map(|ch| ch.block_number);
+        if latest_block_number.unwrap_or_default() >= target {
+            info!(target: "reth::cli", latest = latest_block_number, "Nothing to run");
+            return Ok(latest_block_number)
+        }
+
+        let stored_block_number = provider_factory
+            .get_stage_checkpoint(StageId::Bodies)?
+            .map(|ch| ch.block_number)
+            .unwrap_or_default();
+        if stored_block_number < target {
+            eyre::bail!(
+                "Blocks up to {target} are required, but only blocks up to {stored_block_number} are stored locally"
+            )
+        }
+
+        let exporter = self.export_outcome.as_deref().map(OutcomeExporter::new).transpose()?;
+
+        let mut current_max_block = latest_block_number.unwrap_or_default();
+        while current_max_block < target {
+            let next_block = current_max_block + 1;
+            let target_block = match self.adaptive_interval {
+                Some(gas_target) => self.local_adaptive_target_block(
+                    &provider_factory,
+                    next_block,
+                    target,
+                    gas_target,
+                )?,
+                None => target.min(current_max_block + self.interval),
+            };
+            let target_header = provider_factory
+                .sealed_header(target_block)?
+                .ok_or_else(|| eyre::eyre!("Block {target_block} is not stored locally"))?;
+            let target_block_hash = target_header.hash();
+
+            // Run the pipeline
+            info!(target: "reth::cli", from = next_block, to = target_block, tip = ?target_block_hash, "Starting offline pipeline");
+            let mut pipeline = self.build_offline_pipeline(
+                config,
+                provider_factory.clone(),
+                target_block,
+                executor.clone(),
+            );
+            if self.metrics.is_some() {
+                task_executor.spawn(record_stage_durations(pipeline.events()));
+            }
+            if self.verify_state_root {
+                if let ControlFlow::Unwind { bad_block, .. } = pipeline.run_loop().await? {
+                    eyre::bail!(
+                        "Block {} ({}) failed validation",
+                        bad_block.number,
+                        bad_block.hash()
+                    )
+                }
+            } else {
+                pipeline.run().await?;
+            }
+            trace!(target: "reth::cli", from = next_block, to = target_block, tip = ?target_block_hash, "Pipeline finished");
+
+            if let Some(exporter) = &exporter {
+                self.export_outcomes(
+                    exporter,
+                    &provider_factory,
+                    next_block..=target_block,
+                    target_header.state_root,
+                )?;
+            }
+
+            // Update latest block
+            current_max_block = target_block;
+        }
+
+        Ok(latest_block_number)
+    }
+
+    /// Executes the locally stored blocks after the execution checkpoint up to `target` on top of
+    /// an in-memory overlay seeded with the given state overrides, if any.
+    ///
+    /// The database is only read from, all state changes are kept in memory. Blocks are executed
+    /// without validating them against their headers, the overridden state or the forced spec
+    /// are expected to produce non-canonical outputs.
+    fn execute_in_memory<DB: Database, E: BlockExecutorProvider>(
+        &self,
+        provider_factory: &ProviderFactory<DB>,
+        target: BlockNumber,
+        state_override: Option<StateOverride>,
+        executor: E,
+    ) -> eyre::Result<()> {
+        let provider = provider_factory.provider()?;
+        let latest_block_number = provider
+            .get_stage_checkpoint(StageId::Execution)?
+            .map(|ch| ch.block_number)
+            .unwrap_or_default();
+        if latest_block_number >= target {
+            info!(target: "reth::cli", latest = latest_block_number, "Nothing to run");
+            return Ok(())
+        }
+
+        let mut db = CacheDB::new(StateProviderDatabase::new(LatestStateProviderRef::new(
+            provider.tx_ref(),
+            provider_factory.static_file_provider(),
+        )));
+        if let Some(state_override) = state_override {
+            apply_state_overrides(state_override, &mut db)?;
+        }
+
+        let block_with_td = |block_number: BlockNumber| -> eyre::Result<(BlockWithSenders, U256)> {
+            let block = provider
+                .block_with_senders(block_number.into(), TransactionVariant::WithHash)?
+                .ok_or_else(|| eyre::eyre!("Block {block_number} is not stored locally"))?;
+            let td = provider
+                .header_td_by_number(block_number)?
+                .ok_or_else(|| eyre::eyre!("Total difficulty of block {block_number} not found"))?;
+            Ok((block, td))
+        };
+
+        for block_number in latest_block_number + 1..=target {
+            let (block, td) = block_with_td(block_number)?;
+            let output = executor.executor(&mut db).execute((&block, td).into())?;
+            if let Some(spec_id) = self.force_spec {
+                info!(
+                    target: "reth::cli",
+                    block_number,
+                    ?spec_id,
+                    gas_used = output.gas_used,
+                    header_gas_used = block.header.gas_used,
+                    failed_transactions = output.receipts.iter().filter(|receipt| !receipt.success).count(),
+                    "Executed block with forced spec, result is non-canonical"
+                );
+            } else {
+                debug!(
+                    target: "reth::cli",
+                    block_number,
+                    gas_used = output.gas_used,
+                    header_gas_used = block.header.gas_used,
+                    "Executed block with state override"
+                );
+            }
+            commit_bundle(&mut db, output.state);
+        }
+
+        info!(target: "reth::cli", from = latest_block_number + 1, to = target, "Executed blocks in memory");
+        Ok(())
+    }
+
+    /// Exports the outcomes of the given blocks after the pipeline ran up to the last of them.
+    ///
+    /// The last block is exported with the given state root of its header. The hashing and
+    /// merkle stages only keep the state root of the database up to date if the state root is
+    /// verified, so it is not computed from the database.
+    fn export_outcomes<DB: Database>(
+        &self,
+        exporter: &OutcomeExporter,
+        provider_factory: &ProviderFactory<DB>,
+        blocks: RangeInclusive<BlockNumber>,
+        state_root: B256,
+    ) -> eyre::Result<()> {
+        let provider = provider_factory.provider()?;
+
+        let last_block = *blocks.end();
+        for block in blocks {
+            let mut outcome = BlockOutcome::from_provider(&provider, block)?;
+            if block == last_block {
+                outcome = outcome.with_state_root(state_root);
+            }
+            exporter.write(&outcome)?;
+        }
+
+        debug!(target: "reth::cli", to = last_block, "Exported block outcomes");
+        Ok(())
+    }
+
+    /// Unwinds the execution outputs of all blocks after `unwind_to`.
+    fn unwind_execution_outputs<DB>(
+        &self,
+        config: &Config,
+        provider_factory: ProviderFactory<DB>,
+        unwind_to: BlockNumber,
+        current_max_block: BlockNumber,
+    ) -> eyre::Result<()>
+    where
+        DB: Database + Unpin + Clone + 'static,
+    {
+        info!(target: "reth::cli", from = unwind_to + 1, to = current_max_block, "Unwinding execution outputs");
+
+        let mut pipeline = self.build_execution_unwind_pipeline(config, provider_factory.clone());
+        // Move all applicable data from database to static files.
+        pipeline.move_to_static_files()?;
+        pipeline.unwind(unwind_to, None)?;
+
+        // The finish stage is not part of the unwind pipeline, reset its checkpoint so that the
+        // unwound blocks are executed again on the next run.
+        let provider_rw = provider_factory.provider_rw()?;
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(unwind_to))?;
+        provider_rw.commit()?;
+
+        Ok(())
+    }
+
     /// Execute `execution-debug` command
-    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+    pub async fn execute(mut self, ctx: CliContext) -> eyre::Result<()> {
         let Environment { provider_factory, config, data_dir } = self.env.init(AccessRights::RW)?;
 
+        // The maximum block, `--to-hash` is resolved from the network if it's not stored locally.
+        let mut target = match (self.to.or(self.single), self.to_hash) {
+            (Some(number), _) => BlockHashOrNumber::Number(number),
+            (None, Some(hash)) => BlockHashOrNumber::Hash(hash),
+            (None, None) => eyre::bail!("One of --to, --to-hash or --single is required"),
+        };
+        if let BlockHashOrNumber::Hash(hash) = target {
+            if let Some(number) = provider_factory.block_number(hash)? {
+                info!(target: "reth::cli", %hash, number, "Resolved maximum block from the database");
+                target = BlockHashOrNumber::Number(number);
+            } else if self.no_network || self.state_override.is_some() || self.force_spec.is_some()
+            {
+                eyre::bail!("Block {hash} is not stored locally")
+            }
+        }
+
+        if let Some(block) = self.single {
+            let latest_block_number = provider_factory
+                .get_stage_checkpoint(StageId::Execution)?
+                .map(|ch| ch.block_number)
+                .unwrap_or_default();
+            if latest_block_number + 1 != block {
+                eyre::bail!(
+                    "Block {block} can only be executed on top of block {}, but blocks are executed up to {latest_block_number}",
+                    block.saturating_sub(1)
+                )
+            }
+            self.interval = 1;
+        }
+
+        if let Some(listen_addr) = self.metrics {
+            info!(target: "reth::cli", "Starting metrics endpoint at {}", listen_addr);
+            let config = MetricServerConfig::new(
+                listen_addr,
+                VersionInfo {
+                    version: CARGO_PKG_VERSION,
+                    build_timestamp: VERGEN_BUILD_TIMESTAMP,
+                    cargo_features: VERGEN_CARGO_FEATURES,
+                    git_sha: VERGEN_GIT_SHA,
+                    target_triple: VERGEN_CARGO_TARGET_TRIPLE,
+                    build_profile: BUILD_PROFILE_NAME,
+                },
+                ctx.task_executor.clone(),
+                Hooks::new(
+                    provider_factory.db_ref().clone(),
+                    provider_factory.static_file_provider(),
+                ),
+            );
+
+            MetricServer::new(config).serve().await?;
+        }
+
+        if let Some(compression) = self.static_file_compression {
+            info!(target: "reth::cli", ?compression, "Configuring static file compression");
+            provider_factory.static_file_provider().set_compression(compression);
+        }
+
+        // Commit any pending static file appends if the command is interrupted, so the static
+        // files don't end up truncated on the next startup.
+        let static_file_provider = provider_factory.static_file_provider();
+        ctx.task_executor.spawn_critical_with_graceful_shutdown_signal(
+            "static file writer flush",
+            |shutdown| async move {
+                let _guard = shutdown.await;
+                if let Err(err) = static_file_provider.commit() {
+                    error!(target: "reth::cli", %err, "Failed to commit static file writers on shutdown");
+                } else {
+                    debug!(target: "reth::cli", "Committed static file writers on shutdown");
+                }
+            },
+        );
+
+        let force_spec = self.force_spec;
+        if let Some(spec_id) = force_spec {
+            warn!(target: "reth::cli", ?spec_id, "Forcing the EVM spec of all blocks, execution results are NOT canonical");
+        }
+
+        let single = self.single;
+        let started_at = Instant::now();
+        let result = match self.inspect {
+            Some(kind) => {
+                let inspector = ExecutionStatsInspector::new(kind);
+                let executor = block_executor!(provider_factory.chain_spec(), |evm_config| {
+                    ForcedSpecEvmConfig::new(
+                        InspectorEvmConfig::new(evm_config, inspector.clone()),
+                        force_spec,
+                    )
+                });
+                let result = self
+                    .execute_with_executor(
+                        &ctx.task_executor,
+                        config,
+                        provider_factory,
+                        data_dir,
+                        target,
+                        executor,
+                    )
+                    .await;
+                inspector.report();
+                result
+            }
+            None => {
+                let executor = block_executor!(provider_factory.chain_spec(), |evm_config| {
+                    ForcedSpecEvmConfig::new(evm_config, force_spec)
+                });
+                self.execute_with_executor(
+                    &ctx.task_executor,
+                    config,
+                    provider_factory,
+                    data_dir,
+                    target,
+                    executor,
+                )
+                .await
+            }
+        };
+
+        if let Some(block) = single {
+            let elapsed = started_at.elapsed();
+            match &result {
+                Ok(()) => info!(target: "reth::cli", block, ?elapsed, "Block passed"),
+                Err(err) => error!(target: "reth::cli", block, ?elapsed, %err, "Block failed"),
+            }
+        }
+
+        result
+    }
+
+    /// Runs the command up to the `target` block with the given block executor.
+    ///
+    /// The `target` block is only resolved from the network if it's given by hash, it must be
+    /// given by number without the network.
+    async fn execute_with_executor<E: BlockExecutorProvider>(
+        self,
+        task_executor: &TaskExecutor,
+        config: Config,
+        provider_factory: ProviderFactory<Arc<DatabaseEnv>>,
+        data_dir: ChainPath<DataDirPath>,
+        target: BlockHashOrNumber,
+        executor: E,
+    ) -> eyre::Result<()> {
+        if self.state_override.is_some() || self.force_spec.is_some() || self.no_network {
+            let target = target
+                .as_number()
+                .ok_or_else(|| eyre::eyre!("Block {target} is not stored locally"))?;
+
+            if self.state_override.is_some() || self.force_spec.is_some() {
+                return self.execute_in_memory(
+                    &provider_factory,
+                    target,
+                    self.state_override.clone(),
+                    executor,
+                )
+            }
+
+            let latest_block_number = self
+                .execute_offline(&config, provider_factory.clone(), task_executor, target, executor)
+                .await?;
+            if self.prune_execution_only {
+                self.unwind_execution_outputs(
+                    &config,
+                    provider_factory,
+                    latest_block_number.unwrap_or_default(),
+                    target,
+                )?;
+            }
+            return Ok(())
+        }
+
         let consensus: Arc<dyn Consensus> =
             Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
 
@@ -168,7 +935,7 @@ impl Command {
         let network = self
             .build_network(
                 &config,
-                ctx.task_executor.clone(),
+                task_executor.clone(),
                 provider_factory.clone(),
                 network_secret_path,
                 data_dir.known_peers(),
@@ -180,30 +947,58 @@ impl Command {
 
         // Configure the pipeline
         let fetch_client = network.fetch_client().await?;
+        let endpoint = self.cl_endpoint.as_deref().map(EndpointHeaderClient::new).transpose()?;
+        let target = match target {
+            BlockHashOrNumber::Number(number) => number,
+            BlockHashOrNumber::Hash(hash) => {
+                let header = match &endpoint {
+                    Some(endpoint) => endpoint.header_by_hash(hash).await?,
+                    None => get_single_header(&fetch_client, target).await?,
+                };
+                info!(target: "reth::cli", %hash, number = header.number, "Resolved maximum block from the network");
+                header.number
+            }
+        };
+        let download_stats = DownloadStats::default();
         let mut pipeline = self.build_pipeline(
             &config,
-            fetch_client.clone(),
+            CountingClient::new(fetch_client.clone(), download_stats.clone()),
             Arc::clone(&consensus),
             provider_factory.clone(),
-            &ctx.task_executor,
+            task_executor,
             static_file_producer,
+            executor,
         )?;
 
         let provider = provider_factory.provider()?;
 
         let latest_block_number =
             provider.get_stage_checkpoint(StageId::Finish)?.map(|ch| ch.block_number);
-        if latest_block_number.unwrap_or_default() >= self.to {
+        if latest_block_number.unwrap_or_default() >= target {
             info!(target: "reth::cli", latest = latest_block_number, "Nothing to run");
             return Ok(())
         }
 
+        if self.metrics.is_some() {
+            task_executor.spawn(record_stage_durations(pipeline.events()));
+        }
+
+        let mut report = match &self.report_path {
+            Some(path) => Some((
+                ReportWriter::new(path)?,
+                RunRecorder::spawn(pipeline.events(), download_stats, task_executor),
+            )),
+            None => None,
+        };
+
+        let exporter = self.export_outcome.as_deref().map(OutcomeExporter::new).transpose()?;
+
         let pipeline_events = pipeline.events();
         let events = stream_select(
             network.event_listener().map(Into::into),
             pipeline_events.map(Into::into),
         );
-        ctx.task_executor.spawn_critical(
+        task_executor.spawn_critical(
             "events task",
             reth_node_events::node::handle_events(
                 Some(Box::new(network)),
@@ -214,29 +1009,379 @@ impl Command {
         );
 
         let mut current_max_block = latest_block_number.unwrap_or_default();
-        while current_max_block < self.to {
+        while current_max_block < target {
             let next_block = current_max_block + 1;
-            let target_block = self.to.min(current_max_block + self.interval);
-            let target_block_hash =
-                self.fetch_block_hash(fetch_client.clone(), target_block).await?;
+            let target_block = match self.adaptive_interval {
+                Some(gas_target) => {
+                    self.fetch_adaptive_target_block(
+                        fetch_client.clone(),
+                        next_block,
+                        target,
+                        gas_target,
+                    )
+                    .await?
+                }
+                None => target.min(current_max_block + self.interval),
+            };
+            let target_header = match &endpoint {
+                Some(endpoint) => endpoint.header_by_number(target_block).await?,
+                None => self.fetch_block_header(fetch_client.clone(), target_block).await?,
+            };
+            let target_block_hash = target_header.hash();
 
             // Run the pipeline
             info!(target: "reth::cli", from = next_block, to = target_block, tip = ?target_block_hash, "Starting pipeline");
             pipeline.set_tip(target_block_hash);
+            let run_started_at = Instant::now();
             let result = pipeline.run_loop().await?;
             trace!(target: "reth::cli", from = next_block, to = target_block, tip = ?target_block_hash, ?result, "Pipeline finished");
 
-            // Unwind the pipeline without committing.
-            {
-                provider_factory
-                    .provider_rw()?
-                    .take_block_and_execution_range(next_block..=target_block)?;
+            if let Some((writer, recorder)) = &mut report {
+                let run_report =
+                    recorder.finish_run(next_block, target_block, run_started_at.elapsed()).await;
+                writer.write(&run_report)?;
+            }
+
+            if self.verify_state_root || self.single.is_some() {
+                if let ControlFlow::Unwind { bad_block, .. } = result {
+                    eyre::bail!(
+                        "Block {} ({}) failed validation",
+                        bad_block.number,
+                        bad_block.hash()
+                    )
+                }
             }
+            if let Some(exporter) = &exporter {
+                // the blocks of a failed run were unwound already
+                if !result.is_unwind() {
+                    self.export_outcomes(
+                        exporter,
+                        &provider_factory,
+                        next_block..=target_block,
+                        target_header.state_root,
+                    )?;
+                }
+            }
+
+            // Unwind the pipeline without committing.
+            unwind_in_batches(
+                &provider_factory.provider_rw()?,
+                next_block..=target_block,
+                self.unwind_batch.unwrap_or(u64::MAX),
+            )?;
 
             // Update latest block
             current_max_block = target_block;
         }
 
+        if self.prune_execution_only {
+            self.unwind_execution_outputs(
+                &config,
+                provider_factory,
+                latest_block_number.unwrap_or_default(),
+                current_max_block,
+            )?;
+        }
+
         Ok(())
     }
 }
+
+/// Unwinds the given blocks from the highest block down, in batches of at most `batch` blocks.
+///
+/// All batches are unwound with the given provider, i.e. in the same transaction, since every batch
+/// verifies the state root against the state left behind by the batches above it.
+fn unwind_in_batches(
+    provider_rw: &impl BlockExecutionWriter,
+    blocks: RangeInclusive<BlockNumber>,
+    batch: u64,
+) -> ProviderResult<()> {
+    let (first, mut unwind_to) = blocks.into_inner();
+    loop {
+        let unwind_from = unwind_to.saturating_sub(batch - 1).max(first);
+        trace!(target: "reth::cli", from = unwind_from, to = unwind_to, "Unwinding batch");
+        provider_rw.take_block_and_execution_range(unwind_from..=unwind_to)?;
+        if unwind_from == first {
+            return Ok(())
+        }
+        unwind_to = unwind_from - 1;
+    }
+}
+
+/// Durations of the stage runs of the `debug execution` command.
+#[derive(Metrics)]
+#[metrics(scope = "debug.execution.stage")]
+struct StageDurationMetrics {
+    /// The duration of the last run of the stage in seconds.
+    last_run_duration_seconds: Gauge,
+    /// The durations of all runs of the stage in seconds.
+    run_duration_seconds: Histogram,
+}
+
+/// Records the duration of every stage run of the pipeline that emits the given events, labeled by
+/// stage.
+///
+/// Unwinds and failed runs are not recorded.
+async fn record_stage_durations(mut events: impl Stream<Item = PipelineEvent> + Unpin) {
+    let mut metrics = HashMap::<StageId, StageDurationMetrics>::new();
+    let mut running = HashMap::<StageId, Instant>::new();
+
+    while let Some(event) = events.next().await {
+        match event {
+            PipelineEvent::Run { stage_id, .. } => {
+                running.insert(stage_id, Instant::now());
+            }
+            PipelineEvent::Ran { stage_id, .. } => {
+                let Some(started_at) = running.remove(&stage_id) else { continue };
+                let duration = started_at.elapsed().as_secs_f64();
+
+                let metrics = metrics.entry(stage_id).or_insert_with(|| {
+                    StageDurationMetrics::new_with_labels(&[("stage", stage_id.to_string())])
+                });
+                metrics.last_run_duration_seconds.set(duration);
+                metrics.run_duration_seconds.record(duration);
+            }
+            PipelineEvent::Error { stage_id } => {
+                running.remove(&stage_id);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Decodable;
+    use reth_network_p2p::test_utils::TestHeadersClient;
+    use reth_node_ethereum::{EthEvmConfig, EthExecutorProvider};
+    use reth_primitives::{Address, Block, Header, StaticFileSegment};
+    use reth_provider::{
+        test_utils::{blocks::BlockchainTestData, create_test_provider_factory},
+        AccountReader,
+    };
+
+    fn command(args: &[&str]) -> Command {
+        Command::try_parse_from(std::iter::once("execution").chain(args.iter().copied())).unwrap()
+    }
+
+    fn headers(range: RangeInclusive<u64>, gas_used: u64) -> Vec<Header> {
+        range.map(|number| Header { number, gas_used, ..Default::default() }).collect()
+    }
+
+    #[tokio::test]
+    async fn adaptive_target_block_reaches_gas_target() {
+        let command = command(&["--to", "10", "--adaptive-interval", "35"]);
+        let client = TestHeadersClient::default();
+        client.extend(headers(1..=10, 10)).await;
+
+        assert_eq!(command.fetch_adaptive_target_block(client, 1, 10, 35).await.unwrap(), 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn adaptive_target_block_gives_up_without_headers() {
+        let command = command(&["--to", "10", "--adaptive-interval", "35"]);
+        let client = TestHeadersClient::default();
+
+        assert!(command.fetch_adaptive_target_block(client.clone(), 1, 10, 35).await.is_err());
+        assert_eq!(client.request_attempts(), ADAPTIVE_INTERVAL_MAX_RETRIES as u64 + 1);
+    }
+
+    #[test]
+    fn unwind_execution_outputs_resets_finish_checkpoint() {
+        let command = command(&["--to", "10", "--prune-execution-only"]);
+        let provider_factory = create_test_provider_factory();
+        {
+            let provider_rw = provider_factory.provider_rw().unwrap();
+            provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(10)).unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        command
+            .unwind_execution_outputs(&Config::default(), provider_factory.clone(), 0, 10)
+            .unwrap();
+
+        let finish = provider_factory.provider().unwrap().get_stage_checkpoint(StageId::Finish);
+        assert_eq!(finish.unwrap().map(|checkpoint| checkpoint.block_number), Some(0));
+    }
+
+    /// Stores the genesis and the executed blocks of the [`BlockchainTestData`].
+    fn insert_test_blocks<DB: Database>(
+        provider_factory: &ProviderFactory<DB>,
+    ) -> BlockchainTestData {
+        let data = BlockchainTestData::default();
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        provider_rw
+            .insert_historical_block(data.genesis.clone().try_seal_with_senders().unwrap())
+            .unwrap();
+
+        let mut outcome = data.blocks[0].1.clone();
+        for (_, block_outcome) in &data.blocks[1..] {
+            outcome.extend(block_outcome.clone());
+        }
+        let hashed_state = outcome.hash_state_slow().into_sorted();
+        provider_rw
+            .append_blocks_with_state(
+                data.blocks.iter().map(|(block, _)| block.clone()).collect(),
+                outcome,
+                hashed_state,
+                Default::default(),
+            )
+            .unwrap();
+        provider_rw
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        provider_rw.commit().unwrap();
+        data
+    }
+
+    #[test]
+    fn unwinds_in_batches_smaller_than_the_range() {
+        let provider_factory = create_test_provider_factory();
+        insert_test_blocks(&provider_factory);
+
+        // every batch verifies the state root of the block below it
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        unwind_in_batches(&provider_rw, 1..=5, 2).unwrap();
+        assert!(provider_rw.block_body_indices(1).unwrap().is_none());
+        assert!(provider_rw.block_body_indices(0).unwrap().is_some());
+    }
+
+    /// Stores a chain of empty blocks up to the given block, rewarding the given beneficiary.
+    fn insert_empty_blocks<DB: Database>(
+        provider_factory: &ProviderFactory<DB>,
+        tip: BlockNumber,
+        beneficiary: Address,
+    ) {
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        let mut parent_hash = B256::ZERO;
+        for number in 0..=tip {
+            let header = Header {
+                number,
+                parent_hash,
+                beneficiary,
+                gas_limit: 30_000_000,
+                timestamp: number * 12,
+                ..Default::default()
+            };
+            let block = Block { header, ..Default::default() }.seal_slow();
+            parent_hash = block.hash();
+            provider_rw.insert_historical_block(block.try_seal_with_senders().unwrap()).unwrap();
+        }
+        provider_rw
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        provider_rw.commit().unwrap();
+    }
+
+    fn forced_spec_executor<DB: Database>(
+        provider_factory: &ProviderFactory<DB>,
+        spec_id: SpecId,
+    ) -> EthExecutorProvider<ForcedSpecEvmConfig<EthEvmConfig>> {
+        EthExecutorProvider::new(
+            provider_factory.chain_spec(),
+            ForcedSpecEvmConfig::new(EthEvmConfig::default(), Some(spec_id)),
+        )
+    }
+
+    #[test]
+    fn execute_in_memory_does_not_write_state() {
+        let command = command(&["--to", "2", "--force-spec", "cancun"]);
+        let provider_factory = create_test_provider_factory();
+        let beneficiary = Address::with_last_byte(0xaa);
+        insert_empty_blocks(&provider_factory, 2, beneficiary);
+
+        command
+            .execute_in_memory(
+                &provider_factory,
+                2,
+                None,
+                forced_spec_executor(&provider_factory, SpecId::CANCUN),
+            )
+            .unwrap();
+
+        // the block rewards are only applied to the in-memory overlay
+        let provider = provider_factory.provider().unwrap();
+        assert!(provider.basic_account(beneficiary).unwrap().is_none());
+        assert!(provider.get_stage_checkpoint(StageId::Execution).unwrap().is_none());
+    }
+
+    #[test]
+    fn execute_in_memory_requires_stored_blocks() {
+        let command = command(&["--to", "3", "--force-spec", "cancun"]);
+        let provider_factory = create_test_provider_factory();
+        insert_empty_blocks(&provider_factory, 2, Address::with_last_byte(0xaa));
+
+        let err = command
+            .execute_in_memory(
+                &provider_factory,
+                3,
+                None,
+                forced_spec_executor(&provider_factory, SpecId::CANCUN),
+            )
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Block 3 is not stored locally");
+    }
+
+    #[test]
+    fn execute_in_memory_skips_executed_blocks() {
+        let command = command(&["--to", "2", "--force-spec", "cancun"]);
+        let provider_factory = create_test_provider_factory();
+        {
+            let provider_rw = provider_factory.provider_rw().unwrap();
+            provider_rw.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(2)).unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        // no blocks are stored, so executing any block would fail
+        command
+            .execute_in_memory(
+                &provider_factory,
+                2,
+                None,
+                forced_spec_executor(&provider_factory, SpecId::CANCUN),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn exports_outcomes_with_the_state_root_of_the_last_header() {
+        let command = command(&["--to", "5"]);
+        let provider_factory = create_test_provider_factory();
+        let data = insert_test_blocks(&provider_factory);
+        let dir = tempfile::tempdir().unwrap();
+        let exporter = OutcomeExporter::new(dir.path()).unwrap();
+
+        let last_block = &data.blocks.last().unwrap().0;
+        command
+            .export_outcomes(&exporter, &provider_factory, 1..=5, last_block.state_root)
+            .unwrap();
+
+        for (block, execution_outcome) in &data.blocks {
+            let encoded = std::fs::read(dir.path().join(format!("{}.rlp", block.number))).unwrap();
+            let outcome = BlockOutcome::decode(&mut encoded.as_slice()).unwrap();
+            let receipts: Vec<_> = execution_outcome
+                .receipts_by_block(block.number)
+                .iter()
+                .flatten()
+                .map(|receipt| receipt.clone().with_bloom())
+                .collect();
+
+            assert_eq!(outcome.number, block.number);
+            assert_eq!(outcome.hash, block.hash());
+            assert_eq!(
+                outcome.gas_used,
+                receipts.last().map_or(0, |r| r.receipt.cumulative_gas_used)
+            );
+            assert_eq!(outcome.receipts, receipts);
+            let state_root = (block.number == 5).then_some(last_block.state_root);
+            assert_eq!(outcome.state_root, state_root);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Inspectors that can be attached to the execution stage of `reth debug execution`.
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use clap::ValueEnum;
+use reth_chainspec::ChainSpec;
+use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
+use reth_primitives::{
+    revm_primitives::{BlockEnv, Bytes, CfgEnvWithHandlerCfg, Env, TxEnv},
+    Address, Header, TransactionSigned, TransactionSignedEcRecovered, U256,
+};
+use reth_revm::{
+    interpreter::{opcode, Interpreter, OpCode},
+    Database, Evm, EvmContext, GetInspector, Inspector,
+};
+use tracing::info;
+
+/// The built-in inspectors of `reth debug execution --inspect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InspectKind {
+    /// Counts how often every opcode was executed.
+    Opcodes,
+    /// Counts how often storage was read and written, i.e. `SLOAD` and `SSTORE` executions.
+    Storage,
+}
+
+/// An [`Inspector`] that aggregates opcode counts over all blocks it is attached to.
+///
+/// Clones share the same counters, so the inspector can be installed into every EVM created by
+/// the executor, see [`InspectorEvmConfig`].
+#[derive(Debug, Clone)]
+pub struct ExecutionStatsInspector {
+    kind: InspectKind,
+    opcodes: Arc<[AtomicU64; 256]>,
+}
+
+impl ExecutionStatsInspector {
+    /// Creates a new inspector with all counters at zero.
+    pub fn new(kind: InspectKind) -> Self {
+        Self { kind, opcodes: Arc::new(std::array::from_fn(|_| AtomicU64::new(0))) }
+    }
+
+    /// Returns how often the given opcode was executed.
+    pub fn count(&self, opcode: u8) -> u64 {
+        self.opcodes[opcode as usize].load(Ordering::Relaxed)
+    }
+
+    /// Logs the aggregated stats.
+    pub fn report(&self) {
+        match self.kind {
+            InspectKind::Opcodes => {
+                let mut counts = (0..=u8::MAX)
+                    .map(|opcode| (opcode, self.count(opcode)))
+                    .filter(|(_, count)| *count > 0)
+                    .collect::<Vec<_>>();
+                counts.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+
+                let total = counts.iter().map(|(_, count)| count).sum::<u64>();
+                info!(target: "reth::cli", total, "Executed opcodes");
+                for (opcode, count) in counts {
+                    info!(target: "reth::cli", opcode = OpCode::name_by_op(opcode), count, "Opcode count");
+                }
+            }
+            InspectKind::Storage => {
+                info!(
+                    target: "reth::cli",
+                    sload = self.count(opcode::SLOAD),
+                    sstore = self.count(opcode::SSTORE),
+                    "Executed storage operations"
+                );
+            }
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for ExecutionStatsInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        self.opcodes[interp.current_opcode() as usize].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// An EVM config that installs an [`ExecutionStatsInspector`] into every EVM it creates, and
+/// otherwise behaves like the wrapped config.
+#[derive(Debug, Clone)]
+pub struct InspectorEvmConfig<EvmConfig> {
+    evm_config: EvmConfig,
+    inspector: ExecutionStatsInspector,
+}
+
+impl<EvmConfig> InspectorEvmConfig<EvmConfig> {
+    /// Creates a new config that attaches the given inspector to the EVMs of `evm_config`.
+    pub const fn new(evm_config: EvmConfig, inspector: ExecutionStatsInspector) -> Self {
+        Self { evm_config, inspector }
+    }
+}
+
+impl<EvmConfig: ConfigureEvmEnv> ConfigureEvmEnv for InspectorEvmConfig<EvmConfig> {
+    fn tx_env(&self, transaction: &TransactionSignedEcRecovered) -> TxEnv {
+        self.evm_config.tx_env(transaction)
+    }
+
+    fn fill_tx_env(&self, tx_env: &mut TxEnv, transaction: &TransactionSigned, sender: Address) {
+        self.evm_config.fill_tx_env(tx_env, transaction, sender)
+    }
+
+    fn fill_tx_env_system_contract_call(
+        &self,
+        env: &mut Env,
+        caller: Address,
+        contract: Address,
+        data: Bytes,
+    ) {
+        self.evm_config.fill_tx_env_system_contract_call(env, caller, contract, data)
+    }
+
+    fn fill_cfg_env(
+        &self,
+        cfg_env: &mut CfgEnvWithHandlerCfg,
+        chain_spec: &ChainSpec,
+        header: &Header,
+        total_difficulty: U256,
+    ) {
+        self.evm_config.fill_cfg_env(cfg_env, chain_spec, header, total_difficulty)
+    }
+
+    fn fill_block_env(&self, block_env: &mut BlockEnv, header: &Header, after_merge: bool) {
+        self.evm_config.fill_block_env(block_env, header, after_merge)
+    }
+
+    fn fill_cfg_and_block_env(
+        &self,
+        cfg: &mut CfgEnvWithHandlerCfg,
+        block_env: &mut BlockEnv,
+        chain_spec: &ChainSpec,
+        header: &Header,
+        total_difficulty: U256,
+    ) {
+        self.evm_config.fill_cfg_and_block_env(cfg, block_env, chain_spec, header, total_difficulty)
+    }
+}
+
+impl<EvmConfig: ConfigureEvm> ConfigureEvm for InspectorEvmConfig<EvmConfig> {
+    type DefaultExternalContext<'a> = ExecutionStatsInspector;
+
+    fn evm<DB: Database>(&self, db: DB) -> Evm<'_, Self::DefaultExternalContext<'_>, DB> {
+        self.evm_config.evm_with_inspector(db, self.inspector.clone())
+    }
+
+    fn evm_with_inspector<DB, I>(&self, db: DB, inspector: I) -> Evm<'_, I, DB>
+    where
+        DB: Database,
+        I: GetInspector<DB>,
+    {
+        self.evm_config.evm_with_inspector(db, inspector)
+    }
+
+    fn default_external_context<'a>(&self) -> Self::DefaultExternalContext<'a> {
+        self.inspector.clone()
+    }
+}
@@ -4,10 +4,15 @@ use clap::{Parser, Subcommand};
 use reth_cli_runner::CliContext;
 
 mod build_block;
+mod endpoint;
 mod execution;
 mod in_memory_merkle;
+mod inspect;
 mod merkle;
+mod outcome;
 mod replay_engine;
+mod report;
+mod spec;
 
 /// `reth debug` command
 #[derive(Debug, Parser)]
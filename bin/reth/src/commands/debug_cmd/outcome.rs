@@ -0,0 +1,88 @@
+//! Per-block execution outcomes of `reth debug execution --export-outcome`.
+
+use std::path::{Path, PathBuf};
+
+use alloy_rlp::{Encodable, RlpDecodable, RlpEncodable};
+use reth_primitives::{proofs::calculate_receipt_root, BlockNumber, Bloom, ReceiptWithBloom, B256};
+use reth_provider::{BlockHashReader, ReceiptProvider};
+
+/// The outcome of executing a single block, exported as one RLP encoded list per block.
+///
+/// The list holds, in order: the block number and hash, the gas used, the receipts root and logs
+/// bloom computed from the receipts, the receipts in their network encoding, and the state root
+/// if it was exported for the block. The encoding only depends on the execution results, so
+/// the outcomes of two runs, or of two clients, can be compared byte by byte.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[rlp(trailing)]
+pub struct BlockOutcome {
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The hash of the block.
+    pub hash: B256,
+    /// The gas used by all transactions of the block.
+    pub gas_used: u64,
+    /// The root of the receipts of the block.
+    pub receipts_root: B256,
+    /// The combined bloom of the logs of all receipts.
+    pub logs_bloom: Bloom,
+    /// The receipts of the block, in transaction order.
+    pub receipts: Vec<ReceiptWithBloom>,
+    /// The state root after executing the block, if it was exported for the block.
+    pub state_root: Option<B256>,
+}
+
+impl BlockOutcome {
+    /// Reads the outcome of the given executed block from the provider.
+    pub fn from_provider<Provider>(provider: &Provider, number: BlockNumber) -> eyre::Result<Self>
+    where
+        Provider: BlockHashReader + ReceiptProvider,
+    {
+        let hash = provider
+            .block_hash(number)?
+            .ok_or_else(|| eyre::eyre!("Block {number} is not stored locally"))?;
+        let receipts: Vec<_> = provider
+            .receipts_by_block(number.into())?
+            .ok_or_else(|| eyre::eyre!("Receipts of block {number} not found"))?
+            .into_iter()
+            .map(|receipt| receipt.with_bloom())
+            .collect();
+
+        Ok(Self {
+            number,
+            hash,
+            gas_used: receipts.last().map_or(0, |receipt| receipt.receipt.cumulative_gas_used),
+            receipts_root: calculate_receipt_root(&receipts),
+            logs_bloom: receipts.iter().fold(Bloom::ZERO, |bloom, receipt| bloom | receipt.bloom),
+            receipts,
+            state_root: None,
+        })
+    }
+
+    /// Sets the state root after executing the block.
+    pub const fn with_state_root(mut self, state_root: B256) -> Self {
+        self.state_root = Some(state_root);
+        self
+    }
+}
+
+/// Writes every [`BlockOutcome`] to its own file in a directory, named after the block number.
+#[derive(Debug)]
+pub struct OutcomeExporter {
+    dir: PathBuf,
+}
+
+impl OutcomeExporter {
+    /// Creates the directory, if it doesn't exist yet.
+    pub fn new(dir: &Path) -> eyre::Result<Self> {
+        reth_fs_util::create_dir_all(dir)?;
+        Ok(Self { dir: dir.to_path_buf() })
+    }
+
+    /// Writes the RLP encoded outcome to `<block number>.rlp`, replacing an existing file.
+    pub fn write(&self, outcome: &BlockOutcome) -> eyre::Result<()> {
+        let mut encoded = Vec::with_capacity(outcome.length());
+        outcome.encode(&mut encoded);
+        reth_fs_util::write(self.dir.join(format!("{}.rlp", outcome.number)), encoded)?;
+        Ok(())
+    }
+}
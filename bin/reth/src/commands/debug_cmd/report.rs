@@ -0,0 +1,378 @@
+//! Per-run reports of `reth debug execution --report-path`.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use alloy_rlp::Encodable;
+use futures::{FutureExt, Stream, StreamExt};
+use reth_network_api::PeerId;
+use reth_network_p2p::{
+    bodies::client::{BodiesClient, BodiesFut},
+    download::DownloadClient,
+    headers::client::{HeadersClient, HeadersFut, HeadersRequest},
+    priority::Priority,
+};
+use reth_primitives::{BlockNumber, B256};
+use reth_stages::{PipelineEvent, StageId};
+use reth_tasks::TaskExecutor;
+use serde::Serialize;
+use tokio::sync::{mpsc, oneshot};
+
+/// The stats of a single pipeline run, written as one JSON line to the report file.
+#[derive(Debug, Serialize)]
+pub struct RunReport {
+    /// The first block of the run.
+    pub from: BlockNumber,
+    /// The last block of the run.
+    pub to: BlockNumber,
+    /// The duration of the pipeline run in seconds.
+    pub duration_secs: f64,
+    /// The time spent in each stage during the run in seconds, by stage.
+    pub stage_durations_secs: BTreeMap<String, f64>,
+    /// The number of headers received from peers during the run.
+    pub headers_downloaded: u64,
+    /// The number of block bodies received from peers during the run.
+    pub bodies_downloaded: u64,
+    /// The RLP encoded size of the received headers and bodies in bytes.
+    pub downloaded_bytes: u64,
+}
+
+/// Writes a [`RunReport`] per pipeline run as a JSON line to a file.
+#[derive(Debug)]
+pub struct ReportWriter {
+    file: BufWriter<File>,
+}
+
+impl ReportWriter {
+    /// Creates the report file at the given path, truncating an existing file.
+    pub fn new(path: &Path) -> eyre::Result<Self> {
+        Ok(Self { file: BufWriter::new(reth_fs_util::create_file(path)?) })
+    }
+
+    /// Appends the report to the file.
+    ///
+    /// The file is flushed after every report, so the reports of completed runs are kept if the
+    /// command is interrupted.
+    pub fn write(&mut self, report: &RunReport) -> eyre::Result<()> {
+        serde_json::to_writer(&mut self.file, report)?;
+        self.file.write_all(b"\n")?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+/// Collects the stats of every pipeline run, see [`RunReport`].
+#[derive(Debug)]
+pub struct RunRecorder {
+    /// Requests for the stage durations recorded since the last request.
+    stage_durations: mpsc::UnboundedSender<oneshot::Sender<BTreeMap<String, Duration>>>,
+    downloads: DownloadStats,
+}
+
+impl RunRecorder {
+    /// Spawns a task that records the stage runs reported by the given pipeline events, and
+    /// returns a recorder that combines them with the downloads recorded by the given stats.
+    pub fn spawn(
+        events: impl Stream<Item = PipelineEvent> + Unpin + Send + 'static,
+        downloads: DownloadStats,
+        task_executor: &TaskExecutor,
+    ) -> Self {
+        let (stage_durations, requests) = mpsc::unbounded_channel();
+        task_executor.spawn(record_stage_run_durations(events, requests));
+        Self { stage_durations, downloads }
+    }
+
+    /// Returns the report of the run that just finished, and resets the stats for the next run.
+    ///
+    /// This must be called after the pipeline run returned, so that all of its events were
+    /// emitted.
+    pub async fn finish_run(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        duration: Duration,
+    ) -> RunReport {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.stage_durations.send(tx);
+        let stage_durations = rx.await.unwrap_or_default();
+
+        let DownloadCounts { headers, bodies, bytes } = self.downloads.take();
+        RunReport {
+            from,
+            to,
+            duration_secs: duration.as_secs_f64(),
+            stage_durations_secs: stage_durations
+                .into_iter()
+                .map(|(stage, duration)| (stage, duration.as_secs_f64()))
+                .collect(),
+            headers_downloaded: headers,
+            bodies_downloaded: bodies,
+            downloaded_bytes: bytes,
+        }
+    }
+}
+
+/// Sums up the time spent in each stage, and hands the sums out on request.
+///
+/// Like the stage duration metrics, unwinds and failed runs are not recorded.
+///
+/// Pending events are always processed before a request, so a request that is sent after a
+/// pipeline run returned covers all stage runs of the pipeline run.
+async fn record_stage_run_durations<St>(
+    mut events: St,
+    mut requests: mpsc::UnboundedReceiver<oneshot::Sender<BTreeMap<String, Duration>>>,
+) where
+    St: Stream<Item = PipelineEvent> + Unpin,
+{
+    let mut running = HashMap::<StageId, Instant>::new();
+    let mut durations = BTreeMap::<String, Duration>::new();
+
+    loop {
+        tokio::select! {
+            biased;
+            Some(event) = events.next() => match event {
+                PipelineEvent::Run { stage_id, .. } => {
+                    running.insert(stage_id, Instant::now());
+                }
+                PipelineEvent::Ran { stage_id, .. } => {
+                    let Some(started_at) = running.remove(&stage_id) else { continue };
+                    *durations.entry(stage_id.to_string()).or_default() += started_at.elapsed();
+                }
+                PipelineEvent::Error { stage_id } => {
+                    running.remove(&stage_id);
+                }
+                _ => {}
+            },
+            Some(response_tx) = requests.recv() => {
+                let _ = response_tx.send(std::mem::take(&mut durations));
+            }
+            else => break,
+        }
+    }
+}
+
+/// The number of downloaded headers and bodies, see [`DownloadStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DownloadCounts {
+    /// The number of received headers.
+    pub headers: u64,
+    /// The number of received block bodies.
+    pub bodies: u64,
+    /// The RLP encoded size of the received headers and bodies in bytes.
+    pub bytes: u64,
+}
+
+/// Shareable counters of the headers and bodies received by a [`CountingClient`].
+#[derive(Debug, Clone, Default)]
+pub struct DownloadStats {
+    headers: Arc<AtomicU64>,
+    bodies: Arc<AtomicU64>,
+    bytes: Arc<AtomicU64>,
+}
+
+impl DownloadStats {
+    /// Returns the counts since the last call, and resets the counters.
+    pub fn take(&self) -> DownloadCounts {
+        DownloadCounts {
+            headers: self.headers.swap(0, Ordering::Relaxed),
+            bodies: self.bodies.swap(0, Ordering::Relaxed),
+            bytes: self.bytes.swap(0, Ordering::Relaxed),
+        }
+    }
+
+    fn record<T: Encodable>(&self, counter: &AtomicU64, items: &[T]) {
+        counter.fetch_add(items.len() as u64, Ordering::Relaxed);
+        self.bytes
+            .fetch_add(items.iter().map(|item| item.length() as u64).sum(), Ordering::Relaxed);
+    }
+}
+
+/// A download client that counts the headers and bodies that the wrapped client receives, e.g. to
+/// tell how much of a pipeline run was spent on downloading.
+///
+/// Only successful responses are counted, including responses that fail validation later on.
+#[derive(Debug, Clone)]
+pub struct CountingClient<C> {
+    client: C,
+    stats: DownloadStats,
+}
+
+impl<C> CountingClient<C> {
+    /// Wraps the given client, recording the received headers and bodies in the given stats.
+    pub const fn new(client: C, stats: DownloadStats) -> Self {
+        Self { client, stats }
+    }
+}
+
+impl<C: DownloadClient> DownloadClient for CountingClient<C> {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        self.client.report_bad_message(peer_id)
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.client.num_connected_peers()
+    }
+}
+
+impl<C> HeadersClient for CountingClient<C>
+where
+    C: HeadersClient,
+    C::Output: 'static,
+{
+    type Output = HeadersFut;
+
+    fn get_headers_with_priority(
+        &self,
+        request: HeadersRequest,
+        priority: Priority,
+    ) -> Self::Output {
+        let stats = self.stats.clone();
+        Box::pin(self.client.get_headers_with_priority(request, priority).inspect(
+            move |response| {
+                if let Ok(headers) = response {
+                    stats.record(&stats.headers, headers.data());
+                }
+            },
+        ))
+    }
+}
+
+impl<C> BodiesClient for CountingClient<C>
+where
+    C: BodiesClient,
+    C::Output: 'static,
+{
+    type Output = BodiesFut;
+
+    fn get_block_bodies_with_priority(
+        &self,
+        hashes: Vec<B256>,
+        priority: Priority,
+    ) -> Self::Output {
+        let stats = self.stats.clone();
+        Box::pin(self.client.get_block_bodies_with_priority(hashes, priority).inspect(
+            move |response| {
+                if let Ok(bodies) = response {
+                    stats.record(&stats.bodies, bodies.data());
+                }
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_network_p2p::{
+        error::RequestError, headers::client::HeadersDirection, test_utils::TestHeadersClient,
+    };
+    use reth_primitives::Header;
+    use reth_stages::{ExecOutput, PipelineStagesProgress, StageCheckpoint};
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    fn run(stage_id: StageId) -> PipelineEvent {
+        PipelineEvent::Run {
+            pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 1 },
+            stage_id,
+            checkpoint: None,
+            target: Some(10),
+        }
+    }
+
+    fn ran(stage_id: StageId) -> PipelineEvent {
+        PipelineEvent::Ran {
+            pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 1 },
+            stage_id,
+            result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+        }
+    }
+
+    #[tokio::test]
+    async fn counting_client_counts_received_headers() {
+        let headers =
+            (1..=3).map(|number| Header { number, ..Default::default() }).collect::<Vec<_>>();
+        let client = TestHeadersClient::default();
+        client.extend(headers.clone()).await;
+
+        let stats = DownloadStats::default();
+        let counting = CountingClient::new(client.clone(), stats.clone());
+        let request =
+            HeadersRequest { start: 1u64.into(), limit: 2, direction: HeadersDirection::Rising };
+        counting.get_headers_with_priority(request.clone(), Priority::Normal).await.unwrap();
+
+        let bytes = headers[..2].iter().map(|header| header.length() as u64).sum();
+        assert_eq!(stats.take(), DownloadCounts { headers: 2, bodies: 0, bytes });
+        // the counters are reset
+        assert_eq!(stats.take(), DownloadCounts::default());
+
+        // failed requests are not counted
+        client.set_error(RequestError::Timeout).await;
+        assert!(counting.get_headers_with_priority(request, Priority::Normal).await.is_err());
+        assert_eq!(stats.take(), DownloadCounts::default());
+    }
+
+    #[tokio::test]
+    async fn records_stage_run_durations() {
+        let (events_tx, events) = mpsc::unbounded_channel();
+        let (requests_tx, requests) = mpsc::unbounded_channel();
+        tokio::spawn(record_stage_run_durations(UnboundedReceiverStream::new(events), requests));
+
+        events_tx.send(run(StageId::Execution)).unwrap();
+        events_tx.send(ran(StageId::Execution)).unwrap();
+        // failed stage runs are not recorded
+        events_tx.send(run(StageId::MerkleExecute)).unwrap();
+        events_tx.send(PipelineEvent::Error { stage_id: StageId::MerkleExecute }).unwrap();
+        events_tx.send(ran(StageId::MerkleExecute)).unwrap();
+
+        let (tx, rx) = oneshot::channel();
+        requests_tx.send(tx).unwrap();
+        let durations = rx.await.unwrap();
+        assert_eq!(durations.keys().collect::<Vec<_>>(), vec!["Execution"]);
+
+        // the durations are reset after every request
+        let (tx, rx) = oneshot::channel();
+        requests_tx.send(tx).unwrap();
+        assert!(rx.await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn writes_a_json_line_per_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.jsonl");
+
+        let mut writer = ReportWriter::new(&path).unwrap();
+        for from in [1, 11] {
+            writer
+                .write(&RunReport {
+                    from,
+                    to: from + 9,
+                    duration_secs: 1.5,
+                    stage_durations_secs: BTreeMap::from([("Execution".to_string(), 1.0)]),
+                    headers_downloaded: 10,
+                    bodies_downloaded: 10,
+                    downloaded_bytes: 1024,
+                })
+                .unwrap();
+        }
+
+        let reports = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0]["from"], 1);
+        assert_eq!(reports[1]["from"], 11);
+        assert_eq!(reports[1]["to"], 20);
+        assert_eq!(reports[1]["stage_durations_secs"]["Execution"], 1.0);
+    }
+}
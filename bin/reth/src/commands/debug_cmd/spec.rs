@@ -0,0 +1,211 @@
+//! EVM spec overrides of `reth debug execution --force-spec`.
+
+use reth_chainspec::ChainSpec;
+use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
+use reth_primitives::{
+    revm_primitives::{
+        Account, AccountStatus, BlockEnv, Bytes, CfgEnvWithHandlerCfg, Env, EvmStorageSlot,
+        HashMap, SpecId, TxEnv,
+    },
+    Address, Header, TransactionSigned, TransactionSignedEcRecovered, U256,
+};
+use reth_revm::{
+    db::{BundleState, CacheDB},
+    Database, DatabaseCommit, DatabaseRef, Evm, GetInspector,
+};
+
+/// Parses the name of an EVM spec, e.g. `cancun` or `prague`.
+///
+/// Names are matched case-insensitively and without spaces, dashes or underscores, so both
+/// `gray-glacier` and `GrayGlacier` are accepted.
+pub fn parse_spec_id(value: &str) -> eyre::Result<SpecId> {
+    let normalize = |name: &str| name.replace([' ', '-', '_'], "").to_ascii_lowercase();
+    let name = normalize(value);
+    (0..=u8::MAX)
+        .filter_map(SpecId::n)
+        .find(|spec_id| normalize(<&str>::from(*spec_id)) == name)
+        .ok_or_else(|| eyre::eyre!("Unknown EVM spec: {value}"))
+}
+
+/// An EVM config that executes every block with the given spec instead of the spec that is
+/// active for the block, and otherwise behaves like the wrapped config.
+///
+/// The block env is filled for the forced spec, i.e. post-merge specs use the block's mix hash as
+/// `prevrandao`, and Cancun and later specs use an excess blob gas of zero for blocks without one.
+/// System calls and block rewards still follow the activation schedule of the chain spec.
+///
+/// If no spec is given, the wrapped config is used as is.
+#[derive(Debug, Clone)]
+pub struct ForcedSpecEvmConfig<EvmConfig> {
+    evm_config: EvmConfig,
+    spec_id: Option<SpecId>,
+}
+
+impl<EvmConfig> ForcedSpecEvmConfig<EvmConfig> {
+    /// Creates a new config that forces the given spec on the EVMs of `evm_config`.
+    pub const fn new(evm_config: EvmConfig, spec_id: Option<SpecId>) -> Self {
+        Self { evm_config, spec_id }
+    }
+}
+
+impl<EvmConfig: ConfigureEvmEnv> ConfigureEvmEnv for ForcedSpecEvmConfig<EvmConfig> {
+    fn tx_env(&self, transaction: &TransactionSignedEcRecovered) -> TxEnv {
+        self.evm_config.tx_env(transaction)
+    }
+
+    fn fill_tx_env(&self, tx_env: &mut TxEnv, transaction: &TransactionSigned, sender: Address) {
+        self.evm_config.fill_tx_env(tx_env, transaction, sender)
+    }
+
+    fn fill_tx_env_system_contract_call(
+        &self,
+        env: &mut Env,
+        caller: Address,
+        contract: Address,
+        data: Bytes,
+    ) {
+        self.evm_config.fill_tx_env_system_contract_call(env, caller, contract, data)
+    }
+
+    fn fill_cfg_env(
+        &self,
+        cfg_env: &mut CfgEnvWithHandlerCfg,
+        chain_spec: &ChainSpec,
+        header: &Header,
+        total_difficulty: U256,
+    ) {
+        self.evm_config.fill_cfg_env(cfg_env, chain_spec, header, total_difficulty);
+        if let Some(spec_id) = self.spec_id {
+            cfg_env.handler_cfg.spec_id = spec_id;
+        }
+    }
+
+    fn fill_block_env(&self, block_env: &mut BlockEnv, header: &Header, after_merge: bool) {
+        self.evm_config.fill_block_env(block_env, header, after_merge)
+    }
+
+    fn fill_cfg_and_block_env(
+        &self,
+        cfg: &mut CfgEnvWithHandlerCfg,
+        block_env: &mut BlockEnv,
+        chain_spec: &ChainSpec,
+        header: &Header,
+        total_difficulty: U256,
+    ) {
+        self.evm_config.fill_cfg_and_block_env(
+            cfg,
+            block_env,
+            chain_spec,
+            header,
+            total_difficulty,
+        );
+        let Some(spec_id) = self.spec_id else { return };
+
+        cfg.handler_cfg.spec_id = spec_id;
+        self.evm_config.fill_block_env(block_env, header, spec_id >= SpecId::MERGE);
+        if spec_id >= SpecId::CANCUN && block_env.blob_excess_gas_and_price.is_none() {
+            block_env.set_blob_excess_gas_and_price(0);
+        }
+    }
+}
+
+impl<EvmConfig: ConfigureEvm> ConfigureEvm for ForcedSpecEvmConfig<EvmConfig> {
+    type DefaultExternalContext<'a> = EvmConfig::DefaultExternalContext<'a>;
+
+    fn evm<DB: Database>(&self, db: DB) -> Evm<'_, Self::DefaultExternalContext<'_>, DB> {
+        self.evm_config.evm(db)
+    }
+
+    fn evm_with_inspector<DB, I>(&self, db: DB, inspector: I) -> Evm<'_, I, DB>
+    where
+        DB: Database,
+        I: GetInspector<DB>,
+    {
+        self.evm_config.evm_with_inspector(db, inspector)
+    }
+
+    fn default_external_context<'a>(&self) -> Self::DefaultExternalContext<'a> {
+        self.evm_config.default_external_context()
+    }
+}
+
+/// Applies the state changes of an executed block to the given in-memory overlay, so that the
+/// next block executes on top of them.
+pub fn commit_bundle<ExtDB: DatabaseRef>(db: &mut CacheDB<ExtDB>, bundle: BundleState) {
+    db.contracts.extend(bundle.contracts);
+
+    let changes = bundle
+        .state
+        .into_iter()
+        .map(|(address, account)| {
+            let mut status = AccountStatus::Touched;
+            if account.info.is_none() {
+                status |= AccountStatus::SelfDestructed;
+            } else if account.was_destroyed() {
+                status |= AccountStatus::Created;
+            }
+
+            let storage = account
+                .storage
+                .into_iter()
+                .map(|(slot, value)| {
+                    (
+                        slot,
+                        EvmStorageSlot::new_changed(
+                            value.previous_or_original_value,
+                            value.present_value,
+                        ),
+                    )
+                })
+                .collect();
+
+            (address, Account { info: account.info.unwrap_or_default(), storage, status })
+        })
+        .collect::<HashMap<_, _>>();
+
+    db.commit(changes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+    use reth_node_ethereum::EthEvmConfig;
+    use reth_primitives::{revm_primitives::CfgEnv, B256};
+
+    #[test]
+    fn parses_spec_names() {
+        assert_eq!(parse_spec_id("cancun").unwrap(), SpecId::CANCUN);
+        assert_eq!(parse_spec_id("Gray-Glacier").unwrap(), SpecId::GRAY_GLACIER);
+        assert_eq!(parse_spec_id("gray_glacier").unwrap(), SpecId::GRAY_GLACIER);
+        assert!(parse_spec_id("unknown").is_err());
+    }
+
+    #[test]
+    fn forces_spec_and_fills_block_env_for_it() {
+        // a pre-merge block without blob gas
+        let header = Header { number: 1, mix_hash: B256::with_last_byte(1), ..Default::default() };
+        let fill = |spec_id| {
+            let mut cfg = CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::LATEST);
+            let mut block_env = BlockEnv::default();
+            ForcedSpecEvmConfig::new(EthEvmConfig::default(), spec_id).fill_cfg_and_block_env(
+                &mut cfg,
+                &mut block_env,
+                &MAINNET,
+                &header,
+                U256::ZERO,
+            );
+            (cfg.handler_cfg.spec_id, block_env)
+        };
+
+        let (spec_id, block_env) = fill(None);
+        assert_eq!(spec_id, SpecId::FRONTIER);
+        assert_eq!(block_env.prevrandao, None);
+        assert!(block_env.blob_excess_gas_and_price.is_none());
+
+        let (spec_id, block_env) = fill(Some(SpecId::CANCUN));
+        assert_eq!(spec_id, SpecId::CANCUN);
+        assert_eq!(block_env.prevrandao, Some(header.mix_hash));
+        assert_eq!(block_env.get_blob_excess_gas(), Some(0));
+    }
+}
@@ -2,12 +2,20 @@
 
 /// Creates the block executor type based on the configured feature.
 ///
+/// The optional second argument is a function that wraps the default EVM config of the executor.
+///
 /// Note(mattsse): This is incredibly horrible and will be replaced
 #[cfg(not(feature = "optimism"))]
 macro_rules! block_executor {
     ($chain_spec:expr) => {
         reth_node_ethereum::EthExecutorProvider::ethereum($chain_spec)
     };
+    ($chain_spec:expr, $wrap_evm_config:expr) => {
+        reth_node_ethereum::EthExecutorProvider::new(
+            $chain_spec,
+            ($wrap_evm_config)(reth_node_ethereum::EthEvmConfig::default()),
+        )
+    };
 }
 
 #[cfg(feature = "optimism")]
@@ -15,6 +23,12 @@ macro_rules! block_executor {
     ($chain_spec:expr) => {
         reth_node_optimism::OpExecutorProvider::optimism($chain_spec)
     };
+    ($chain_spec:expr, $wrap_evm_config:expr) => {
+        reth_node_optimism::OpExecutorProvider::new(
+            $chain_spec,
+            ($wrap_evm_config)(reth_node_optimism::OptimismEvmConfig::default()),
+        )
+    };
 }
 
 pub(crate) use block_executor;
@@ -1,15 +1,25 @@
 //! Node builder setup tests.
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use reth_db::{
     test_utils::{create_test_rw_db, TempDatabase},
     DatabaseEnv,
 };
-use reth_node_builder::{EngineNodeLauncher, FullNodeComponents, NodeBuilder, NodeConfig};
-use reth_node_ethereum::node::{EthereumAddOns, EthereumNode};
+use reth_network::{EthVersion, HelloMessageWithProtocols, NetworkHandle};
+use reth_node_api::{FullNodeTypes, NodeTypes};
+use reth_node_builder::{
+    components::NetworkBuilder, BuilderContext, EngineNodeLauncher, FullNodeComponents,
+    NodeBuilder, NodeConfig,
+};
+use reth_node_core::args::{DiscoveryArgs, NetworkArgs};
+use reth_node_ethereum::{
+    node::{EthereumAddOns, EthereumNetworkBuilder, EthereumNode},
+    EthEngineTypes,
+};
 use reth_provider::providers::BlockchainProvider2;
 use reth_tasks::TaskManager;
+use reth_transaction_pool::TransactionPool;
 
 #[test]
 fn test_basic_setup() {
@@ -64,3 +74,58 @@ fn test_node_setup() {
     let _builder =
         NodeBuilder::new(config).with_database(db).node(EthereumNode::default()).check_launch();
 }
+
+/// Ethereum node types that only advertise eth/68.
+#[derive(Debug, Clone, Copy, Default)]
+struct Eth68Node;
+
+impl NodeTypes for Eth68Node {
+    type Primitives = ();
+    type Engine = EthEngineTypes;
+
+    fn protocol_versions() -> Vec<EthVersion> {
+        vec![EthVersion::Eth68]
+    }
+}
+
+/// Builds the ethereum network and records the hello message of its config.
+#[derive(Debug, Clone, Default)]
+struct RecordingNetworkBuilder {
+    hello_message: Arc<Mutex<Option<HelloMessageWithProtocols>>>,
+}
+
+impl<Node, Pool> NetworkBuilder<Node, Pool> for RecordingNetworkBuilder
+where
+    Node: FullNodeTypes,
+    Pool: TransactionPool + Unpin + 'static,
+{
+    async fn build_network(
+        self,
+        ctx: &BuilderContext<Node>,
+        pool: Pool,
+    ) -> eyre::Result<NetworkHandle> {
+        *self.hello_message.lock().unwrap() = Some(ctx.network_config()?.hello_message);
+        EthereumNetworkBuilder::default().build_network(ctx, pool).await
+    }
+}
+
+#[tokio::test]
+async fn test_node_types_protocol_versions() {
+    let tasks = TaskManager::current();
+    let config = NodeConfig::test().with_network(NetworkArgs {
+        discovery: DiscoveryArgs { disable_discovery: true, ..DiscoveryArgs::default() },
+        ..NetworkArgs::default()
+    });
+    let network_builder = RecordingNetworkBuilder::default();
+    let _handle = NodeBuilder::new(config)
+        .testing_node(tasks.executor())
+        .with_types::<Eth68Node>()
+        .with_components(EthereumNode::components().network(network_builder.clone()))
+        .with_add_ons::<EthereumAddOns>()
+        .launch()
+        .await
+        .unwrap();
+
+    let hello_message = network_builder.hello_message.lock().unwrap().take().unwrap();
+    assert_eq!(hello_message.protocols, vec![EthVersion::Eth68.into()]);
+}
@@ -137,7 +137,7 @@ mod session;
 mod state;
 mod swarm;
 
-pub use reth_eth_wire::{DisconnectReason, HelloMessageWithProtocols};
+pub use reth_eth_wire::{DisconnectReason, EthVersion, HelloMessageWithProtocols};
 pub use reth_network_api::{
     BlockDownloaderProvider, DiscoveredEvent, DiscoveryEvent, NetworkEvent,
     NetworkEventListenerProvider, NetworkInfo, PeerRequest, PeerRequestSender, Peers, PeersInfo,
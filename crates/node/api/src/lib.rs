@@ -25,4 +25,6 @@ pub mod node;
 pub use node::*;
 
 // re-export for convenience
+pub use reth_eth_wire_types::EthVersion;
 pub use reth_provider::FullProvider;
+pub use reth_rpc_server_types::RpcModuleSelection;
@@ -6,10 +6,12 @@ use reth_db_api::{
     database::Database,
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
 };
+use reth_eth_wire_types::EthVersion;
 use reth_evm::execute::BlockExecutorProvider;
 use reth_network_api::FullNetwork;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_provider::FullProvider;
+use reth_rpc_server_types::RpcModuleSelection;
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::TransactionPool;
 
@@ -26,6 +28,22 @@ pub trait NodeTypes: Send + Sync + Unpin + 'static {
     type Primitives: NodePrimitives;
     /// The node's engine types, defining the interaction with the consensus engine.
     type Engine: EngineTypes;
+
+    /// Returns the `eth` wire protocol versions the node advertises to its peers, in order of
+    /// preference.
+    ///
+    /// By default this is eth/68, eth/67 and eth/66.
+    fn protocol_versions() -> Vec<EthVersion> {
+        vec![EthVersion::Eth68, EthVersion::Eth67, EthVersion::Eth66]
+    }
+
+    /// Returns the RPC modules that are installed on the http and ws transports if no modules
+    /// are selected explicitly.
+    ///
+    /// By default these are the [standard modules](RpcModuleSelection::STANDARD_MODULES).
+    fn default_rpc_modules() -> RpcModuleSelection {
+        RpcModuleSelection::standard_modules().into()
+    }
 }
 
 /// A [`NodeTypes`] type builder
@@ -103,6 +121,14 @@ where
 {
     type Primitives = Types::Primitives;
     type Engine = Types::Engine;
+
+    fn protocol_versions() -> Vec<EthVersion> {
+        Types::protocol_versions()
+    }
+
+    fn default_rpc_modules() -> RpcModuleSelection {
+        Types::default_rpc_modules()
+    }
 }
 
 impl<Types, DB, Provider> FullNodeTypes for FullNodeTypesAdapter<Types, DB, Provider>
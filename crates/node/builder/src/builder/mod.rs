@@ -621,11 +621,13 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         let builder = self
             .config()
             .network
-            .network_config(
+            .network_config_with_protocols(
                 self.reth_config(),
                 self.config().chain.clone(),
                 secret_key,
                 default_peers_path,
+                // advertise the protocol versions of the configured node types
+                Node::protocol_versions(),
             )
             .with_task_executor(Box::new(self.executor.clone()))
             .set_head(self.head);
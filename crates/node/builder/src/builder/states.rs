@@ -8,7 +8,9 @@
 use std::{fmt, future::Future, marker::PhantomData};
 
 use reth_exex::ExExContext;
-use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes};
+use reth_node_api::{
+    EthVersion, FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes, RpcModuleSelection,
+};
 use reth_node_core::{
     node_config::NodeConfig,
     rpc::eth::{helpers::AddDevSigners, FullEthApiServer},
@@ -91,6 +93,14 @@ pub struct NodeAdapter<T: FullNodeTypes, C: NodeComponents<T>> {
 impl<T: FullNodeTypes, C: NodeComponents<T>> NodeTypes for NodeAdapter<T, C> {
     type Primitives = T::Primitives;
     type Engine = T::Engine;
+
+    fn protocol_versions() -> Vec<EthVersion> {
+        T::protocol_versions()
+    }
+
+    fn default_rpc_modules() -> RpcModuleSelection {
+        T::default_rpc_modules()
+    }
 }
 
 impl<T: FullNodeTypes, C: NodeComponents<T>> FullNodeTypes for NodeAdapter<T, C> {
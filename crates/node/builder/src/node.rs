@@ -4,7 +4,7 @@ pub use reth_node_api::{FullNodeTypes, NodeTypes};
 use std::{marker::PhantomData, sync::Arc};
 
 use reth_chainspec::ChainSpec;
-use reth_node_api::FullNodeComponents;
+use reth_node_api::{EthVersion, FullNodeComponents, RpcModuleSelection};
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
     node_config::NodeConfig,
@@ -13,6 +13,7 @@ use reth_node_core::{
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_provider::ChainSpecProvider;
 use reth_rpc_builder::{auth::AuthServerHandle, RpcServerHandle};
+use reth_rpc_eth_types::EthStateCache;
 use reth_tasks::TaskExecutor;
 
 use crate::{
@@ -62,6 +63,14 @@ where
     type Primitives = N::Primitives;
 
     type Engine = N::Engine;
+
+    fn protocol_versions() -> Vec<EthVersion> {
+        N::protocol_versions()
+    }
+
+    fn default_rpc_modules() -> RpcModuleSelection {
+        N::default_rpc_modules()
+    }
 }
 
 impl<N, C, AO> Node<N> for AnyNode<N, C, AO>
@@ -127,6 +136,14 @@ where
         &self.rpc_server_handles.auth
     }
 
+    /// Returns the [`EthStateCache`] shared by the node's RPC handlers.
+    ///
+    /// This is a cheap handle to the same cache service the `eth` namespace uses, so it can be
+    /// used to warm the cache or read from it without going through the RPC server.
+    pub fn eth_state_cache(&self) -> EthStateCache {
+        self.rpc_registry.eth_cache().clone()
+    }
+
     /// Returns the [`EngineApiClient`] interface for the authenticated engine API.
     ///
     /// This will send authenticated http requests to the node's auth server.
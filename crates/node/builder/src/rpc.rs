@@ -264,7 +264,8 @@ where
     Engine: EngineApiServer<Node::Engine>,
 {
     let auth_config = config.rpc.auth_server_config(jwt_secret)?;
-    let module_config = config.rpc.transport_rpc_module_config();
+    let module_config =
+        config.rpc.transport_rpc_module_config_with_defaults(Node::default_rpc_modules());
     debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
     let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
@@ -274,7 +275,7 @@ where
         .with_events(node.provider().clone())
         .with_executor(node.task_executor().clone())
         .with_evm_config(node.evm_config().clone())
-        .build_with_auth_server(module_config, engine_api, EthApi::eth_api_builder());
+        .build_with_auth_server(module_config, engine_api, EthApi::eth_api_builder())?;
 
     let mut registry = RpcRegistry { registry };
     let ctx = RpcContext {
@@ -16,7 +16,7 @@ use reth_network::{
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
-    HelloMessageWithProtocols, NetworkConfigBuilder, SessionsConfig,
+    EthVersion, HelloMessageWithProtocols, NetworkConfigBuilder, SessionsConfig,
 };
 use reth_network_peers::{mainnet_nodes, TrustedPeer};
 use secp256k1::SecretKey;
@@ -136,12 +136,33 @@ impl NetworkArgs {
     /// 1. --bootnodes flag
     /// 2. Network preset flags (e.g. --holesky)
     /// 3. default to mainnet nodes
+    ///
+    /// The node advertises eth/68, eth/67 and eth/66, see [`Self::network_config_with_protocols`].
     pub fn network_config(
         &self,
         config: &Config,
         chain_spec: Arc<ChainSpec>,
         secret_key: SecretKey,
         default_peers_file: PathBuf,
+    ) -> NetworkConfigBuilder {
+        self.network_config_with_protocols(
+            config,
+            chain_spec,
+            secret_key,
+            default_peers_file,
+            [EthVersion::Eth68, EthVersion::Eth67, EthVersion::Eth66],
+        )
+    }
+
+    /// Build a [`NetworkConfigBuilder`] like [`Self::network_config`], with a hello message that
+    /// advertises the given `eth` protocol versions, in order of preference.
+    pub fn network_config_with_protocols(
+        &self,
+        config: &Config,
+        chain_spec: Arc<ChainSpec>,
+        secret_key: SecretKey,
+        default_peers_file: PathBuf,
+        protocols: impl IntoIterator<Item = EthVersion>,
     ) -> NetworkConfigBuilder {
         let chain_bootnodes = self
             .resolved_bootnodes()
@@ -182,6 +203,7 @@ impl NetworkArgs {
                 builder.hello_message(
                     HelloMessageWithProtocols::builder(peer_id)
                         .client_version(&self.identity)
+                        .protocols(protocols.into_iter().map(Into::into))
                         .build(),
                 )
             })
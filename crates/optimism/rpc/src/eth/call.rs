@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use reth_evm::ConfigureEvm;
 use reth_node_api::FullNodeComponents;
 use reth_primitives::{
@@ -31,6 +33,26 @@ where
         self.inner.gas_cap()
     }
 
+    #[inline]
+    fn call_timeout(&self) -> Option<Duration> {
+        self.inner.call_timeout()
+    }
+
+    #[inline]
+    fn method_gas_limit_override(&self, method: &str) -> Option<u64> {
+        self.inner.method_gas_cap(method)
+    }
+
+    #[inline]
+    fn estimate_gas_buffer_percent(&self) -> u64 {
+        self.inner.estimate_gas_buffer_percent()
+    }
+
+    #[inline]
+    fn on_gas_cap_exceeded(&self, method: &'static str) {
+        self.inner.gas_cap_metrics().increment(method)
+    }
+
     #[inline]
     fn evm_config(&self) -> &impl ConfigureEvm {
         self.inner.evm_config()
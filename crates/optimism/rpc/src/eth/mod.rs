@@ -9,7 +9,7 @@ mod pending_block;
 
 use std::{fmt, sync::Arc};
 
-use alloy_primitives::U256;
+use alloy_primitives::{U256, U64};
 use derive_more::Deref;
 use reth_evm::ConfigureEvm;
 use reth_network_api::NetworkInfo;
@@ -26,7 +26,7 @@ use reth_rpc_eth_api::{
     },
     EthApiTypes,
 };
-use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle};
+use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle, ProofPermitMetrics};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskExecutor, TaskSpawner,
@@ -72,8 +72,7 @@ impl<N: FullNodeComponents> OpEthApi<N> {
     /// Creates a new instance for given context.
     #[allow(clippy::type_complexity)]
     pub fn with_spawner(ctx: &EthApiBuilderCtx<N>) -> Self {
-        let blocking_task_pool =
-            BlockingTaskPool::build().expect("failed to build blocking task pool");
+        let blocking_task_pool = ctx.blocking_task_pool();
 
         let inner = EthApiInner::new(
             ctx.provider.clone(),
@@ -89,7 +88,17 @@ impl<N: FullNodeComponents> OpEthApi<N> {
             ctx.executor.clone(),
             None,
             ctx.config.proof_permits,
-        );
+        )
+        .with_gas_cap_metrics(ctx.gas_cap_metrics.clone())
+        .with_proof_permit_metrics(ctx.proof_permit_metrics.clone())
+        .with_max_fee_history_blocks(ctx.config.max_fee_history_blocks)
+        .with_call_timeout(ctx.config.call_timeout)
+        .with_estimate_gas_buffer_percent(ctx.config.estimate_gas_buffer_percent)
+        .with_pending_block_cache_ttl(ctx.config.pending_block_cache_ttl)
+        .with_chain_id_override(ctx.config.chain_id_override)
+        .with_trace_block_window(ctx.config.trace_block_window)
+        .with_method_gas_caps(ctx.config.method_gas_caps.clone())
+        .with_trace_cache(ctx.trace_cache.clone());
 
         Self { inner: Arc::new(inner) }
     }
@@ -126,6 +135,11 @@ where
     fn signers(&self) -> &parking_lot::RwLock<Vec<Box<dyn reth_rpc_eth_api::helpers::EthSigner>>> {
         self.inner.signers()
     }
+
+    #[inline]
+    fn chain_id(&self) -> U64 {
+        U64::from(self.inner.chain_id_override().unwrap_or_else(|| self.network().chain_id()))
+    }
 }
 
 impl<N> SpawnBlocking for OpEthApi<N>
@@ -205,6 +219,11 @@ where
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_proof_window()
     }
+
+    #[inline]
+    fn proof_permit_metrics(&self) -> &ProofPermitMetrics {
+        self.inner.proof_permit_metrics()
+    }
 }
 
 impl<N> EthFees for OpEthApi<N>
@@ -212,6 +231,10 @@ where
     Self: LoadFee,
     N: FullNodeComponents,
 {
+    #[inline]
+    fn max_fee_history_blocks(&self) -> Option<u64> {
+        self.inner.max_fee_history_blocks()
+    }
 }
 
 impl<N> Trace for OpEthApi<N>
@@ -223,6 +246,16 @@ where
     fn evm_config(&self) -> &impl ConfigureEvm {
         self.inner.evm_config()
     }
+
+    #[inline]
+    fn max_trace_window(&self) -> Option<u64> {
+        self.inner.trace_block_window()
+    }
+
+    #[inline]
+    fn trace_cache(&self) -> &EthStateCache {
+        self.inner.trace_cache()
+    }
 }
 
 impl<N: FullNodeComponents> AddDevSigners for OpEthApi<N> {
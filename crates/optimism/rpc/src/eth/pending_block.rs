@@ -1,5 +1,7 @@
 //! Loads OP pending block for a RPC response.   
 
+use std::time::Duration;
+
 use crate::OpEthApi;
 use reth_evm::ConfigureEvm;
 use reth_node_api::FullNodeComponents;
@@ -44,6 +46,11 @@ where
         self.inner.evm_config()
     }
 
+    #[inline]
+    fn pending_block_cache_ttl(&self) -> Option<Duration> {
+        self.inner.pending_block_cache_ttl()
+    }
+
     /// Returns the locally built pending block
     async fn local_pending_block(&self) -> Result<Option<SealedBlockWithSenders>, Self::Error> {
         // See: <https://github.com/ethereum-optimism/op-geth/blob/f2e69450c6eec9c35d56af91389a1c47737206ca/miner/worker.go#L367-L375>
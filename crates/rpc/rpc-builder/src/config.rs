@@ -45,6 +45,14 @@ pub trait RethRpcServerConfig {
     /// settings in the [`TransportRpcModuleConfig`].
     fn transport_rpc_module_config(&self) -> TransportRpcModuleConfig;
 
+    /// Returns the [`TransportRpcModuleConfig`] like [`Self::transport_rpc_module_config`], but
+    /// installs the given modules on the http and ws transports if no modules are selected
+    /// explicitly, instead of the standard modules.
+    fn transport_rpc_module_config_with_defaults(
+        &self,
+        default_modules: RpcModuleSelection,
+    ) -> TransportRpcModuleConfig;
+
     /// Returns the default server builder for http/ws
     fn http_ws_server_builder(&self) -> ServerBuilder<Identity, Identity>;
 
@@ -106,6 +114,7 @@ impl RethRpcServerConfig for RpcServerArgs {
             max_receipts: self.rpc_state_cache.max_receipts,
             max_envs: self.rpc_state_cache.max_envs,
             max_concurrent_db_requests: self.rpc_state_cache.max_concurrent_db_requests,
+            ..Default::default()
         }
     }
 
@@ -122,23 +131,25 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn transport_rpc_module_config(&self) -> TransportRpcModuleConfig {
+        self.transport_rpc_module_config_with_defaults(
+            RpcModuleSelection::standard_modules().into(),
+        )
+    }
+
+    fn transport_rpc_module_config_with_defaults(
+        &self,
+        default_modules: RpcModuleSelection,
+    ) -> TransportRpcModuleConfig {
         let mut config = TransportRpcModuleConfig::default()
             .with_config(RpcModuleConfig::new(self.eth_config()));
 
         if self.http {
-            config = config.with_http(
-                self.http_api
-                    .clone()
-                    .unwrap_or_else(|| RpcModuleSelection::standard_modules().into()),
-            );
+            config =
+                config.with_http(self.http_api.clone().unwrap_or_else(|| default_modules.clone()));
         }
 
         if self.ws {
-            config = config.with_ws(
-                self.ws_api
-                    .clone()
-                    .unwrap_or_else(|| RpcModuleSelection::standard_modules().into()),
-            );
+            config = config.with_ws(self.ws_api.clone().unwrap_or(default_modules));
         }
 
         if self.is_ipc_enabled() {
@@ -222,7 +233,10 @@ mod tests {
     use reth_node_core::args::RpcServerArgs;
     use reth_rpc_eth_types::RPC_DEFAULT_GAS_CAP;
     use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
-    use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+    use std::{
+        net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+        time::Duration,
+    };
 
     use crate::config::RethRpcServerConfig;
 
@@ -365,4 +379,16 @@ mod tests {
         assert_eq!(config.max_blocks_per_filter, Some(100));
         assert_eq!(config.max_logs_per_response, Some(200));
     }
+
+    #[test]
+    fn test_filter_reap_interval() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+
+        let config = args.eth_config().filter_config();
+        assert_eq!(config.reap_interval, Duration::from_secs(30));
+
+        let config =
+            args.eth_config().stale_filter_reap_interval(Duration::from_secs(5)).filter_config();
+        assert_eq!(config.reap_interval, Duration::from_secs(5));
+    }
 }
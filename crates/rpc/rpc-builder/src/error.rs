@@ -1,4 +1,6 @@
 use crate::{cors::CorsDomainError, RethRpcModule};
+use jsonrpsee::core::RegisterMethodError;
+use rayon::ThreadPoolBuildError;
 use reth_ipc::server::IpcServerStartError;
 use std::{
     collections::HashSet,
@@ -85,6 +87,18 @@ impl RpcError {
     }
 }
 
+/// Errors when building the `eth` namespace handlers, see
+/// [`EthHandlersBuilder::build`](crate::eth::EthHandlersBuilder::build).
+#[derive(Debug, thiserror::Error)]
+pub enum EthHandlersError {
+    /// Failed to build the pool for blocking tasks.
+    #[error("failed to build blocking task pool: {0}")]
+    BlockingTaskPool(#[from] ThreadPoolBuildError),
+    /// Additional methods conflict with each other.
+    #[error(transparent)]
+    RegisterMethod(#[from] RegisterMethodError),
+}
+
 /// Conflicting modules between http and ws servers.
 #[derive(Debug)]
 pub struct ConflictingModules {
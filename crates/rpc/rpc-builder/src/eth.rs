@@ -1,15 +1,123 @@
+use std::{
+    future::Future,
+    panic::AssertUnwindSafe,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use futures::FutureExt;
+
+use jsonrpsee::{
+    core::{server::MethodsError, traits::ToRpcParams, JsonRawValue, RegisterMethodError},
+    types::{
+        error::{INTERNAL_ERROR_CODE, METHOD_NOT_FOUND_CODE, OVERSIZED_RESPONSE_CODE},
+        ErrorObjectOwned, Params,
+    },
+    Methods, RpcModule,
+};
 use reth_evm::ConfigureEvm;
-use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider, StateProviderFactory};
-use reth_rpc::{EthFilter, EthPubSub};
+use reth_provider::{
+    BlockReader, CanonStateSubscriptions, ChainSpecProvider, EvmEnvProvider, StateProviderFactory,
+    StaticFileProviderFactory,
+};
+use reth_rpc::{
+    eth::{FilterSummary, PubSubStats},
+    EthFilter, EthPubSub, EthSimulateBundle,
+};
+use reth_rpc_eth_api::helpers::LoadFee;
 use reth_rpc_eth_types::{
-    cache::cache_new_blocks_task, EthApiBuilderCtx, EthConfig, EthStateCache,
+    builder::{rate_limit::LIMIT_EXCEEDED_CODE, response_cache::response_cache_reorg_task},
+    cache::cache_new_blocks_task_with_lag,
+    state_changes::state_changes_task,
+    CacheCoalescingStats, CacheEvictionStats, DecimalQuantities, DynEthResponseMiddleware,
+    DynRateLimiter, EthApiBuilderCtx, EthConfig, EthStateCache, NumberEncoding, RateLimit,
+    ResponseCache, RevertError, StateChangeNotification,
 };
-use reth_tasks::TaskSpawner;
+use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner};
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::{debug, error};
+
+use crate::error::EthHandlersError;
 
 /// Alias for `eth` namespace API builder.
 pub type DynEthApiBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi> =
     Box<dyn Fn(&EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>) -> EthApi>;
 
+/// Alias for `eth` filter API builder.
+pub type DynEthFilterBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events> = Box<
+    dyn Fn(
+        &EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>,
+    ) -> EthFilter<Provider, Pool>,
+>;
+
+/// Builds the methods of an RPC namespace from an [`EthApiBuilderCtx`], so that custom namespaces
+/// can share the context with the `eth` namespace.
+///
+/// Implemented for all functions that take the context and return methods.
+pub trait EthRpcModuleBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events> {
+    /// Builds the methods for the given context.
+    fn build(
+        &self,
+        ctx: &EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>,
+    ) -> Result<Methods, RegisterMethodError>;
+
+    /// Returns a builder that merges the methods of this and the other builder, both built from
+    /// the same context.
+    ///
+    /// Building the composite fails if both builders register a method with the same name.
+    fn and_then<Other>(self, other: Other) -> AndThen<Self, Other>
+    where
+        Self: Sized,
+        Other: EthRpcModuleBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events>,
+    {
+        AndThen { first: self, second: other }
+    }
+}
+
+impl<F, M, Provider, Pool, EvmConfig, Network, Tasks, Events>
+    EthRpcModuleBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events> for F
+where
+    F: Fn(&EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>) -> M,
+    M: Into<Methods>,
+{
+    fn build(
+        &self,
+        ctx: &EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>,
+    ) -> Result<Methods, RegisterMethodError> {
+        Ok(self(ctx).into())
+    }
+}
+
+/// Composite [`EthRpcModuleBuilder`], see [`EthRpcModuleBuilder::and_then`].
+#[derive(Debug, Clone)]
+pub struct AndThen<First, Second> {
+    first: First,
+    second: Second,
+}
+
+impl<First, Second, Provider, Pool, EvmConfig, Network, Tasks, Events>
+    EthRpcModuleBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events>
+    for AndThen<First, Second>
+where
+    First: EthRpcModuleBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events>,
+    Second: EthRpcModuleBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events>,
+{
+    fn build(
+        &self,
+        ctx: &EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>,
+    ) -> Result<Methods, RegisterMethodError> {
+        let mut methods = self.first.build(ctx)?;
+        methods.merge(self.second.build(ctx)?)?;
+        Ok(methods)
+    }
+}
+
+/// Alias for a boxed [`EthRpcModuleBuilder`].
+pub type DynEthRpcModuleBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events> =
+    Box<dyn EthRpcModuleBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events>>;
+
 /// Handlers for core, filter and pubsub `eth` namespace APIs.
 #[derive(Debug, Clone)]
 pub struct EthHandlers<Provider, Pool, Network, Events, EthApi> {
@@ -17,10 +125,38 @@ pub struct EthHandlers<Provider, Pool, Network, Events, EthApi> {
     pub api: EthApi,
     /// The async caching layer used by the eth handlers
     pub cache: EthStateCache,
+    /// Separate caching layer used by tracing calls, if configured with
+    /// [`EthConfig::trace_cache`]
+    pub trace_cache: Option<EthStateCache>,
     /// Polling based filter handler available on all transports
     pub filter: EthFilter<Provider, Pool>,
     /// Handler for subscriptions only available for transports that support it (ws, ipc)
     pub pubsub: EthPubSub<Provider, Pool, Events, Network>,
+    /// Post-processes the responses of the `eth_` request handlers, if set
+    pub response_middleware: Option<DynEthResponseMiddleware>,
+    /// Limits the rate of requests to the `eth_` request handlers, if set
+    pub rate_limiter: Option<DynRateLimiter>,
+    /// Caches the responses of the allowlisted `eth_` methods, if configured with
+    /// [`EthConfig::response_cache`]
+    pub response_cache: Option<Arc<ResponseCache>>,
+    /// The config the handlers were built with, applies to all transports without an override
+    pub config: EthConfig,
+    /// Handlers for the http transport, if its config is overridden
+    pub http: Option<EthTransportHandlers<Provider, Pool, EthApi>>,
+    /// Handlers for the ws transport, if its config is overridden
+    pub ws: Option<EthTransportHandlers<Provider, Pool, EthApi>>,
+    /// Handlers for the ipc transport, if its config is overridden
+    pub ipc: Option<EthTransportHandlers<Provider, Pool, EthApi>>,
+    /// Methods of additional namespaces, built from the same context as the `eth` handlers
+    pub custom_methods: Methods,
+    /// Handles of the tasks spawned while building the handlers, e.g. to supervise them
+    pub tasks: EthTaskHandles,
+    /// Whether the handlers serve requests, if built in standby mode, see
+    /// [`EthHandlersBuilder::standby`]
+    pub standby: Option<EthStandby>,
+    /// Limits the concurrent tracing calls of the handlers, e.g. to share the limit with the
+    /// `debug_` and `trace_` namespaces
+    pub tracing_task_guard: BlockingTaskGuard,
 }
 
 impl<Provider, Pool, Network, Events, EthApi> EthHandlers<Provider, Pool, Network, Events, EthApi> {
@@ -53,8 +189,247 @@ impl<Provider, Pool, Network, Events, EthApi> EthHandlers<Provider, Pool, Networ
             executor,
             events,
             eth_api_builder,
+            filter_builder: None,
+            tracing_task_guard: None,
+            response_middleware: None,
+            rate_limiter: None,
+            transport_configs: Default::default(),
+            module_builder: None,
+            state_change_sink: None,
+            standby: false,
         }
     }
+
+    /// Returns the [`EthConfig`] that applies to the http transport.
+    pub fn http_config(&self) -> &EthConfig {
+        self.http.as_ref().map_or(&self.config, |handlers| &handlers.config)
+    }
+
+    /// Returns the [`EthConfig`] that applies to the ws transport.
+    pub fn ws_config(&self) -> &EthConfig {
+        self.ws.as_ref().map_or(&self.config, |handlers| &handlers.config)
+    }
+
+    /// Returns the [`EthConfig`] that applies to the ipc transport.
+    pub fn ipc_config(&self) -> &EthConfig {
+        self.ipc.as_ref().map_or(&self.config, |handlers| &handlers.config)
+    }
+
+    /// Returns `true` once the shared [`EthStateCache`] is warm, see [`EthStateCache::is_warm`].
+    ///
+    /// This can be used to hold back traffic until the cache serves the tip of the chain.
+    pub fn is_cache_warm(&self) -> bool {
+        self.cache.is_warm()
+    }
+
+    /// Returns the [`CacheEvictionStats`] of the shared [`EthStateCache`], e.g. to tell which of
+    /// its caches is too small for the workload.
+    pub fn cache_eviction_stats(&self) -> CacheEvictionStats {
+        self.cache.eviction_stats()
+    }
+
+    /// Returns the [`CacheCoalescingStats`] of the shared [`EthStateCache`], i.e. how many
+    /// requests waited for the in-flight fetch of an earlier request instead of fetching the data
+    /// themselves.
+    ///
+    /// High counts indicate hot blocks whose contention the cache absorbs, e.g. to inform the
+    /// sizing of the cache.
+    pub fn cache_coalescing_stats(&self) -> CacheCoalescingStats {
+        self.cache.coalescing_stats()
+    }
+
+    /// Returns the number of canonical state notifications the shared [`EthStateCache`] dropped
+    /// because it couldn't keep up, see [`EthStateCache::notification_overflows`].
+    ///
+    /// A non-zero count explains a cache that misses recent blocks.
+    pub fn cache_notification_overflows(&self) -> u64 {
+        self.cache.notification_overflows()
+    }
+
+    /// Returns a [`FilterSummary`] of every filter that is currently installed on the filter
+    /// handler, e.g. to find clients that install filters and never poll them.
+    ///
+    /// Filters of transports with an overridden [`EthConfig`] are not included.
+    pub async fn filter_summaries(&self) -> Vec<FilterSummary>
+    where
+        Provider: Send + Sync + 'static,
+        Pool: Send + Sync + 'static,
+    {
+        self.filter.filter_summaries().await
+    }
+
+    /// Returns the current [`PubSubStats`] of the subscription handler, e.g. to tell whether
+    /// subscribers skip blocks because they consume their subscriptions too slowly.
+    pub fn pubsub_stats(&self) -> PubSubStats {
+        self.pubsub.channel_stats()
+    }
+
+    /// Clears the caches of the gas price oracles of all `eth_` request handlers, including those
+    /// of transports with an overridden [`EthConfig`], see [`GasPriceOracle::clear_cache`].
+    ///
+    /// [`GasPriceOracle::clear_cache`]: reth_rpc_eth_types::GasPriceOracle::clear_cache
+    pub async fn clear_gas_price_oracle_cache(&self)
+    where
+        EthApi: LoadFee,
+    {
+        self.api.gas_oracle().clear_cache().await;
+        for handlers in [&self.http, &self.ws, &self.ipc].into_iter().flatten() {
+            handlers.api.gas_oracle().clear_cache().await;
+        }
+    }
+
+    /// Returns `true` if the handlers serve requests, i.e. unless they were built in standby mode
+    /// and not promoted yet, see [`EthHandlersBuilder::standby`].
+    pub fn is_serving(&self) -> bool {
+        self.standby.as_ref().map_or(true, EthStandby::is_promoted)
+    }
+
+    /// Promotes handlers that were built in standby mode, so that they start serving requests on
+    /// all transports they are registered on.
+    ///
+    /// Has no effect if the handlers were not built in standby mode or were already promoted.
+    pub fn promote(&self) {
+        if let Some(standby) = &self.standby {
+            standby.promote()
+        }
+    }
+
+    /// Passes the given `eth_` methods through the wrappers the handlers were built with, in this
+    /// order: the response middleware, the revert data stripping, the response cache, the
+    /// response size limit, the rate limiter and the standby check.
+    ///
+    /// Note: subscriptions can't be wrapped, so this must not be applied to modules that contain
+    /// subscriptions.
+    pub fn wrap_eth_methods(&self, module: impl Into<Methods>) -> Methods {
+        let module = apply_response_middleware(module, self.response_middleware.as_ref());
+        let module = apply_revert_data_stripping(module, self.config.strip_revert_data);
+        let module = apply_response_cache(module, self.response_cache.as_ref());
+        let module = apply_max_response_bytes(module, self.config.max_response_bytes);
+        let module = apply_rate_limiter(module, self.rate_limiter.as_ref());
+        apply_standby(module, self.standby.as_ref())
+    }
+
+    /// Rejects all calls to the methods of the given module until the handlers are promoted, if
+    /// they were built in standby mode, see [`apply_standby`].
+    ///
+    /// This is applied to all namespaces that are served from the handlers, other than the
+    /// `eth_` namespace, which is wrapped by [`Self::wrap_eth_methods`].
+    pub fn gate_standby(&self, module: impl Into<Methods>) -> Methods {
+        apply_standby(module, self.standby.as_ref())
+    }
+
+    /// Returns a new handle to the filter handler, e.g. to install the filter API on an
+    /// additional transport.
+    ///
+    /// The handle shares all state with [`Self::filter`], including the installed filters and the
+    /// task that removes stale filters, so no additional task is spawned.
+    pub fn filter_handle(&self) -> EthFilter<Provider, Pool> {
+        self.filter.clone()
+    }
+}
+
+/// Serving state of [`EthHandlers`] that were built in standby mode, see
+/// [`EthHandlersBuilder::standby`].
+///
+/// Clones share the same state, so handlers can be promoted from anywhere, e.g. by the component
+/// that detects the failure of the primary node.
+#[derive(Debug, Clone, Default)]
+pub struct EthStandby {
+    promoted: Arc<AtomicBool>,
+}
+
+impl EthStandby {
+    /// Starts serving requests.
+    pub fn promote(&self) {
+        self.promoted.store(true, Ordering::Relaxed)
+    }
+
+    /// Returns `true` once promoted.
+    pub fn is_promoted(&self) -> bool {
+        self.promoted.load(Ordering::Relaxed)
+    }
+}
+
+/// A task spawned by [`EthHandlersBuilder::build`].
+#[derive(Debug)]
+pub struct EthTaskHandle {
+    /// The name of the task
+    pub name: &'static str,
+    /// The handle of the task
+    pub handle: JoinHandle<()>,
+}
+
+/// Handles of the tasks spawned by [`EthHandlersBuilder::build`]: the state cache service, the
+/// task that keeps the cache up to date with the canonical chain and, if configured, the same
+/// tasks for the trace cache, the task that clears the response cache on reorgs and the task that
+/// forwards state changes.
+///
+/// The tasks are spawned as before, critical tasks still shut down the node when they panic, so
+/// the handles can be ignored. Tasks owned by the handlers themselves, e.g. the task that removes
+/// stale filters, are not included.
+///
+/// Clones share the same handles, which can be taken once with [`Self::take`].
+#[derive(Debug, Clone, Default)]
+pub struct EthTaskHandles(Arc<Mutex<Vec<EthTaskHandle>>>);
+
+impl EthTaskHandles {
+    /// Takes all handles, leaving none behind for other clones.
+    pub fn take(&self) -> Vec<EthTaskHandle> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(|err| err.into_inner()))
+    }
+
+    fn push(&self, name: &'static str, handle: JoinHandle<()>) {
+        self.0.lock().unwrap_or_else(|err| err.into_inner()).push(EthTaskHandle { name, handle });
+    }
+}
+
+/// Core and filter `eth` handlers, built for a transport with an overridden [`EthConfig`].
+#[derive(Debug, Clone)]
+pub struct EthTransportHandlers<Provider, Pool, EthApi> {
+    /// The config that applies to the transport
+    pub config: EthConfig,
+    /// Main `eth_` request handler, built with the transport's config
+    pub api: EthApi,
+    /// Polling based filter handler, built with the transport's filter config
+    pub filter: EthFilter<Provider, Pool>,
+}
+
+/// Overrides of the [`EthConfig`] for the `eth` handlers of individual transports, e.g. a strict
+/// config for a public http endpoint and a permissive one for a local ipc endpoint.
+///
+/// Transports without an override use the config the handlers are built with. All transports
+/// share the same [`EthStateCache`], so the cache settings of overrides are ignored.
+///
+/// Only the `eth_` namespace of an overridden transport is served by its own handlers, all other
+/// namespaces, e.g. `debug_` and `trace_`, use the shared handlers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EthTransportConfigs {
+    /// Config override for the http transport
+    pub http: Option<EthConfig>,
+    /// Config override for the ws transport
+    pub ws: Option<EthConfig>,
+    /// Config override for the ipc transport
+    pub ipc: Option<EthConfig>,
+}
+
+impl EthTransportConfigs {
+    /// Sets the [`EthConfig`] override for the http transport.
+    pub fn with_http(mut self, config: EthConfig) -> Self {
+        self.http = Some(config);
+        self
+    }
+
+    /// Sets the [`EthConfig`] override for the ws transport.
+    pub fn with_ws(mut self, config: EthConfig) -> Self {
+        self.ws = Some(config);
+        self
+    }
+
+    /// Sets the [`EthConfig`] override for the ipc transport.
+    pub fn with_ipc(mut self, config: EthConfig) -> Self {
+        self.ipc = Some(config);
+        self
+    }
 }
 
 /// Builds [`EthHandlers`] for core, filter, and pubsub `eth_` apis.
@@ -68,12 +443,28 @@ pub struct EthHandlersBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig,
     executor: Tasks,
     events: Events,
     eth_api_builder: DynEthApiBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi>,
+    filter_builder: Option<DynEthFilterBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events>>,
+    tracing_task_guard: Option<BlockingTaskGuard>,
+    response_middleware: Option<DynEthResponseMiddleware>,
+    rate_limiter: Option<DynRateLimiter>,
+    transport_configs: EthTransportConfigs,
+    module_builder:
+        Option<DynEthRpcModuleBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events>>,
+    state_change_sink: Option<mpsc::Sender<StateChangeNotification>>,
+    standby: bool,
 }
 
 impl<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi>
     EthHandlersBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi>
 where
-    Provider: StateProviderFactory + BlockReader + EvmEnvProvider + Clone + Unpin + 'static,
+    Provider: StateProviderFactory
+        + BlockReader
+        + EvmEnvProvider
+        + ChainSpecProvider
+        + StaticFileProviderFactory
+        + Clone
+        + Unpin
+        + 'static,
     Pool: Send + Sync + Clone + 'static,
     EvmConfig: ConfigureEvm,
     Network: Clone + 'static,
@@ -81,26 +472,159 @@ where
     Events: CanonStateSubscriptions + Clone + 'static,
     EthApi: 'static,
 {
+    /// Configures the guard that restricts concurrent tracing calls, so it can be shared with
+    /// handlers outside the `eth` namespace.
+    ///
+    /// If unset, a new guard with [`EthConfig::max_tracing_requests`] permits is created.
+    pub fn with_tracing_task_guard(mut self, tracing_task_guard: BlockingTaskGuard) -> Self {
+        self.tracing_task_guard = Some(tracing_task_guard);
+        self
+    }
+
+    /// Configures the builder for the filter handler, e.g. to build it with a customized
+    /// [`EthFilterConfig`](reth_rpc_eth_types::EthFilterConfig).
+    ///
+    /// The builder is also used for the filter handlers of transports with an overridden
+    /// [`EthConfig`]. If unset, [`EthFilterApiBuilder::build`] is used.
+    pub fn with_filter_builder(
+        mut self,
+        filter_builder: DynEthFilterBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events>,
+    ) -> Self {
+        self.filter_builder = Some(filter_builder);
+        self
+    }
+
+    /// Configures the middleware that the responses of the `eth_` request handlers are passed
+    /// through.
+    pub fn with_response_middleware(
+        mut self,
+        response_middleware: DynEthResponseMiddleware,
+    ) -> Self {
+        self.response_middleware = Some(response_middleware);
+        self
+    }
+
+    /// Configures the rate limiter that is consulted before every call to the core and filter
+    /// `eth_` request handlers.
+    ///
+    /// Throttled calls are rejected with a `Limit exceeded` error. Subscriptions are not limited.
+    pub fn with_rate_limiter(mut self, rate_limiter: DynRateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Configures per transport overrides of the [`EthConfig`].
+    ///
+    /// Separate core and filter handlers are built for every overridden transport, which are
+    /// installed on the transport by
+    /// [`RpcRegistryInner::create_transport_rpc_modules`](crate::RpcRegistryInner::create_transport_rpc_modules).
+    /// Each of them populates its own fee history cache and removes its own stale filters.
+    pub fn with_transport_configs(mut self, transport_configs: EthTransportConfigs) -> Self {
+        self.transport_configs = transport_configs;
+        self
+    }
+
+    /// Configures the builder for methods of additional namespaces, that are built from the same
+    /// context as the `eth` handlers, see [`EthHandlers::custom_methods`].
+    ///
+    /// Multiple builders can be combined with [`EthRpcModuleBuilder::and_then`].
+    pub fn with_module_builder(
+        mut self,
+        module_builder: impl EthRpcModuleBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events>
+            + 'static,
+    ) -> Self {
+        self.module_builder = Some(Box::new(module_builder));
+        self
+    }
+
+    /// Configures a sink that the [`BlockStateChanges`] of every canonical block are sent to, e.g.
+    /// for an indexer that runs alongside the node.
+    ///
+    /// The changes are forwarded by a separate task, which waits for the receiver if the channel
+    /// is full and ends when the receiver is dropped. If the task lags behind the canonical chain
+    /// because the receiver is too slow, the sink receives [`StateChangeNotification::Lagged`].
+    /// See [`state_changes_task`](reth_rpc_eth_types::state_changes::state_changes_task).
+    ///
+    /// [`BlockStateChanges`]: reth_rpc_eth_types::BlockStateChanges
+    pub fn with_state_change_sink(mut self, sink: mpsc::Sender<StateChangeNotification>) -> Self {
+        self.state_change_sink = Some(sink);
+        self
+    }
+
+    /// Configures whether the handlers are built in standby mode, e.g. for a hot standby node
+    /// that takes over the traffic of a failed primary node.
+    ///
+    /// In standby mode, all tasks are spawned as usual, so the caches keep up with the canonical
+    /// chain, but all namespaces served from these handlers reject calls and new subscriptions as
+    /// if they were not registered, until the handlers are promoted with [`EthHandlers::promote`].
+    /// This includes the `eth_` methods of the auth server and the `debug_`, `trace_`, `txpool_`,
+    /// `ots_` and `eth_callBundle` namespaces of the [`RpcRegistryInner`](crate::RpcRegistryInner).
+    pub const fn standby(mut self, standby: bool) -> Self {
+        self.standby = standby;
+        self
+    }
+
     /// Returns a new instance with handlers for `eth` namespace.
-    pub fn build(self) -> EthHandlers<Provider, Pool, Network, Events, EthApi> {
-        let Self { provider, pool, network, evm_config, config, executor, events, eth_api_builder } =
-            self;
+    ///
+    /// Fails if the blocking task pool can't be built, or if the configured module builder
+    /// registers conflicting methods.
+    pub fn build(
+        self,
+    ) -> Result<EthHandlers<Provider, Pool, Network, Events, EthApi>, EthHandlersError> {
+        let Self {
+            provider,
+            pool,
+            network,
+            evm_config,
+            config,
+            executor,
+            events,
+            eth_api_builder,
+            filter_builder,
+            tracing_task_guard,
+            response_middleware,
+            rate_limiter,
+            transport_configs,
+            module_builder,
+            state_change_sink,
+            standby,
+        } = self;
 
-        let cache = EthStateCache::spawn_with(
+        let tracing_task_guard = tracing_task_guard
+            .unwrap_or_else(|| BlockingTaskGuard::new(config.max_tracing_requests));
+        let blocking_task_pool = config.build_blocking_task_pool()?;
+
+        let tasks = EthTaskHandles::default();
+
+        let (cache, cache_service) = EthStateCache::spawn_with_handle(
             provider.clone(),
-            config.cache,
+            config.cache.clone(),
             executor.clone(),
             evm_config.clone(),
         );
+        tasks.push("eth state cache", cache_service);
+        let cache = cache.with_tag_cache_ttl(config.tag_cache_ttl);
 
-        let new_canonical_blocks = events.canonical_state_stream();
-        let c = cache.clone();
-        executor.spawn_critical(
-            "cache canonical blocks task",
-            Box::pin(async move {
-                cache_new_blocks_task(c, new_canonical_blocks).await;
-            }),
-        );
+        let trace_cache = config.trace_cache.clone().map(|trace_cache_config| {
+            let (trace_cache, trace_cache_service) = EthStateCache::spawn_with_handle(
+                provider.clone(),
+                trace_cache_config,
+                executor.clone(),
+                evm_config.clone(),
+            );
+            tasks.push("eth trace state cache", trace_cache_service);
+            trace_cache
+        });
+
+        let response_cache =
+            config.response_cache.clone().map(|config| Arc::new(ResponseCache::new(config)));
+
+        let response_middleware = match config.number_encoding {
+            NumberEncoding::Hex => response_middleware,
+            NumberEncoding::Decimal => {
+                Some(Arc::new(DecimalQuantities::new(response_middleware)) as _)
+            }
+        };
 
         let ctx = EthApiBuilderCtx {
             provider,
@@ -111,15 +635,382 @@ where
             executor,
             events,
             cache,
+            trace_cache,
+            blocking_task_pool,
+            gas_cap_metrics: Default::default(),
+            proof_permit_metrics: Default::default(),
+            tracing_task_guard,
+            response_middleware,
         };
+        debug!(target: "rpc::eth", description = ?ctx.describe_with_chain(), "built eth api context");
+
+        let c = ctx.cache.clone();
+        if ctx.config.cache_task_critical {
+            let new_canonical_blocks = ctx.canonical_state_broadcast_stream();
+            let handle = ctx.executor.spawn_critical(
+                "cache canonical blocks task",
+                Box::pin(async move {
+                    cache_new_blocks_task_with_lag(c, new_canonical_blocks).await;
+                }),
+            );
+            tasks.push("cache canonical blocks task", handle);
+        } else {
+            // restart the task with a fresh subscription if it panics, notifications received in
+            // the meantime are lost
+            let new_canonical_state_stream = ctx.canonical_state_broadcast_stream_factory();
+            let mut new_canonical_blocks = new_canonical_state_stream();
+            let handle = ctx.executor.spawn(Box::pin(async move {
+                loop {
+                    let task = cache_new_blocks_task_with_lag(c.clone(), new_canonical_blocks);
+                    if AssertUnwindSafe(task).catch_unwind().await.is_ok() {
+                        // canonical state stream closed
+                        break
+                    }
+                    error!(target: "rpc::eth", "cache canonical blocks task panicked, restarting");
+                    new_canonical_blocks = new_canonical_state_stream();
+                }
+            }));
+            tasks.push("cache canonical blocks task", handle);
+        }
+
+        if let Some(trace_cache) = ctx.trace_cache.clone() {
+            let new_canonical_blocks = ctx.canonical_state_broadcast_stream();
+            let handle = ctx.executor.spawn(Box::pin(async move {
+                cache_new_blocks_task_with_lag(trace_cache, new_canonical_blocks).await;
+            }));
+            tasks.push("trace cache canonical blocks task", handle);
+        }
+
+        if let Some(response_cache) = response_cache.clone() {
+            let new_canonical_blocks = ctx.canonical_state_broadcast_stream();
+            let handle = ctx.executor.spawn(Box::pin(async move {
+                response_cache_reorg_task(response_cache, new_canonical_blocks).await;
+            }));
+            tasks.push("response cache reorg task", handle);
+        }
+
+        if let Some(sink) = state_change_sink {
+            let new_canonical_blocks = ctx.canonical_state_broadcast_stream();
+            let handle = ctx.executor.spawn(Box::pin(async move {
+                state_changes_task(new_canonical_blocks, sink).await;
+            }));
+            tasks.push("state changes task", handle);
+        }
 
         let api = eth_api_builder(&ctx);
 
-        let filter = EthFilterApiBuilder::build(&ctx);
+        let build_filter = |ctx: &EthApiBuilderCtx<_, _, _, _, _, _>| match &filter_builder {
+            Some(filter_builder) => filter_builder(ctx),
+            None => EthFilterApiBuilder::build(ctx),
+        };
+        let filter = build_filter(&ctx);
 
+        let standby = standby.then(EthStandby::default);
         let pubsub = EthPubSubApiBuilder::build(&ctx);
+        let pubsub = match &standby {
+            Some(standby) => pubsub.with_subscription_gate(standby.promoted.clone()),
+            None => pubsub,
+        };
+
+        let EthTransportConfigs { http, ws, ipc } = transport_configs;
+        let build_transport = |config: Option<EthConfig>| {
+            config.map(|config| {
+                let ctx = EthApiBuilderCtx { config: config.clone(), ..ctx.clone() };
+                EthTransportHandlers {
+                    config,
+                    api: eth_api_builder(&ctx),
+                    filter: build_filter(&ctx),
+                }
+            })
+        };
+        let http = build_transport(http);
+        let ws = build_transport(ws);
+        let ipc = build_transport(ipc);
 
-        EthHandlers { api, cache: ctx.cache, filter, pubsub }
+        let custom_methods = match module_builder {
+            Some(module_builder) => module_builder.build(&ctx)?,
+            None => Methods::new(),
+        };
+
+        Ok(EthHandlers {
+            api,
+            cache: ctx.cache,
+            trace_cache: ctx.trace_cache,
+            filter,
+            pubsub,
+            response_middleware: ctx.response_middleware,
+            rate_limiter,
+            response_cache,
+            config: ctx.config,
+            http,
+            ws,
+            ipc,
+            custom_methods,
+            tasks,
+            standby,
+            tracing_task_guard: ctx.tracing_task_guard,
+        })
+    }
+}
+
+/// A method of the module wrapped by [`wrap_methods`], which calls the method with the raw params
+/// of a request.
+#[derive(Debug, Clone)]
+pub struct WrappedMethod {
+    methods: Arc<Methods>,
+    name: &'static str,
+}
+
+impl WrappedMethod {
+    /// Returns the name of the method.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Calls the method with the given params.
+    pub async fn call(
+        &self,
+        params: Params<'static>,
+    ) -> Result<serde_json::Value, ErrorObjectOwned> {
+        let params = RawParams(params.as_str().map(ToOwned::to_owned));
+        self.methods.call(self.name, params).await.map_err(|err| match err {
+            MethodsError::JsonRpc(err) => err,
+            err => ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>),
+        })
+    }
+}
+
+/// Wraps every method of the given module with the given function, which is called with the name
+/// of the method, the params of the request and the wrapped method, e.g. to inspect or replace the
+/// result of the call.
+///
+/// Note: subscriptions can't be wrapped, so this must not be applied to modules that contain
+/// subscriptions.
+pub fn wrap_methods<F, Fut>(module: impl Into<Methods>, f: F) -> Methods
+where
+    F: Fn(&'static str, Params<'static>, WrappedMethod) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<serde_json::Value, ErrorObjectOwned>> + Send + 'static,
+{
+    let methods = Arc::new(module.into());
+    let mut wrapped = RpcModule::new(());
+    let method_names: Vec<_> = methods.method_names().collect();
+    for name in method_names {
+        let next = WrappedMethod { methods: methods.clone(), name };
+        let f = f.clone();
+        wrapped
+            .register_async_method(name, move |params, _, _| f(name, params, next.clone()))
+            .expect("method names are unique");
+    }
+
+    wrapped.into()
+}
+
+/// Passes the successful responses of all methods of the given module through the given
+/// middleware.
+///
+/// If no middleware is set, the module is returned unchanged.
+///
+/// Note: subscriptions can't be wrapped, so this must not be applied to modules that contain
+/// subscriptions.
+pub fn apply_response_middleware(
+    module: impl Into<Methods>,
+    response_middleware: Option<&DynEthResponseMiddleware>,
+) -> Methods {
+    let module = module.into();
+    let Some(response_middleware) = response_middleware.cloned() else { return module };
+
+    wrap_methods(module, move |method, params, next| {
+        let response_middleware = response_middleware.clone();
+        async move {
+            let result = next.call(params).await?;
+            Ok(response_middleware.on_response(method, result))
+        }
+    })
+}
+
+/// The methods whose revert data is stripped by [`apply_revert_data_stripping`].
+const REVERT_DATA_STRIPPED_METHODS: [&str; 2] = ["eth_call", "eth_estimateGas"];
+
+/// Strips the revert data from the errors of reverted `eth_call` and `eth_estimateGas` requests
+/// of the given module if enabled, see [`EthConfig::strip_revert_data`].
+///
+/// If disabled, the module is returned unchanged.
+///
+/// Note: subscriptions can't be wrapped, so this must not be applied to modules that contain
+/// subscriptions.
+pub fn apply_revert_data_stripping(module: impl Into<Methods>, strip_revert_data: bool) -> Methods {
+    let module = module.into();
+    if !strip_revert_data {
+        return module
+    }
+
+    wrap_methods(module, |method, params, next| async move {
+        let result = next.call(params).await;
+        if REVERT_DATA_STRIPPED_METHODS.contains(&method) {
+            return result.map_err(RevertError::strip_data)
+        }
+        result
+    })
+}
+
+/// Serves the responses of the methods of the given module that are cached by the given cache
+/// from the cache, and caches their successful responses otherwise, see [`ResponseCache`].
+///
+/// If no cache is set, the module is returned unchanged.
+///
+/// Note: subscriptions can't be wrapped, so this must not be applied to modules that contain
+/// subscriptions.
+pub fn apply_response_cache(
+    module: impl Into<Methods>,
+    response_cache: Option<&Arc<ResponseCache>>,
+) -> Methods {
+    let module = module.into();
+    let Some(response_cache) = response_cache.cloned() else { return module };
+
+    wrap_methods(module, move |method, params, next| {
+        let response_cache = response_cache.clone();
+        async move {
+            let Some(key) = response_cache.key(method, params.as_str()) else {
+                return next.call(params).await
+            };
+            if let Some(response) = response_cache.get(&key) {
+                return Ok(response)
+            }
+            let response = next.call(params).await?;
+            response_cache.insert(key, &response);
+            Ok(response)
+        }
+    })
+}
+
+/// Rejects the results of all methods of the given module whose serialized size exceeds the given
+/// number of bytes with a `response too large` error, see [`EthConfig::max_response_bytes`].
+///
+/// If no limit is set, the module is returned unchanged.
+///
+/// Note: subscriptions can't be wrapped, so this must not be applied to modules that contain
+/// subscriptions.
+pub fn apply_max_response_bytes(
+    module: impl Into<Methods>,
+    max_response_bytes: Option<usize>,
+) -> Methods {
+    let module = module.into();
+    let Some(max_response_bytes) = max_response_bytes else { return module };
+
+    wrap_methods(module, move |_, params, next| async move {
+        let result = next.call(params).await?;
+        if serialized_len(&result) > max_response_bytes {
+            return Err(ErrorObjectOwned::owned(
+                OVERSIZED_RESPONSE_CODE,
+                "response too large",
+                Some(format!("Exceeded max limit of {max_response_bytes} bytes")),
+            ))
+        }
+        Ok(result)
+    })
+}
+
+/// Returns the length of the JSON encoding of the given value, without allocating it.
+fn serialized_len(value: &serde_json::Value) -> usize {
+    /// Counts the written bytes.
+    struct ByteCounter(usize);
+
+    impl std::io::Write for ByteCounter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = ByteCounter(0);
+    serde_json::to_writer(&mut counter, value).expect("values are serializable");
+    counter.0
+}
+
+/// Consults the given rate limiter before every call to a method of the given module, and
+/// rejects throttled calls with a `Limit exceeded` error.
+///
+/// If no rate limiter is set, the module is returned unchanged.
+///
+/// Note: subscriptions can't be wrapped, so this must not be applied to modules that contain
+/// subscriptions.
+pub fn apply_rate_limiter(
+    module: impl Into<Methods>,
+    rate_limiter: Option<&DynRateLimiter>,
+) -> Methods {
+    let module = module.into();
+    let Some(rate_limiter) = rate_limiter.cloned() else { return module };
+
+    wrap_methods(module, move |method, params, next| {
+        let throttled = rate_limiter.check(method) == RateLimit::Throttled;
+        async move {
+            if throttled {
+                return Err(ErrorObjectOwned::owned(
+                    LIMIT_EXCEEDED_CODE,
+                    "Limit exceeded",
+                    None::<()>,
+                ))
+            }
+            next.call(params).await
+        }
+    })
+}
+
+/// Rejects all calls to the methods of the given module with a `Method not found` error until the
+/// given standby handlers are promoted, see [`EthHandlersBuilder::standby`].
+///
+/// If not in standby mode, the module is returned unchanged.
+///
+/// Note: subscriptions can't be wrapped, so this must not be applied to modules that contain
+/// subscriptions.
+pub fn apply_standby(module: impl Into<Methods>, standby: Option<&EthStandby>) -> Methods {
+    let module = module.into();
+    let Some(standby) = standby.cloned() else { return module };
+
+    wrap_methods(module, move |_, params, next| {
+        let promoted = standby.is_promoted();
+        async move {
+            if !promoted {
+                return Err(ErrorObjectOwned::owned(
+                    METHOD_NOT_FOUND_CODE,
+                    "Method not found",
+                    None::<()>,
+                ))
+            }
+            next.call(params).await
+        }
+    })
+}
+
+/// Raw JSON params of a request, forwarded to the wrapped method as is.
+struct RawParams(Option<String>);
+
+impl ToRpcParams for RawParams {
+    fn to_rpc_params(self) -> Result<Option<Box<JsonRawValue>>, serde_json::Error> {
+        self.0.map(JsonRawValue::from_string).transpose()
+    }
+}
+
+/// Builds the bundle simulation handler [`EthSimulateBundle`].
+#[derive(Debug)]
+pub struct SimulateApiBuilder;
+
+impl SimulateApiBuilder {
+    /// Builds the [`EthSimulateBundle`] handler, for given context and `eth` API built from the
+    /// same context.
+    ///
+    /// Simulations are executed through the given `eth` API, so they are capped at
+    /// [`EthConfig::rpc_gas_cap`] and read through the context's [`EthStateCache`]. They share the
+    /// permits of the context's tracing guard with all tracing calls.
+    pub fn build<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi>(
+        ctx: &EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>,
+        eth_api: EthApi,
+    ) -> EthSimulateBundle<EthApi> {
+        EthSimulateBundle::new(eth_api, ctx.tracing_task_guard.clone())
     }
 }
 
@@ -133,7 +1024,7 @@ impl EthFilterApiBuilder {
         ctx: &EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>,
     ) -> EthFilter<Provider, Pool>
     where
-        Provider: Send + Sync + Clone + 'static,
+        Provider: StaticFileProviderFactory + Send + Sync + Clone + 'static,
         Pool: Send + Sync + Clone + 'static,
         Tasks: TaskSpawner + Clone + 'static,
     {
@@ -143,6 +1034,7 @@ impl EthFilterApiBuilder {
             ctx.cache.clone(),
             ctx.config.filter_config(),
             Box::new(ctx.executor.clone()),
+            Some(ctx.provider.static_file_provider()),
         )
     }
 }
@@ -153,6 +1045,9 @@ pub struct EthPubSubApiBuilder;
 
 impl EthPubSubApiBuilder {
     /// Builds the [`EthPubSubApiServer`](reth_rpc_eth_api::EthPubSubApiServer), for given context.
+    ///
+    /// Building is cheap and has no idle overhead, since tasks are only spawned per subscription,
+    /// see [`EthPubSub`].
     pub fn build<Provider, Pool, EvmConfig, Network, Tasks, Events>(
         ctx: &EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>,
     ) -> EthPubSub<Provider, Pool, Events, Network>
@@ -163,12 +1058,154 @@ impl EthPubSubApiBuilder {
         Network: Clone,
         Tasks: TaskSpawner + Clone + 'static,
     {
-        EthPubSub::with_spawner(
+        let pubsub = EthPubSub::with_spawner(
             ctx.provider.clone(),
             ctx.pool.clone(),
             ctx.events.clone(),
             ctx.network.clone(),
             Box::new(ctx.executor.clone()),
         )
+        .with_subscription_limits(
+            ctx.config.max_subscriptions_per_connection,
+            ctx.config.max_total_subscriptions,
+        );
+        if ctx.config.subscription_log_backfill {
+            pubsub.with_log_backfill(
+                ctx.config.max_logs_per_response,
+                ctx.config.max_blocks_per_filter,
+            )
+        } else {
+            pubsub
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_eth_types::{RateLimiter, ResponseCacheConfig, RpcInvalidTransactionError};
+
+    #[derive(Debug)]
+    struct ThrottleMethod(&'static str);
+
+    impl RateLimiter for ThrottleMethod {
+        fn check(&self, method: &str) -> RateLimit {
+            if method == self.0 {
+                RateLimit::Throttled
+            } else {
+                RateLimit::Ready
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn wrap_methods_forwards_params() {
+        let mut module = RpcModule::new(());
+        module
+            .register_method("eth_echo", |params, _, _| params.one::<u64>().unwrap_or_default())
+            .unwrap();
+        let methods = wrap_methods(module, |method, params, next| async move {
+            assert_eq!(method, next.name());
+            let result = next.call(params).await?;
+            Ok(serde_json::Value::from_iter([(method, result)]))
+        });
+
+        let result = methods.call::<_, serde_json::Value>("eth_echo", [7u64]).await.unwrap();
+        assert_eq!(result, serde_json::json!({ "eth_echo": 7 }));
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_rejects_throttled_methods() {
+        let mut module = RpcModule::new(());
+        module.register_method("eth_limited", |_, _, _| 1u64).unwrap();
+        module.register_method("eth_unlimited", |_, _, _| 2u64).unwrap();
+
+        let rate_limiter: DynRateLimiter = Arc::new(ThrottleMethod("eth_limited"));
+        let methods = apply_rate_limiter(module, Some(&rate_limiter));
+
+        let err = methods.call::<_, u64>("eth_limited", [(); 0]).await.unwrap_err();
+        assert!(
+            matches!(err, MethodsError::JsonRpc(err) if err.code() == LIMIT_EXCEEDED_CODE),
+            "throttled call should be rejected"
+        );
+        assert_eq!(methods.call::<_, u64>("eth_unlimited", [(); 0]).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn strips_revert_data_of_calls() {
+        let revert = || -> Result<u64, ErrorObjectOwned> {
+            Err(RpcInvalidTransactionError::Revert(RevertError::new(vec![1].into())).into())
+        };
+        let mut module = RpcModule::new(());
+        module.register_method("eth_call", move |_, _, _| revert()).unwrap();
+        module.register_method("eth_simulateV1", move |_, _, _| revert()).unwrap();
+        let methods = apply_revert_data_stripping(module, true);
+
+        let err = methods.call::<_, u64>("eth_call", [(); 0]).await.unwrap_err();
+        assert!(
+            matches!(err, MethodsError::JsonRpc(err) if err.data().is_none()),
+            "revert data should be stripped"
+        );
+        let err = methods.call::<_, u64>("eth_simulateV1", [(); 0]).await.unwrap_err();
+        assert!(matches!(err, MethodsError::JsonRpc(err) if err.data().is_some()));
+    }
+
+    #[tokio::test]
+    async fn max_response_bytes_rejects_large_results() {
+        let mut module = RpcModule::new(());
+        module.register_method("eth_small", |_, _, _| "0x1").unwrap();
+        module.register_method("eth_large", |_, _, _| "0x".repeat(100)).unwrap();
+        let methods = apply_max_response_bytes(module, Some(16));
+
+        assert_eq!(methods.call::<_, String>("eth_small", [(); 0]).await.unwrap(), "0x1");
+        let err = methods.call::<_, String>("eth_large", [(); 0]).await.unwrap_err();
+        assert!(
+            matches!(err, MethodsError::JsonRpc(err) if err.code() == OVERSIZED_RESPONSE_CODE),
+            "large result should be rejected"
+        );
+    }
+
+    #[tokio::test]
+    async fn standby_rejects_calls_until_promoted() {
+        let mut module = RpcModule::new(());
+        module.register_method("eth_blockNumber", |_, _, _| 1u64).unwrap();
+        let standby = EthStandby::default();
+        let methods = apply_standby(module, Some(&standby));
+
+        let err = methods.call::<_, u64>("eth_blockNumber", [(); 0]).await.unwrap_err();
+        assert!(
+            matches!(err, MethodsError::JsonRpc(err) if err.code() == METHOD_NOT_FOUND_CODE),
+            "standby call should be rejected"
+        );
+
+        standby.promote();
+        assert_eq!(methods.call::<_, u64>("eth_blockNumber", [(); 0]).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn response_cache_serves_allowlisted_methods() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let calls = Arc::new(AtomicU64::new(0));
+        let mut module = RpcModule::new(calls.clone());
+        module
+            .register_method("eth_cached", |_, calls, _| calls.fetch_add(1, Ordering::Relaxed))
+            .unwrap();
+        module
+            .register_method("eth_uncached", |_, calls, _| calls.fetch_add(1, Ordering::Relaxed))
+            .unwrap();
+
+        let response_cache =
+            Arc::new(ResponseCache::new(ResponseCacheConfig::default().with_method("eth_cached")));
+        let methods = apply_response_cache(module, Some(&response_cache));
+
+        assert_eq!(methods.call::<_, u64>("eth_cached", ["0x1"]).await.unwrap(), 0);
+        assert_eq!(methods.call::<_, u64>("eth_cached", ["0x1"]).await.unwrap(), 0);
+        // different params and moving block tags are not served from the cache
+        assert_eq!(methods.call::<_, u64>("eth_cached", ["0x2"]).await.unwrap(), 1);
+        assert_eq!(methods.call::<_, u64>("eth_cached", ["latest"]).await.unwrap(), 2);
+        assert_eq!(methods.call::<_, u64>("eth_cached", ["latest"]).await.unwrap(), 3);
+        assert_eq!(methods.call::<_, u64>("eth_uncached", ["0x1"]).await.unwrap(), 4);
+        assert_eq!(methods.call::<_, u64>("eth_uncached", ["0x1"]).await.unwrap(), 5);
     }
 }
@@ -18,7 +18,10 @@
 //! ```
 //! use reth_evm::ConfigureEvm;
 //! use reth_network_api::{NetworkInfo, Peers};
-//! use reth_provider::{AccountReader, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider};
+//! use reth_provider::{
+//!     AccountReader, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider,
+//!     StaticFileProviderFactory,
+//! };
 //! use reth_rpc::EthApi;
 //! use reth_rpc_builder::{
 //!     RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
@@ -33,7 +36,7 @@
 //!     events: Events,
 //!     evm_config: EvmConfig,
 //! ) where
-//!     Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+//!     Provider: FullRpcProvider + AccountReader + ChangeSetReader + StaticFileProviderFactory,
 //!     Pool: TransactionPool + 'static,
 //!     Network: NetworkInfo + Peers + Clone + 'static,
 //!     Events: CanonStateSubscriptions + Clone + 'static,
@@ -54,7 +57,8 @@
 //!         events,
 //!         evm_config,
 //!     )
-//!     .build(transports, Box::new(EthApi::with_spawner));
+//!     .build(transports, Box::new(EthApi::with_spawner))
+//!     .unwrap();
 //!     let handle = RpcServerConfig::default()
 //!         .with_http(ServerBuilder::default())
 //!         .start(&transport_modules)
@@ -69,7 +73,10 @@
 //! use reth_engine_primitives::EngineTypes;
 //! use reth_evm::ConfigureEvm;
 //! use reth_network_api::{NetworkInfo, Peers};
-//! use reth_provider::{AccountReader, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider};
+//! use reth_provider::{
+//!     AccountReader, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider,
+//!     StaticFileProviderFactory,
+//! };
 //! use reth_rpc::EthApi;
 //! use reth_rpc_api::EngineApiServer;
 //! use reth_rpc_builder::{
@@ -88,7 +95,7 @@
 //!     engine_api: EngineApi,
 //!     evm_config: EvmConfig,
 //! ) where
-//!     Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+//!     Provider: FullRpcProvider + AccountReader + ChangeSetReader + StaticFileProviderFactory,
 //!     Pool: TransactionPool + 'static,
 //!     Network: NetworkInfo + Peers + Clone + 'static,
 //!     Events: CanonStateSubscriptions + Clone + 'static,
@@ -113,8 +120,9 @@
 //!     );
 //!
 //!     // configure the server modules
-//!     let (modules, auth_module, _registry) =
-//!         builder.build_with_auth_server(transports, engine_api, Box::new(EthApi::with_spawner));
+//!     let (modules, auth_module, _registry) = builder
+//!         .build_with_auth_server(transports, engine_api, Box::new(EthApi::with_spawner))
+//!         .unwrap();
 //!
 //!     // start the servers
 //!     let auth_config = AuthServerConfig::builder(JwtSecret::random()).build();
@@ -140,7 +148,7 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use error::{ConflictingModules, RpcError, ServerKind};
+use error::{ConflictingModules, EthHandlersError, RpcError, ServerKind};
 use eth::DynEthApiBuilder;
 use http::{header::AUTHORIZATION, HeaderMap};
 use jsonrpsee::{
@@ -156,16 +164,17 @@ use reth_evm::ConfigureEvm;
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_provider::{
     AccountReader, BlockReader, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, FullRpcProvider, StateProviderFactory,
+    EvmEnvProvider, FullRpcProvider, StateProviderFactory, StaticFileProviderFactory,
 };
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi,
-    TxPoolApi, Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthBundle, EthFilter, EthSimulateBundle, NetApi,
+    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
     helpers::{
-        Call, EthApiSpec, EthTransactions, LoadPendingBlock, TraceExt, UpdateRawTxForwarder,
+        Call, EthApiSpec, EthCall, EthTransactions, LoadBlock, LoadPendingBlock, TraceExt,
+        UpdateRawTxForwarder,
     },
     EthApiServer, FullEthApiServer, RawTransactionForwarder,
 };
@@ -203,7 +212,10 @@ pub mod error;
 
 /// Eth utils
 pub mod eth;
-pub use eth::EthHandlers;
+pub use eth::{
+    EthHandlers, EthRpcModuleBuilder, EthStandby, EthTaskHandle, EthTaskHandles,
+    EthTransportConfigs, EthTransportHandlers,
+};
 
 // Rpc server metrics
 mod metrics;
@@ -223,7 +235,7 @@ pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi>(
     eth: DynEthApiBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi>,
 ) -> Result<RpcServerHandle, RpcError>
 where
-    Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+    Provider: FullRpcProvider + AccountReader + ChangeSetReader + StaticFileProviderFactory,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
@@ -409,7 +421,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
 impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig>
 where
-    Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+    Provider: FullRpcProvider + AccountReader + ChangeSetReader + StaticFileProviderFactory,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
@@ -422,17 +434,22 @@ where
     /// This behaves exactly as [`RpcModuleBuilder::build`] for the [`TransportRpcModules`], but
     /// also configures the auth (engine api) server, which exposes a subset of the `eth_`
     /// namespace.
+    ///
+    /// Fails if the `eth` handlers can't be built, see [`RpcRegistryInner::new`].
     #[allow(clippy::type_complexity)]
     pub fn build_with_auth_server<EngineApi, EngineT, EthApi>(
         self,
         module_config: TransportRpcModuleConfig,
         engine: EngineApi,
         eth: DynEthApiBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi>,
-    ) -> (
-        TransportRpcModules,
-        AuthRpcModule,
-        RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi>,
-    )
+    ) -> Result<
+        (
+            TransportRpcModules,
+            AuthRpcModule,
+            RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi>,
+        ),
+        EthHandlersError,
+    >
     where
         EngineT: EngineTypes,
         EngineApi: EngineApiServer<EngineT>,
@@ -444,19 +461,21 @@ where
 
         let mut registry = RpcRegistryInner::new(
             provider, pool, network, executor, events, config, evm_config, eth,
-        );
+        )?;
 
         let modules = registry.create_transport_rpc_modules(module_config);
 
         let auth_module = registry.create_auth_module(engine);
 
-        (modules, auth_module, registry)
+        Ok((modules, auth_module, registry))
     }
 
     /// Converts the builder into a [`RpcRegistryInner`] which can be used to create all
     /// components.
     ///
-    /// This is useful for getting access to API handlers directly:
+    /// This is useful for getting access to API handlers directly.
+    ///
+    /// Fails if the `eth` handlers can't be built, see [`RpcRegistryInner::new`].
     ///
     /// # Example
     ///
@@ -477,7 +496,8 @@ where
     ///         .with_executor(TokioTaskExecutor::default())
     ///         .with_events(TestCanonStateSubscriptions::default())
     ///         .with_evm_config(evm)
-    ///         .into_registry(Default::default(), Box::new(EthApi::with_spawner));
+    ///         .into_registry(Default::default(), Box::new(EthApi::with_spawner))
+    ///         .unwrap();
     ///
     ///     let eth_api = registry.eth_api();
     /// }
@@ -486,7 +506,7 @@ where
         self,
         config: RpcModuleConfig,
         eth: DynEthApiBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi>,
-    ) -> RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi>
+    ) -> Result<RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi>, EthHandlersError>
     where
         EthApi: 'static,
     {
@@ -496,11 +516,13 @@ where
 
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
     /// be used to start the transport server(s).
+    ///
+    /// Fails if the `eth` handlers can't be built, see [`RpcRegistryInner::new`].
     pub fn build<EthApi>(
         self,
         module_config: TransportRpcModuleConfig,
         eth: DynEthApiBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi>,
-    ) -> TransportRpcModules<()>
+    ) -> Result<TransportRpcModules<()>, EthHandlersError>
     where
         EthApi: FullEthApiServer,
     {
@@ -520,15 +542,16 @@ where
                 config.unwrap_or_default(),
                 evm_config,
                 eth,
-            );
+            )?;
 
             modules.config = module_config;
-            modules.http = registry.maybe_module(http.as_ref());
-            modules.ws = registry.maybe_module(ws.as_ref());
-            modules.ipc = registry.maybe_module(ipc.as_ref());
+            modules.http =
+                registry.maybe_transport_module(http.as_ref(), registry.eth.http.clone());
+            modules.ws = registry.maybe_transport_module(ws.as_ref(), registry.eth.ws.clone());
+            modules.ipc = registry.maybe_transport_module(ipc.as_ref(), registry.eth.ipc.clone());
         }
 
-        modules
+        Ok(modules)
     }
 }
 
@@ -579,7 +602,7 @@ pub struct RpcModuleConfigBuilder {
 
 impl RpcModuleConfigBuilder {
     /// Configures a custom eth namespace config
-    pub const fn eth(mut self, eth: EthConfig) -> Self {
+    pub fn eth(mut self, eth: EthConfig) -> Self {
         self.eth = Some(eth);
         self
     }
@@ -627,7 +650,14 @@ pub struct RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi> {
 impl<Provider, Pool, Network, Tasks, Events, EthApi>
     RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi>
 where
-    Provider: StateProviderFactory + BlockReader + EvmEnvProvider + Clone + Unpin + 'static,
+    Provider: StateProviderFactory
+        + BlockReader
+        + EvmEnvProvider
+        + ChainSpecProvider
+        + StaticFileProviderFactory
+        + Clone
+        + Unpin
+        + 'static,
     Pool: Send + Sync + Clone + 'static,
     Network: Clone + 'static,
     Events: CanonStateSubscriptions + Clone + 'static,
@@ -635,6 +665,9 @@ where
     EthApi: 'static,
 {
     /// Creates a new, empty instance.
+    ///
+    /// Fails if the `eth` handlers can't be built, e.g. if the blocking task pool can't be
+    /// spawned.
     #[allow(clippy::too_many_arguments)]
     pub fn new<EvmConfig>(
         provider: Provider,
@@ -653,12 +686,10 @@ where
             Events,
             EthApi,
         >,
-    ) -> Self
+    ) -> Result<Self, EthHandlersError>
     where
         EvmConfig: ConfigureEvm,
     {
-        let blocking_pool_guard = BlockingTaskGuard::new(config.eth.max_tracing_requests);
-
         let eth = EthHandlers::builder(
             provider.clone(),
             pool.clone(),
@@ -669,8 +700,24 @@ where
             events.clone(),
             eth_api_builder,
         )
-        .build();
+        .build()?;
+
+        Ok(Self::with_eth_handlers(provider, pool, network, executor, events, eth))
+    }
 
+    /// Creates a new, empty instance with the given `eth` handlers, e.g. to install handlers that
+    /// were built with a response middleware or a rate limiter, see [`EthHandlers::builder`].
+    ///
+    /// The `debug_` and `trace_` namespaces share the tracing limit of the handlers.
+    pub fn with_eth_handlers(
+        provider: Provider,
+        pool: Pool,
+        network: Network,
+        executor: Tasks,
+        events: Events,
+        eth: EthHandlers<Provider, Pool, Network, Events, EthApi>,
+    ) -> Self {
+        let blocking_pool_guard = eth.tracing_task_guard.clone();
         Self {
             provider,
             pool,
@@ -751,6 +798,9 @@ where
     pub fn set_eth_raw_transaction_forwarder(&self, forwarder: Arc<dyn RawTransactionForwarder>) {
         // in case the eth api has been created before the forwarder was set: <https://github.com/paradigmxyz/reth/issues/8661>
         self.eth.api.set_eth_raw_transaction_forwarder(forwarder.clone());
+        for handlers in [&self.eth.http, &self.eth.ws, &self.eth.ipc].into_iter().flatten() {
+            handlers.api.set_eth_raw_transaction_forwarder(forwarder.clone());
+        }
     }
 }
 
@@ -808,7 +858,8 @@ where
         EthApi: EthApiServer,
     {
         let eth_api = self.eth_api().clone();
-        self.modules.insert(RethRpcModule::Eth, eth_api.into_rpc().into());
+        let module = self.eth.wrap_eth_methods(eth_api.into_rpc());
+        self.modules.insert(RethRpcModule::Eth, module);
         self
     }
 
@@ -822,7 +873,7 @@ where
         EthApi: EthApiServer + TraceExt,
     {
         let otterscan_api = self.otterscan_api();
-        self.modules.insert(RethRpcModule::Ots, otterscan_api.into_rpc().into());
+        self.modules.insert(RethRpcModule::Ots, self.eth.gate_standby(otterscan_api.into_rpc()));
         self
     }
 
@@ -836,7 +887,7 @@ where
         EthApi: EthApiSpec + EthTransactions + TraceExt,
     {
         let debug_api = self.debug_api();
-        self.modules.insert(RethRpcModule::Debug, debug_api.into_rpc().into());
+        self.modules.insert(RethRpcModule::Debug, self.eth.gate_standby(debug_api.into_rpc()));
         self
     }
 
@@ -850,7 +901,7 @@ where
         EthApi: TraceExt,
     {
         let trace_api = self.trace_api();
-        self.modules.insert(RethRpcModule::Trace, trace_api.into_rpc().into());
+        self.modules.insert(RethRpcModule::Trace, self.eth.gate_standby(trace_api.into_rpc()));
         self
     }
 
@@ -912,6 +963,21 @@ where
         EthBundle::new(eth_api, self.blocking_pool_guard.clone())
     }
 
+    /// Instantiates [`EthSimulateBundle`] Api
+    ///
+    /// Simulations share their permits with the tracing calls of the registry.
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn simulate_bundle_api(&self) -> EthSimulateBundle<EthApi>
+    where
+        EthApi: EthCall + LoadBlock,
+    {
+        let eth_api = self.eth_api().clone();
+        EthSimulateBundle::new(eth_api, self.blocking_pool_guard.clone())
+    }
+
     /// Instantiates `OtterscanApi`
     ///
     /// # Panics
@@ -936,6 +1002,7 @@ where
     {
         let eth_api = self.eth_api().clone();
         DebugApi::new(self.provider.clone(), eth_api, self.blocking_pool_guard.clone())
+            .with_max_trace_memory(self.eth.config.max_trace_memory_bytes)
     }
 
     /// Instantiates `NetApi`
@@ -985,7 +1052,39 @@ where
         let eth_handlers = self.eth_handlers();
         let engine_eth = EngineEthApi::new(eth_handlers.api.clone(), eth_handlers.filter.clone());
 
-        module.merge(engine_eth.into_rpc()).expect("No conflicting methods");
+        module
+            .merge(eth_handlers.gate_standby(engine_eth.into_rpc()))
+            .expect("No conflicting methods");
+
+        AuthRpcModule { inner: module }
+    }
+
+    /// Configures the auth module like [`Self::create_auth_module`], but only includes the minimal
+    /// subset of `eth_` handlers that consensus clients require, see
+    /// [`MINIMAL_ENGINE_ETH_METHODS`](reth_rpc::MINIMAL_ENGINE_ETH_METHODS).
+    ///
+    /// The `eth_` handlers share the caches of the handlers of this registry.
+    ///
+    /// Note: This does _not_ register the `engine_` in this registry.
+    pub fn create_minimal_auth_module<EngineApi, EngineT>(
+        &self,
+        engine_api: EngineApi,
+    ) -> AuthRpcModule
+    where
+        EngineT: EngineTypes,
+        EngineApi: EngineApiServer<EngineT>,
+    {
+        let mut module = RpcModule::new(());
+
+        module.merge(engine_api.into_rpc()).expect("No conflicting methods");
+
+        // also merge the minimal subset of `eth_` handlers
+        let eth_handlers = self.eth_handlers();
+        let engine_eth = EngineEthApi::new(eth_handlers.api.clone(), eth_handlers.filter.clone());
+
+        module
+            .merge(eth_handlers.gate_standby(engine_eth.into_minimal_rpc()))
+            .expect("No conflicting methods");
 
         AuthRpcModule { inner: module }
     }
@@ -995,23 +1094,72 @@ where
         config.map(|config| self.module_for(config))
     }
 
+    /// Helper function to create the [`RpcModule`] of a transport if it's not `None`.
+    ///
+    /// If the [`EthConfig`] of the transport is overridden, its `eth_` namespace is served by the
+    /// given [`EthTransportHandlers`] instead of the shared handlers.
+    fn maybe_transport_module(
+        &mut self,
+        config: Option<&RpcModuleSelection>,
+        handlers: Option<EthTransportHandlers<Provider, Pool, EthApi>>,
+    ) -> Option<RpcModule<()>> {
+        let config = config?;
+        let Some(EthTransportHandlers { api, filter, .. }) = handlers else {
+            return Some(self.module_for(config))
+        };
+
+        let namespaces: Vec<_> = config.iter_selection().collect();
+        let all_methods = self.reth_methods(namespaces.iter().copied());
+        let mut module = RpcModule::new(());
+        for (namespace, methods) in namespaces.into_iter().zip(all_methods) {
+            let methods = match namespace {
+                RethRpcModule::Eth => Self::eth_module(&self.eth, api.clone(), filter.clone()),
+                _ => methods,
+            };
+            module.merge(methods).expect("No conflicts");
+        }
+        Some(module)
+    }
+
     /// Configure a [`TransportRpcModules`] using the current registry. This
     /// creates [`RpcModule`] instances for the modules selected by the
     /// `config`.
+    ///
+    /// # Panics
+    ///
+    /// If the [`EthHandlers::custom_methods`] conflict with the selected modules, see
+    /// [`Self::try_create_transport_rpc_modules`].
     pub fn create_transport_rpc_modules(
         &mut self,
         config: TransportRpcModuleConfig,
     ) -> TransportRpcModules<()> {
+        self.try_create_transport_rpc_modules(config).expect("No conflicting custom methods")
+    }
+
+    /// Configure a [`TransportRpcModules`] using the current registry. This
+    /// creates [`RpcModule`] instances for the modules selected by the
+    /// `config`.
+    ///
+    /// The `eth_` namespace of transports with an overridden [`EthConfig`] is served by their
+    /// [`EthTransportHandlers`], see [`EthHandlers::http_config`].
+    ///
+    /// The [`EthHandlers::custom_methods`] are merged into every configured transport. Fails if
+    /// they register a method of the selected modules.
+    pub fn try_create_transport_rpc_modules(
+        &mut self,
+        config: TransportRpcModuleConfig,
+    ) -> Result<TransportRpcModules<()>, RegisterMethodError> {
         let mut modules = TransportRpcModules::default();
-        let http = self.maybe_module(config.http.as_ref());
-        let ws = self.maybe_module(config.ws.as_ref());
-        let ipc = self.maybe_module(config.ipc.as_ref());
+        let http = self.maybe_transport_module(config.http.as_ref(), self.eth.http.clone());
+        let ws = self.maybe_transport_module(config.ws.as_ref(), self.eth.ws.clone());
+        let ipc = self.maybe_transport_module(config.ipc.as_ref(), self.eth.ipc.clone());
 
         modules.config = config;
         modules.http = http;
         modules.ws = ws;
         modules.ipc = ipc;
-        modules
+        modules.merge_configured(self.eth.custom_methods.clone())?;
+        Ok(modules)
     }
 
     /// Populates a new [`RpcModule`] based on the selected [`RethRpcModule`]s in the given
@@ -1025,6 +1173,23 @@ where
         module
     }
 
+    /// Merges the given core and filter handlers with the subscription handler of the given
+    /// [`EthHandlers`] into the `eth_` namespace, wrapped by [`EthHandlers::wrap_eth_methods`].
+    fn eth_module(
+        eth: &EthHandlers<Provider, Pool, Network, Events, EthApi>,
+        eth_api: EthApi,
+        eth_filter: EthFilter<Provider, Pool>,
+    ) -> Methods {
+        // merge all eth handlers
+        let mut module = eth_api.into_rpc();
+        module.merge(eth_filter.into_rpc()).expect("No conflicts");
+        // subscriptions can't be wrapped
+        let mut module = eth.wrap_eth_methods(module);
+        module.merge(eth.pubsub.clone().into_rpc()).expect("No conflicts");
+
+        module
+    }
+
     /// Returns the [Methods] for the given [`RethRpcModule`]
     ///
     /// If this is the first time the namespace is requested, a new instance of API implementation
@@ -1037,8 +1202,7 @@ where
         &mut self,
         namespaces: impl Iterator<Item = RethRpcModule>,
     ) -> Vec<Methods> {
-        let EthHandlers { api: eth_api, filter: eth_filter, pubsub: eth_pubsub, .. } =
-            self.eth_handlers().clone();
+        let EthHandlers { api: eth_api, filter: eth_filter, .. } = self.eth_handlers().clone();
 
         // Create a copy, so we can list out all the methods for rpc_ api
         let namespaces: Vec<_> = namespaces.collect();
@@ -1054,34 +1218,32 @@ where
                                 .into_rpc()
                                 .into()
                         }
-                        RethRpcModule::Debug => DebugApi::new(
-                            self.provider.clone(),
-                            eth_api.clone(),
-                            self.blocking_pool_guard.clone(),
-                        )
-                        .into_rpc()
-                        .into(),
+                        RethRpcModule::Debug => self.eth.gate_standby(
+                            DebugApi::new(
+                                self.provider.clone(),
+                                eth_api.clone(),
+                                self.blocking_pool_guard.clone(),
+                            )
+                            .with_max_trace_memory(self.eth.config.max_trace_memory_bytes)
+                            .into_rpc(),
+                        ),
                         RethRpcModule::Eth => {
-                            // merge all eth handlers
-                            let mut module = eth_api.clone().into_rpc();
-                            module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
-                            module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
-
-                            module.into()
+                            Self::eth_module(&self.eth, eth_api.clone(), eth_filter.clone())
                         }
                         RethRpcModule::Net => {
                             NetApi::new(self.network.clone(), eth_api.clone()).into_rpc().into()
                         }
-                        RethRpcModule::Trace => TraceApi::new(
-                            self.provider.clone(),
-                            eth_api.clone(),
-                            self.blocking_pool_guard.clone(),
-                        )
-                        .into_rpc()
-                        .into(),
+                        RethRpcModule::Trace => self.eth.gate_standby(
+                            TraceApi::new(
+                                self.provider.clone(),
+                                eth_api.clone(),
+                                self.blocking_pool_guard.clone(),
+                            )
+                            .into_rpc(),
+                        ),
                         RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
                         RethRpcModule::Txpool => {
-                            TxPoolApi::new(self.pool.clone()).into_rpc().into()
+                            self.eth.gate_standby(TxPoolApi::new(self.pool.clone()).into_rpc())
                         }
                         RethRpcModule::Rpc => RPCApi::new(
                             namespaces
@@ -1091,16 +1253,28 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
+                        RethRpcModule::Ots => {
+                            self.eth.gate_standby(OtterscanApi::new(eth_api.clone()).into_rpc())
+                        }
                         RethRpcModule::Reth => {
                             RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
                                 .into_rpc()
                                 .into()
                         }
                         RethRpcModule::EthCallBundle => {
-                            EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
-                                .into_rpc()
-                                .into()
+                            let mut module =
+                                EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
+                                    .into_rpc();
+                            module
+                                .merge(
+                                    EthSimulateBundle::new(
+                                        eth_api.clone(),
+                                        self.blocking_pool_guard.clone(),
+                                    )
+                                    .into_rpc(),
+                                )
+                                .expect("No conflicts");
+                            self.eth.gate_standby(module)
                         }
                     })
                     .clone()
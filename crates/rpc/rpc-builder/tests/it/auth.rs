@@ -1,9 +1,10 @@
 //! Auth server tests
 
-use crate::utils::launch_auth;
+use crate::utils::{launch_auth, test_engine_api, test_rpc_builder};
 use jsonrpsee::core::client::{ClientT, SubscriptionClientT};
 use reth_ethereum_engine_primitives::EthEngineTypes;
 use reth_primitives::{Block, U64};
+use reth_rpc::{EthApi, MINIMAL_ENGINE_ETH_METHODS};
 use reth_rpc_api::clients::EngineApiClient;
 use reth_rpc_layer::JwtSecret;
 use reth_rpc_types::engine::{ForkchoiceState, PayloadId, TransitionConfiguration};
@@ -45,3 +46,24 @@ async fn test_auth_endpoints_ws() {
     let client = handle.ws_client().await;
     test_basic_engine_calls(&client).await
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_minimal_auth_module_eth_methods() {
+    let registry = test_rpc_builder()
+        .into_registry(Default::default(), Box::new(EthApi::with_spawner))
+        .unwrap();
+
+    let mut module = registry.create_auth_module(test_engine_api());
+    let methods = module.module_mut().method_names().collect::<Vec<_>>();
+    assert!(methods.contains(&"eth_getLogs"));
+
+    let mut module = registry.create_minimal_auth_module(test_engine_api());
+    let methods = module.module_mut().method_names().collect::<Vec<_>>();
+    let eth_methods =
+        methods.iter().filter(|method| method.starts_with("eth_")).collect::<Vec<_>>();
+    assert_eq!(eth_methods.len(), MINIMAL_ENGINE_ETH_METHODS.len());
+    for method in MINIMAL_ENGINE_ETH_METHODS {
+        assert!(methods.contains(method));
+    }
+    assert!(methods.contains(&"engine_exchangeCapabilities"));
+}
@@ -1,6 +1,7 @@
 mod auth;
 mod http;
 mod middleware;
+mod registry;
 mod serde;
 mod startup;
 pub mod utils;
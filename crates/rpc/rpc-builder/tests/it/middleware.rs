@@ -4,10 +4,17 @@ use jsonrpsee::{
     types::Request,
     MethodResponse,
 };
+use reth_evm_ethereum::EthEvmConfig;
+use reth_network_api::noop::NoopNetwork;
+use reth_provider::test_utils::{NoopProvider, TestCanonStateSubscriptions};
 use reth_rpc::EthApi;
-use reth_rpc_builder::{RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_builder::{EthHandlers, RpcRegistryInner, RpcServerConfig, TransportRpcModuleConfig};
 use reth_rpc_eth_api::EthApiClient;
-use reth_rpc_server_types::RpcModuleSelection;
+use reth_rpc_eth_types::{EthConfig, EthResponseMiddleware};
+use reth_rpc_server_types::{RethRpcModule, RpcModuleSelection};
+use reth_tasks::TokioTaskExecutor;
+use reth_transaction_pool::test_utils::{TestPool, TestPoolBuilder};
+use serde_json::Value;
 use std::{
     future::Future,
     pin::Pin,
@@ -59,10 +66,12 @@ where
 #[tokio::test(flavor = "multi_thread")]
 async fn test_rpc_middleware() {
     let builder = test_rpc_builder();
-    let modules = builder.build(
-        TransportRpcModuleConfig::set_http(RpcModuleSelection::All),
-        Box::new(EthApi::with_spawner),
-    );
+    let modules = builder
+        .build(
+            TransportRpcModuleConfig::set_http(RpcModuleSelection::All),
+            Box::new(EthApi::with_spawner),
+        )
+        .unwrap();
 
     let mylayer = MyMiddlewareLayer::default();
 
@@ -78,3 +87,45 @@ async fn test_rpc_middleware() {
     let count = mylayer.count.load(Ordering::Relaxed);
     assert_eq!(count, 1);
 }
+
+#[derive(Debug)]
+struct ReplaceChainId;
+
+impl EthResponseMiddleware for ReplaceChainId {
+    fn on_response(&self, method: &str, result: Value) -> Value {
+        if method == "eth_chainId" {
+            Value::from("0x2a")
+        } else {
+            result
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_eth_response_middleware() {
+    let provider = NoopProvider::default();
+    let pool: TestPool = TestPoolBuilder::default().into();
+    let network = NoopNetwork::default();
+    let executor = TokioTaskExecutor::default();
+    let events = TestCanonStateSubscriptions::default();
+    let eth = EthHandlers::builder(
+        provider.clone(),
+        pool.clone(),
+        network.clone(),
+        EthEvmConfig::default(),
+        EthConfig::default(),
+        executor.clone(),
+        events.clone(),
+        Box::new(EthApi::with_spawner),
+    )
+    .with_response_middleware(Arc::new(ReplaceChainId))
+    .build()
+    .unwrap();
+
+    let mut registry =
+        RpcRegistryInner::with_eth_handlers(provider, pool, network, executor, events, eth);
+    let module = registry.module_for(&RpcModuleSelection::from([RethRpcModule::Eth]));
+
+    let chain_id: String = module.call("eth_chainId", [(); 0]).await.unwrap();
+    assert_eq!(chain_id, "0x2a");
+}
@@ -0,0 +1,184 @@
+//! Registry tests
+
+use jsonrpsee::{
+    core::{client::ClientT, server::MethodsError, RegisterMethodError},
+    rpc_params,
+    types::error::METHOD_NOT_FOUND_CODE,
+    Methods, RpcModule,
+};
+use reth_evm_ethereum::EthEvmConfig;
+use reth_network_api::noop::NoopNetwork;
+use reth_provider::test_utils::{NoopProvider, TestCanonStateSubscriptions};
+use reth_rpc::EthApi;
+use reth_rpc_builder::{
+    EthHandlers, EthTransportConfigs, RpcServerConfig, TransportRpcModuleConfig,
+};
+use reth_rpc_eth_types::{EthApiBuilderCtx, EthConfig};
+use reth_rpc_server_types::RethRpcModule;
+use reth_tasks::TokioTaskExecutor;
+use reth_transaction_pool::test_utils::{TestPool, TestPoolBuilder};
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+use crate::utils::{test_address, test_engine_api, test_eth_handlers_builder, test_registry};
+
+fn hello_module<Provider, Pool, EvmConfig, Network, Tasks, Events>(
+    _: &EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>,
+) -> RpcModule<()> {
+    let mut module = RpcModule::new(());
+    module.register_method("custom_hello", |_, _, _| "hello").unwrap();
+    module
+}
+
+fn chain_id_module<Provider, Pool, EvmConfig, Network, Tasks, Events>(
+    _: &EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>,
+) -> RpcModule<()> {
+    let mut module = RpcModule::new(());
+    module.register_method("eth_chainId", |_, _, _| "0x2a").unwrap();
+    module
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_custom_methods_are_served() {
+    let eth = test_eth_handlers_builder().with_module_builder(hello_module).build().unwrap();
+    let mut registry = test_registry(eth);
+    let modules = registry
+        .try_create_transport_rpc_modules(TransportRpcModuleConfig::set_http([RethRpcModule::Eth]))
+        .unwrap();
+
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .start(&modules)
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+
+    let hello: String = client.request("custom_hello", rpc_params![]).await.unwrap();
+    assert_eq!(hello, "hello");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_conflicting_custom_methods_are_rejected() {
+    let eth = test_eth_handlers_builder().with_module_builder(chain_id_module).build().unwrap();
+    let mut registry = test_registry(eth);
+
+    let result = registry
+        .try_create_transport_rpc_modules(TransportRpcModuleConfig::set_http([RethRpcModule::Eth]));
+    assert!(
+        matches!(result, Err(RegisterMethodError::AlreadyRegistered(ref method)) if method == "eth_chainId"),
+        "custom method should conflict with the eth namespace"
+    );
+}
+
+async fn assert_method_not_found(methods: &Methods, method: &str) {
+    let err = methods.call::<_, serde_json::Value>(method, rpc_params![]).await.unwrap_err();
+    assert!(
+        matches!(err, MethodsError::JsonRpc(ref err) if err.code() == METHOD_NOT_FOUND_CODE),
+        "{method} should be rejected on standby, got {err:?}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_standby_gates_all_namespaces() {
+    let eth = test_eth_handlers_builder().standby(true).build().unwrap();
+    let mut registry = test_registry(eth);
+
+    let mut module = RpcModule::new(());
+    for methods in registry.reth_methods(
+        [RethRpcModule::Eth, RethRpcModule::Debug, RethRpcModule::Trace, RethRpcModule::Txpool]
+            .into_iter(),
+    ) {
+        module.merge(methods).unwrap();
+    }
+    let mut auth = registry.create_auth_module(test_engine_api());
+    let auth = auth.module_mut().clone();
+
+    for method in ["eth_chainId", "debug_getRawHeader", "trace_block", "txpool_status"] {
+        assert_method_not_found(&module, method).await;
+    }
+    assert_method_not_found(&auth, "eth_chainId").await;
+    let err =
+        module.subscribe_unbounded("eth_subscribe", rpc_params!["newHeads"]).await.unwrap_err();
+    assert!(
+        matches!(err, MethodsError::JsonRpc(ref err) if err.code() == METHOD_NOT_FOUND_CODE),
+        "subscription should be rejected on standby, got {err:?}"
+    );
+
+    registry.eth_handlers().promote();
+
+    module.call::<_, serde_json::Value>("eth_chainId", rpc_params![]).await.unwrap();
+    module.call::<_, serde_json::Value>("txpool_status", rpc_params![]).await.unwrap();
+    auth.call::<_, serde_json::Value>("eth_chainId", rpc_params![]).await.unwrap();
+    module.subscribe_unbounded("eth_subscribe", rpc_params!["newHeads"]).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_transport_config_overrides() {
+    let http_config = EthConfig::default().chain_id_override(1337);
+    let eth = test_eth_handlers_builder()
+        .with_transport_configs(EthTransportConfigs::default().with_http(http_config.clone()))
+        .build()
+        .unwrap();
+    assert_eq!(eth.http_config(), &http_config);
+    assert_eq!(eth.ws_config(), &eth.config);
+
+    let mut registry = test_registry(eth);
+    let modules = registry.create_transport_rpc_modules(
+        TransportRpcModuleConfig::set_http([RethRpcModule::Eth, RethRpcModule::Net])
+            .with_ws([RethRpcModule::Eth]),
+    );
+
+    let http = modules.http.unwrap();
+    let chain_id: serde_json::Value = http.call("eth_chainId", rpc_params![]).await.unwrap();
+    assert_eq!(chain_id, "0x539");
+    http.call::<_, serde_json::Value>("net_version", rpc_params![]).await.unwrap();
+
+    let ws = modules.ws.unwrap();
+    let chain_id: serde_json::Value = ws.call("eth_chainId", rpc_params![]).await.unwrap();
+    assert_eq!(chain_id, "0x1");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_eth_api_builder_ctx_describes_config() {
+    let description = Arc::new(Mutex::new(None));
+    let captured = description.clone();
+    let config = EthConfig::default()
+        .rpc_gas_cap(1_000_000)
+        .disable_fee_history(true)
+        .subscription_log_backfill(true)
+        .strip_revert_data(true);
+    EthHandlers::builder(
+        NoopProvider::default(),
+        TestPoolBuilder::default().into(),
+        NoopNetwork::default(),
+        EthEvmConfig::default(),
+        config,
+        TokioTaskExecutor::default(),
+        TestCanonStateSubscriptions::default(),
+        Box::new(
+            move |ctx: &EthApiBuilderCtx<
+                NoopProvider,
+                TestPool,
+                EthEvmConfig,
+                NoopNetwork,
+                TokioTaskExecutor,
+                TestCanonStateSubscriptions,
+            >| {
+                *captured.lock().unwrap() = Some(ctx.describe_with_chain());
+                EthApi::with_spawner(ctx)
+            },
+        ),
+    )
+    .build()
+    .unwrap();
+
+    let description = description.lock().unwrap().take().unwrap();
+    assert_eq!(description.chain_id, Some(1));
+    assert_eq!(description.rpc_gas_cap, 1_000_000);
+    assert_eq!(
+        description.features,
+        BTreeSet::from(["bloom_prefilter", "log_backfill", "strip_revert_data"])
+    );
+}
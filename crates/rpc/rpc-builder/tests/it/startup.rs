@@ -27,10 +27,12 @@ async fn test_http_addr_in_use() {
     let handle = launch_http(vec![RethRpcModule::Admin]).await;
     let addr = handle.http_local_addr().unwrap();
     let builder = test_rpc_builder();
-    let server = builder.build(
-        TransportRpcModuleConfig::set_http(vec![RethRpcModule::Admin]),
-        Box::new(EthApi::with_spawner),
-    );
+    let server = builder
+        .build(
+            TransportRpcModuleConfig::set_http(vec![RethRpcModule::Admin]),
+            Box::new(EthApi::with_spawner),
+        )
+        .unwrap();
     let result =
         RpcServerConfig::http(Default::default()).with_http_address(addr).start(&server).await;
     let err = result.unwrap_err();
@@ -42,10 +44,12 @@ async fn test_ws_addr_in_use() {
     let handle = launch_ws(vec![RethRpcModule::Admin]).await;
     let addr = handle.ws_local_addr().unwrap();
     let builder = test_rpc_builder();
-    let server = builder.build(
-        TransportRpcModuleConfig::set_ws(vec![RethRpcModule::Admin]),
-        Box::new(EthApi::with_spawner),
-    );
+    let server = builder
+        .build(
+            TransportRpcModuleConfig::set_ws(vec![RethRpcModule::Admin]),
+            Box::new(EthApi::with_spawner),
+        )
+        .unwrap();
     let result = RpcServerConfig::ws(Default::default()).with_ws_address(addr).start(&server).await;
     let err = result.unwrap_err();
     assert!(is_addr_in_use_kind(&err, ServerKind::WS(addr)), "{err}");
@@ -62,11 +66,13 @@ async fn test_launch_same_port() {
 #[tokio::test(flavor = "multi_thread")]
 async fn test_launch_same_port_different_modules() {
     let builder = test_rpc_builder();
-    let server = builder.build(
-        TransportRpcModuleConfig::set_ws(vec![RethRpcModule::Admin])
-            .with_http(vec![RethRpcModule::Eth]),
-        Box::new(EthApi::with_spawner),
-    );
+    let server = builder
+        .build(
+            TransportRpcModuleConfig::set_ws(vec![RethRpcModule::Admin])
+                .with_http(vec![RethRpcModule::Eth]),
+            Box::new(EthApi::with_spawner),
+        )
+        .unwrap();
     let addr = test_address();
     let res = RpcServerConfig::ws(Default::default())
         .with_ws_address(addr)
@@ -84,11 +90,13 @@ async fn test_launch_same_port_different_modules() {
 #[tokio::test(flavor = "multi_thread")]
 async fn test_launch_same_port_same_cors() {
     let builder = test_rpc_builder();
-    let server = builder.build(
-        TransportRpcModuleConfig::set_ws(vec![RethRpcModule::Eth])
-            .with_http(vec![RethRpcModule::Eth]),
-        Box::new(EthApi::with_spawner),
-    );
+    let server = builder
+        .build(
+            TransportRpcModuleConfig::set_ws(vec![RethRpcModule::Eth])
+                .with_http(vec![RethRpcModule::Eth]),
+            Box::new(EthApi::with_spawner),
+        )
+        .unwrap();
     let addr = test_address();
     let res = RpcServerConfig::ws(Default::default())
         .with_ws_address(addr)
@@ -104,11 +112,13 @@ async fn test_launch_same_port_same_cors() {
 #[tokio::test(flavor = "multi_thread")]
 async fn test_launch_same_port_different_cors() {
     let builder = test_rpc_builder();
-    let server = builder.build(
-        TransportRpcModuleConfig::set_ws(vec![RethRpcModule::Eth])
-            .with_http(vec![RethRpcModule::Eth]),
-        Box::new(EthApi::with_spawner),
-    );
+    let server = builder
+        .build(
+            TransportRpcModuleConfig::set_ws(vec![RethRpcModule::Eth])
+                .with_http(vec![RethRpcModule::Eth]),
+            Box::new(EthApi::with_spawner),
+        )
+        .unwrap();
     let addr = test_address();
     let res = RpcServerConfig::ws(Default::default())
         .with_ws_address(addr)
@@ -10,9 +10,12 @@ use reth_provider::test_utils::{NoopProvider, TestCanonStateSubscriptions};
 use reth_rpc::EthApi;
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerConfig, AuthServerHandle},
-    RpcModuleBuilder, RpcServerConfig, RpcServerHandle, TransportRpcModuleConfig,
+    eth::EthHandlersBuilder,
+    EthHandlers, RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle,
+    TransportRpcModuleConfig,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
+use reth_rpc_eth_types::EthConfig;
 use reth_rpc_layer::JwtSecret;
 use reth_rpc_server_types::RpcModuleSelection;
 use reth_rpc_types::engine::{ClientCode, ClientVersionV1};
@@ -28,6 +31,12 @@ pub const fn test_address() -> SocketAddr {
 /// Launches a new server for the auth module
 pub async fn launch_auth(secret: JwtSecret) -> AuthServerHandle {
     let config = AuthServerConfig::builder(secret).socket_addr(test_address()).build();
+    let module = AuthRpcModule::new(test_engine_api());
+    module.start_server(config).await.unwrap()
+}
+
+/// Returns an [`EngineApi`] for testing.
+pub fn test_engine_api() -> EngineApi<NoopProvider, EthEngineTypes> {
     let (tx, _rx) = unbounded_channel();
     let beacon_engine_handle =
         BeaconConsensusEngineHandle::<EthEngineTypes>::new(tx, Default::default());
@@ -38,7 +47,7 @@ pub async fn launch_auth(secret: JwtSecret) -> AuthServerHandle {
         commit: "defa64b2".to_string(),
     };
 
-    let engine_api = EngineApi::new(
+    EngineApi::new(
         NoopProvider::default(),
         MAINNET.clone(),
         beacon_engine_handle,
@@ -46,16 +55,15 @@ pub async fn launch_auth(secret: JwtSecret) -> AuthServerHandle {
         Box::<TokioTaskExecutor>::default(),
         client,
         EngineCapabilities::default(),
-    );
-    let module = AuthRpcModule::new(engine_api);
-    module.start_server(config).await.unwrap()
+    )
 }
 
 /// Launches a new server with http only with the given modules
 pub async fn launch_http(modules: impl Into<RpcModuleSelection>) -> RpcServerHandle {
     let builder = test_rpc_builder();
-    let server =
-        builder.build(TransportRpcModuleConfig::set_http(modules), Box::new(EthApi::with_spawner));
+    let server = builder
+        .build(TransportRpcModuleConfig::set_http(modules), Box::new(EthApi::with_spawner))
+        .unwrap();
     RpcServerConfig::http(Default::default())
         .with_http_address(test_address())
         .start(&server)
@@ -66,8 +74,9 @@ pub async fn launch_http(modules: impl Into<RpcModuleSelection>) -> RpcServerHan
 /// Launches a new server with ws only with the given modules
 pub async fn launch_ws(modules: impl Into<RpcModuleSelection>) -> RpcServerHandle {
     let builder = test_rpc_builder();
-    let server =
-        builder.build(TransportRpcModuleConfig::set_ws(modules), Box::new(EthApi::with_spawner));
+    let server = builder
+        .build(TransportRpcModuleConfig::set_ws(modules), Box::new(EthApi::with_spawner))
+        .unwrap();
     RpcServerConfig::ws(Default::default())
         .with_ws_address(test_address())
         .start(&server)
@@ -79,10 +88,12 @@ pub async fn launch_ws(modules: impl Into<RpcModuleSelection>) -> RpcServerHandl
 pub async fn launch_http_ws(modules: impl Into<RpcModuleSelection>) -> RpcServerHandle {
     let builder = test_rpc_builder();
     let modules = modules.into();
-    let server = builder.build(
-        TransportRpcModuleConfig::set_ws(modules.clone()).with_http(modules),
-        Box::new(EthApi::with_spawner),
-    );
+    let server = builder
+        .build(
+            TransportRpcModuleConfig::set_ws(modules.clone()).with_http(modules),
+            Box::new(EthApi::with_spawner),
+        )
+        .unwrap();
     RpcServerConfig::ws(Default::default())
         .with_ws_address(test_address())
         .with_ws_address(test_address())
@@ -97,10 +108,12 @@ pub async fn launch_http_ws(modules: impl Into<RpcModuleSelection>) -> RpcServer
 pub async fn launch_http_ws_same_port(modules: impl Into<RpcModuleSelection>) -> RpcServerHandle {
     let builder = test_rpc_builder();
     let modules = modules.into();
-    let server = builder.build(
-        TransportRpcModuleConfig::set_ws(modules.clone()).with_http(modules),
-        Box::new(EthApi::with_spawner),
-    );
+    let server = builder
+        .build(
+            TransportRpcModuleConfig::set_ws(modules.clone()).with_http(modules),
+            Box::new(EthApi::with_spawner),
+        )
+        .unwrap();
     let addr = test_address();
     RpcServerConfig::ws(Default::default())
         .with_ws_address(addr)
@@ -128,3 +141,49 @@ pub fn test_rpc_builder() -> RpcModuleBuilder<
         .with_events(TestCanonStateSubscriptions::default())
         .with_evm_config(EthEvmConfig::default())
 }
+
+/// The `eth` API built from the testing components.
+pub type TestEthApi = EthApi<NoopProvider, TestPool, NoopNetwork, EthEvmConfig>;
+
+/// Returns an [`EthHandlersBuilder`] with testing components.
+pub fn test_eth_handlers_builder() -> EthHandlersBuilder<
+    NoopProvider,
+    TestPool,
+    NoopNetwork,
+    TokioTaskExecutor,
+    TestCanonStateSubscriptions,
+    EthEvmConfig,
+    TestEthApi,
+> {
+    EthHandlers::builder(
+        NoopProvider::default(),
+        TestPoolBuilder::default().into(),
+        NoopNetwork::default(),
+        EthEvmConfig::default(),
+        EthConfig::default(),
+        TokioTaskExecutor::default(),
+        TestCanonStateSubscriptions::default(),
+        Box::new(EthApi::with_spawner),
+    )
+}
+
+/// Returns a [`RpcRegistryInner`] with testing components that serves the given `eth` handlers.
+pub fn test_registry(
+    eth: EthHandlers<NoopProvider, TestPool, NoopNetwork, TestCanonStateSubscriptions, TestEthApi>,
+) -> RpcRegistryInner<
+    NoopProvider,
+    TestPool,
+    NoopNetwork,
+    TokioTaskExecutor,
+    TestCanonStateSubscriptions,
+    TestEthApi,
+> {
+    RpcRegistryInner::with_eth_handlers(
+        NoopProvider::default(),
+        TestPoolBuilder::default().into(),
+        NoopNetwork::default(),
+        TokioTaskExecutor::default(),
+        TestCanonStateSubscriptions::default(),
+        eth,
+    )
+}
@@ -4,9 +4,12 @@
 
 use jsonrpsee::proc_macros::rpc;
 use reth_primitives::{Bytes, B256};
-use reth_rpc_types::mev::{
-    CancelBundleRequest, CancelPrivateTransactionRequest, EthBundleHash, EthCallBundle,
-    EthCallBundleResponse, EthSendBundle, PrivateTransactionRequest,
+use reth_rpc_types::{
+    mev::{
+        CancelBundleRequest, CancelPrivateTransactionRequest, EthBundleHash, EthCallBundle,
+        EthCallBundleResponse, EthSendBundle, PrivateTransactionRequest,
+    },
+    BlockId, EthCallResponse, TransactionRequest,
 };
 
 /// A subset of the [EthBundleApi] API interface that only supports `eth_callBundle`.
@@ -22,6 +25,23 @@ pub trait EthCallBundleApi {
     ) -> jsonrpsee::core::RpcResult<EthCallBundleResponse>;
 }
 
+/// Simulation of an ordered list of calls on top of the state of a block.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthSimulateBundleApi {
+    /// `eth_simulateBundle` executes the given calls in order on top of the state of the given
+    /// block, or the latest block if unset, and returns the result of every call.
+    ///
+    /// Every call is executed on the state left behind by the previous calls. Nothing is
+    /// committed.
+    #[method(name = "simulateBundle")]
+    async fn simulate_bundle(
+        &self,
+        calls: Vec<TransactionRequest>,
+        block_id: Option<BlockId>,
+    ) -> jsonrpsee::core::RpcResult<Vec<EthCallResponse>>;
+}
+
 /// The __full__ Eth bundle rpc interface.
 ///
 /// See also <https://docs.flashbots.net/flashbots-auction/searchers/advanced/rpc-endpoint>
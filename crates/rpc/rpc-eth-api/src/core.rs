@@ -2,7 +2,7 @@
 //! the `eth_` namespace.
 
 use crate::helpers::{
-    transaction::UpdateRawTxForwarder, EthApiSpec, EthBlocks, EthCall, EthFees, EthState,
+    transaction::UpdateRawTxForwarder, Call, EthApiSpec, EthBlocks, EthCall, EthFees, EthState,
     EthTransactions, FullEthApi,
 };
 use alloy_dyn_abi::TypedData;
@@ -588,12 +588,14 @@ where
     /// Handler for: `eth_call`
     async fn call(
         &self,
-        request: TransactionRequest,
+        mut request: TransactionRequest,
         block_number: Option<BlockId>,
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<Bytes> {
         trace!(target: "rpc::eth", ?request, ?block_number, ?state_overrides, ?block_overrides, "Serving eth_call");
+        self.check_gas_cap("eth_call", &request);
+        self.apply_method_gas_cap("eth_call", &mut request);
         Ok(EthCall::call(
             self,
             request,
@@ -611,6 +613,9 @@ where
         state_override: Option<StateOverride>,
     ) -> RpcResult<Vec<EthCallResponse>> {
         trace!(target: "rpc::eth", ?bundle, ?state_context, ?state_override, "Serving eth_callMany");
+        for tx in &bundle.transactions {
+            self.check_gas_cap("eth_callMany", tx);
+        }
         Ok(EthCall::call_many(self, bundle, state_context, state_override).await?)
     }
 
@@ -621,6 +626,7 @@ where
         block_number: Option<BlockId>,
     ) -> RpcResult<AccessListResult> {
         trace!(target: "rpc::eth", ?request, ?block_number, "Serving eth_createAccessList");
+        self.check_gas_cap("eth_createAccessList", &request);
         Ok(EthCall::create_access_list_at(self, request, block_number).await?)
     }
 
@@ -632,6 +638,7 @@ where
         state_override: Option<StateOverride>,
     ) -> RpcResult<U256> {
         trace!(target: "rpc::eth", ?request, ?block_number, "Serving eth_estimateGas");
+        self.check_gas_cap("eth_estimateGas", &request);
         Ok(EthCall::estimate_gas_at(
             self,
             request,
@@ -76,8 +76,9 @@ pub trait EthBlocks: LoadBlock {
                     .map(|block| block.body.len()))
             }
 
-            let block_hash = match LoadBlock::provider(self)
-                .block_hash_for_id(block_id)
+            let block_hash = match self
+                .cache()
+                .block_hash_for_id(&LoadBlock::provider(self), block_id)
                 .map_err(Self::Error::from_eth_err)?
             {
                 Some(block_hash) => block_hash,
@@ -156,14 +157,18 @@ pub trait EthBlocks: LoadBlock {
                     .map(|(sb, receipts)| (sb, Arc::new(receipts))))
             }
 
-            if let Some(block_hash) = LoadBlock::provider(self)
-                .block_hash_for_id(block_id)
+            let cache = LoadReceipt::cache(self);
+            if let Some(block_hash) = cache
+                .block_hash_for_id(&LoadBlock::provider(self), block_id)
                 .map_err(Self::Error::from_eth_err)?
             {
-                return LoadReceipt::cache(self)
-                    .get_block_and_receipts(block_hash)
-                    .await
-                    .map_err(Self::Error::from_eth_err)
+                // receipts are requested through the coalesced lookup, so concurrent
+                // `eth_getBlockReceipts` calls for the same block share a single provider fetch
+                let block = cache.get_sealed_block(block_hash);
+                let receipts = cache.get_block_receipts(block_hash.into());
+                let (block, receipts) =
+                    futures::try_join!(block, receipts).map_err(Self::Error::from_eth_err)?;
+                return Ok(block.zip(receipts))
             }
 
             Ok(None)
@@ -255,8 +260,9 @@ pub trait LoadBlock: LoadPendingBlock + SpawnBlocking {
                 }
             }
 
-            let block_hash = match LoadPendingBlock::provider(self)
-                .block_hash_for_id(block_id)
+            let block_hash = match self
+                .cache()
+                .block_hash_for_id(&LoadPendingBlock::provider(self), block_id)
                 .map_err(Self::Error::from_eth_err)?
             {
                 Some(block_hash) => block_hash,
@@ -2,7 +2,7 @@
 //! are executed on the `tokio` runtime.
 
 use futures::Future;
-use reth_rpc_eth_types::EthApiError;
+use reth_rpc_eth_types::{Cancellation, EthApiError};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
     TaskSpawner,
@@ -65,13 +65,21 @@ pub trait SpawnBlocking: EthApiTypes + Clone + Send + Sync + 'static {
     /// Note: This is expected for futures that are predominantly CPU bound, as it uses `rayon`
     /// under the hood, for blocking IO futures use [`spawn_blocking`](Self::spawn_blocking_io). See
     /// <https://ryhl.io/blog/async-what-is-blocking/>.
+    ///
+    /// The task is cancelled once the returned future is dropped, e.g. because the client
+    /// disconnected. Cancellation is cooperative, see [`Cancellation`].
     fn spawn_tracing<F, R>(&self, f: F) -> impl Future<Output = Result<R, Self::Error>> + Send
     where
         F: FnOnce(Self) -> Result<R, Self::Error> + Send + 'static,
         R: Send + 'static,
     {
         let this = self.clone();
-        let fut = self.tracing_task_pool().spawn(move || f(this));
-        async move { fut.await.map_err(|_| EthApiError::InternalBlockingTaskError)? }
+        let cancellation = Cancellation::default();
+        let cancel_on_drop = cancellation.cancel_on_drop();
+        let fut = self.tracing_task_pool().spawn(move || cancellation.enter(|| f(this)));
+        async move {
+            let _cancel_on_drop = cancel_on_drop;
+            fut.await.map_err(|_| EthApiError::InternalBlockingTaskError)?
+        }
     }
 }
@@ -2,6 +2,8 @@
 //! methods.
 
 use crate::{AsEthApiError, FromEthApiError, FromEvmError, IntoEthApiError};
+use std::time::Duration;
+
 use futures::Future;
 use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
 use reth_primitives::{
@@ -18,14 +20,13 @@ use reth_rpc_eth_types::{
     cache::db::{StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
     error::ensure_success,
     revm_utils::{
-        apply_block_overrides, apply_state_overrides, caller_gas_allowance,
-        cap_tx_gas_limit_with_caller_allowance, get_precompiles, CallFees,
+        apply_block_overrides, apply_state_overrides, buffered_gas_limit, caller_gas_allowance,
+        cap_tx_gas_limit_with_caller_allowance, get_precompiles, optimistic_gas_limit, CallFees,
+        CallTimeoutInspector,
     },
-    EthApiError, RevertError, RpcInvalidTransactionError, StateCacheDb,
-};
-use reth_rpc_server_types::constants::gas_oracle::{
-    CALL_STIPEND_GAS, ESTIMATE_GAS_ERROR_RATIO, MIN_TRANSACTION_GAS,
+    Cancellation, EthApiError, RevertError, RpcInvalidTransactionError, StateCacheDb,
 };
+use reth_rpc_server_types::constants::gas_oracle::{ESTIMATE_GAS_ERROR_RATIO, MIN_TRANSACTION_GAS};
 use reth_rpc_types::{
     simulate::{SimBlock, SimulatedBlock},
     state::{EvmOverrides, StateOverride},
@@ -109,7 +110,7 @@ pub trait EthCall: Call + LoadPendingBlock {
             )?;
 
             let Some(block) = block else { return Err(EthApiError::UnknownBlockNumber.into()) };
-            let gas_limit = self.call_gas_limit();
+            let gas_limit = self.method_gas_limit("eth_callMany");
 
             // we're essentially replaying the transactions in the block here, hence we need the
             // state that points to the beginning of the block, which is the state at
@@ -294,6 +295,74 @@ pub trait Call: LoadState + SpawnBlocking {
     /// Data access in default trait method implementations.
     fn call_gas_limit(&self) -> u64;
 
+    /// Returns the maximum duration of a single `eth_call` execution.
+    ///
+    /// If `None`, calls are only bounded by [`Call::call_gas_limit`], which is the default.
+    fn call_timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Returns the gas limit configured for the given RPC method, e.g. `debug_traceCall`, if it
+    /// overrides [`Call::call_gas_limit`].
+    ///
+    /// No method is overridden by default.
+    fn method_gas_limit_override(&self, _method: &str) -> Option<u64> {
+        None
+    }
+
+    /// Returns the percentage that is added to the gas limit found by the gas estimation, see
+    /// [`Call::estimate_gas_with`].
+    ///
+    /// If non-zero, the estimation searches for the minimum gas limit the transaction requires
+    /// and adds the buffer to it, instead of stopping within 1.5% above that limit. No buffer is
+    /// added by default.
+    fn estimate_gas_buffer_percent(&self) -> u64 {
+        0
+    }
+
+    /// Returns the gas limit for requests of the given RPC method.
+    ///
+    /// This is the override of the method if any, see [`Call::method_gas_limit_override`], and
+    /// [`Call::call_gas_limit`] otherwise.
+    fn method_gas_limit(&self, method: &str) -> u64 {
+        self.method_gas_limit_override(method).unwrap_or_else(|| self.call_gas_limit())
+    }
+
+    /// Invoked if a request of the given RPC method asks for more gas than
+    /// [`Call::method_gas_limit`], e.g. to record metrics.
+    ///
+    /// Does nothing by default.
+    fn on_gas_cap_exceeded(&self, _method: &'static str) {}
+
+    /// Calls [`Call::on_gas_cap_exceeded`] if the request asks for more gas than
+    /// [`Call::method_gas_limit`].
+    ///
+    /// A gas limit of `u64::MAX` is treated as "no cap".
+    fn check_gas_cap(&self, method: &'static str, request: &TransactionRequest) {
+        let gas_cap = self.method_gas_limit(method);
+        if gas_cap != u64::MAX && request.gas.is_some_and(|gas| gas > gas_cap as u128) {
+            self.on_gas_cap_exceeded(method)
+        }
+    }
+
+    /// Sets the gas of a request of the given RPC method to the method's gas limit, if the method
+    /// overrides [`Call::call_gas_limit`] and the request specifies neither gas nor a gas price.
+    ///
+    /// Such requests are otherwise executed with [`Call::call_gas_limit`], see
+    /// [`Call::prepare_call_env`]. Requests with a gas price are capped by the caller's allowance
+    /// instead.
+    fn apply_method_gas_cap(&self, method: &'static str, request: &mut TransactionRequest) {
+        if request.gas.is_some() ||
+            request.gas_price.unwrap_or_default() > 0 ||
+            request.max_fee_per_gas.unwrap_or_default() > 0
+        {
+            return
+        }
+        if let Some(gas_limit) = self.method_gas_limit_override(method) {
+            request.gas = Some(gas_limit as u128);
+        }
+    }
+
     /// Returns a handle for reading evm config.
     ///
     /// Data access in default (L1) trait method implementations.
@@ -325,7 +394,34 @@ pub trait Call: LoadState + SpawnBlocking {
         Ok((res, env))
     }
 
+    /// Executes the [`EnvWithHandlerCfg`] against the given [Database] without committing state
+    /// changes, aborting the execution once the given timeout has elapsed.
+    ///
+    /// Returns [`EthApiError::ExecutionTimedOut`] if the execution was aborted.
+    fn transact_with_timeout<DB>(
+        &self,
+        db: DB,
+        env: EnvWithHandlerCfg,
+        timeout: Duration,
+    ) -> Result<(ResultAndState, EnvWithHandlerCfg), Self::Error>
+    where
+        DB: Database,
+        EthApiError: From<DB::Error>,
+    {
+        let mut evm = self.evm_config().evm_with_env_and_inspector(
+            db,
+            env,
+            CallTimeoutInspector::new(timeout),
+        );
+        let res = evm.transact().map_err(Self::Error::from_evm_err)?;
+        evm.context.external.ensure_not_timed_out().map_err(Self::Error::from_eth_err)?;
+        let (_, env) = evm.into_db_and_env_with_handler_cfg();
+        Ok((res, env))
+    }
+
     /// Executes the call request at the given [`BlockId`].
+    ///
+    /// The execution is aborted if it exceeds [`Call::call_timeout`].
     fn transact_call_at(
         &self,
         request: TransactionRequest,
@@ -336,7 +432,11 @@ pub trait Call: LoadState + SpawnBlocking {
         Self: LoadPendingBlock,
     {
         let this = self.clone();
-        self.spawn_with_call_at(request, at, overrides, move |db, env| this.transact(db, env))
+        let timeout = self.call_timeout();
+        self.spawn_with_call_at(request, at, overrides, move |db, env| match timeout {
+            Some(timeout) => this.transact_with_timeout(db, env, timeout),
+            None => this.transact(db, env),
+        })
     }
 
     /// Executes the closure with the state that corresponds to the given [`BlockId`] on a new task
@@ -488,6 +588,7 @@ pub trait Call: LoadState + SpawnBlocking {
                 break
             }
 
+            Cancellation::ensure_current_not_cancelled().map_err(Self::Error::from_eth_err)?;
             let sender = tx.signer();
             self.evm_config().fill_tx_env(evm.tx_mut(), &tx.into_signed(), sender);
             evm.transact_commit().map_err(Self::Error::from_evm_err)?;
@@ -562,6 +663,11 @@ pub trait Call: LoadState + SpawnBlocking {
             .map(|tx_gas_limit| U256::from(tx_gas_limit).max(block_env_gas_limit))
             .unwrap_or(block_env_gas_limit);
 
+        // Bound the search by the configured gas limit of `eth_estimateGas`, if any
+        if let Some(gas_cap) = self.method_gas_limit_override("eth_estimateGas") {
+            highest_gas_limit = highest_gas_limit.min(U256::from(gas_cap));
+        }
+
         // Configure the evm env
         let mut env = self.build_call_evm_env(cfg, block, request)?;
         let mut db = CacheDB::new(StateProviderDatabase::new(state));
@@ -653,6 +759,8 @@ pub trait Call: LoadState + SpawnBlocking {
         // we know the tx succeeded with the configured gas limit, so we can use that as the
         // highest, in case we applied a gas cap due to caller allowance above
         highest_gas_limit = env.tx.gas_limit;
+        // the buffered estimate must not exceed the gas limit the transaction can have
+        let max_gas_limit = highest_gas_limit;
 
         // NOTE: this is the gas the transaction used, which is less than the
         // transaction requires to succeed.
@@ -666,7 +774,7 @@ pub trait Call: LoadState + SpawnBlocking {
         //
         // Calculate the optimistic gas limit by adding gas used and gas refund,
         // then applying a 64/63 multiplier to account for gas forwarding rules.
-        let optimistic_gas_limit = (gas_used + gas_refund + CALL_STIPEND_GAS) * 64 / 63;
+        let optimistic_gas_limit = optimistic_gas_limit(gas_used, gas_refund);
         if optimistic_gas_limit < highest_gas_limit {
             // Set the transaction's gas limit to the calculated optimistic gas limit.
             env.tx.gas_limit = optimistic_gas_limit;
@@ -686,12 +794,17 @@ pub trait Call: LoadState + SpawnBlocking {
 
         // Pick a point that's close to the estimated gas
         let mut mid_gas_limit = std::cmp::min(
-            gas_used * 3,
+            gas_used.saturating_mul(3),
             ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64,
         );
 
         trace!(target: "rpc::eth::estimate", ?env, ?highest_gas_limit, ?lowest_gas_limit, ?mid_gas_limit, "Starting binary search for gas");
 
+        // A configured buffer replaces the estimation error that is allowed otherwise, so the
+        // buffer is added to the minimum gas limit instead of stacking on top of the error
+        let buffer_percent = self.estimate_gas_buffer_percent();
+        let error_ratio = if buffer_percent == 0 { ESTIMATE_GAS_ERROR_RATIO } else { 0.0 };
+
         // Binary search narrows the range to find the minimum gas limit needed for the transaction
         // to succeed.
         while (highest_gas_limit - lowest_gas_limit) > 1 {
@@ -699,7 +812,7 @@ pub trait Call: LoadState + SpawnBlocking {
             // search is small enough (less than 1.5% of the highest gas limit)
             // <https://github.com/ethereum/go-ethereum/blob/a5a4fa7032bb248f5a7c40f4e8df2b131c4186a4/eth/gasestimator/gasestimator.go#L152
             if (highest_gas_limit - lowest_gas_limit) as f64 / (highest_gas_limit as f64) <
-                ESTIMATE_GAS_ERROR_RATIO
+                error_ratio
             {
                 break
             };
@@ -730,7 +843,7 @@ pub trait Call: LoadState + SpawnBlocking {
             mid_gas_limit = ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64;
         }
 
-        Ok(U256::from(highest_gas_limit))
+        Ok(U256::from(buffered_gas_limit(highest_gas_limit, buffer_percent, max_gas_limit)))
     }
 
     /// Updates the highest and lowest gas limits for binary search based on the execution result.
@@ -1,7 +1,8 @@
 //! Loads fee history from database. Helper trait for `eth_` fee and transaction RPC methods.
 
 use futures::Future;
-use reth_primitives::U256;
+use reth_chainspec::ChainSpec;
+use reth_primitives::{Header, SealedHeader, U256};
 use reth_provider::{BlockIdReader, BlockReaderIdExt, ChainSpecProvider, HeaderProvider};
 use reth_rpc_eth_types::{
     fee_history::calculate_reward_percentiles_for_block, EthApiError, EthStateCache,
@@ -17,6 +18,15 @@ use super::LoadBlock;
 /// Fee related functions for the [`EthApiServer`](crate::EthApiServer) trait in the
 /// `eth_` namespace.
 pub trait EthFees: LoadFee {
+    /// Returns the maximum number of blocks that can be requested in a single fee history call,
+    /// if larger requests are rejected.
+    ///
+    /// By default, the block count of larger requests is truncated to the history limits of the
+    /// gas price oracle.
+    fn max_fee_history_blocks(&self) -> Option<u64> {
+        None
+    }
+
     /// Returns a suggestion for a gas price for legacy transactions.
     ///
     /// See also: <https://github.com/ethereum/pm/issues/328#issuecomment-853234014>
@@ -54,10 +64,22 @@ pub trait EthFees: LoadFee {
         reward_percentiles: Option<Vec<f64>>,
     ) -> impl Future<Output = Result<FeeHistory, Self::Error>> + Send {
         async move {
+            if self.fee_history_cache().is_disabled() {
+                return Err(EthApiError::Unsupported("eth_feeHistory is disabled").into())
+            }
+
             if block_count == 0 {
                 return Ok(FeeHistory::default())
             }
 
+            if let Some(max_fee_history_blocks) = self.max_fee_history_blocks() {
+                if block_count > max_fee_history_blocks {
+                    return Err(
+                        EthApiError::ExceedsMaxFeeHistoryBlocks(max_fee_history_blocks).into()
+                    )
+                }
+            }
+
             // See https://github.com/ethereum/go-ethereum/blob/2754b197c935ee63101cbbca2752338246384fec/eth/gasprice/feehistory.go#L218C8-L225
             let max_fee_history = if reward_percentiles.is_none() {
                 self.gas_oracle().config().max_header_history
@@ -74,8 +96,12 @@ pub trait EthFees: LoadFee {
                 block_count = max_fee_history
             }
 
+            let include_pending =
+                newest_block.is_pending() && self.fee_history_cache().config().include_pending;
+
             if newest_block.is_pending() {
-                // cap the target block since we don't have fee history for the pending block
+                // cap the target block since we don't have fee history for the pending block, a
+                // projected entry is appended below if configured
                 newest_block = BlockNumberOrTag::Latest;
                 // account for missing pending block
                 block_count = block_count.saturating_sub(1);
@@ -105,6 +131,16 @@ pub trait EthFees: LoadFee {
                 }
             }
 
+            let pending_header = if include_pending {
+                let latest = LoadFee::provider(self)
+                    .sealed_header(end_block)
+                    .map_err(Self::Error::from_eth_err)?
+                    .ok_or(EthApiError::UnknownBlockNumber)?;
+                Some(empty_pending_header(&latest, &LoadFee::provider(self).chain_spec()))
+            } else {
+                None
+            };
+
             // Fetch the headers and ensure we got all of them
             //
             // Treat a request for 1 block as a request for `newest_block..=newest_block`,
@@ -124,7 +160,12 @@ pub trait EthFees: LoadFee {
             // Check if the requested range is within the cache bounds
             let fee_entries = self.fee_history_cache().get_history(start_block, end_block).await;
 
-            if let Some(fee_entries) = fee_entries {
+            if let Some(pending) = pending_header.as_ref().filter(|_| block_count == 0) {
+                // only the pending block was requested, so there are no entries that its base
+                // fees are derived from
+                base_fee_per_gas.push(pending.base_fee_per_gas.unwrap_or_default() as u128);
+                base_fee_per_blob_gas.push(pending.blob_fee().unwrap_or_default());
+            } else if let Some(fee_entries) = fee_entries {
                 if fee_entries.len() != block_count as usize {
                     return Err(EthApiError::InvalidBlockRange.into())
                 }
@@ -181,6 +222,7 @@ pub trait EthFees: LoadFee {
                             header.base_fee_per_gas.unwrap_or_default(),
                             &transactions,
                             &receipts,
+                            self.fee_history_cache().config().interpolation,
                         )
                         .unwrap_or_default(),
                     );
@@ -206,6 +248,27 @@ pub trait EthFees: LoadFee {
                 .push(last_header.next_block_blob_fee().unwrap_or_default());
         };
 
+            if let Some(pending) = &pending_header {
+                // the base fees of the pending block were already added as the next base fees of
+                // the newest block, add its usage and the base fees of the block after it
+                gas_used_ratio.push(0.);
+                blob_gas_used_ratio.push(0.);
+                if let Some(percentiles) = &reward_percentiles {
+                    rewards.push(vec![0; percentiles.len()]);
+                }
+                let base_fee_params = LoadFee::provider(self)
+                    .chain_spec()
+                    .base_fee_params_at_timestamp(pending.timestamp);
+                base_fee_per_gas
+                    .push(pending.next_block_base_fee(base_fee_params).unwrap_or_default() as u128);
+                base_fee_per_blob_gas.push(pending.next_block_blob_fee().unwrap_or_default());
+            }
+
+            if !self.fee_history_cache().config().include_blob_fees {
+                base_fee_per_blob_gas.clear();
+                blob_gas_used_ratio.clear();
+            }
+
             Ok(FeeHistory {
                 base_fee_per_gas,
                 gas_used_ratio,
@@ -331,7 +394,7 @@ pub trait LoadFee: LoadBlock {
         async move {
             let (header, suggested_tip) = futures::try_join!(header, suggested_tip)?;
             let base_fee = header.and_then(|h| h.base_fee_per_gas).unwrap_or_default();
-            Ok(suggested_tip + U256::from(base_fee))
+            Ok(self.gas_oracle().clamp_price(suggested_tip + U256::from(base_fee)))
         }
     }
 
@@ -351,6 +414,27 @@ pub trait LoadFee: LoadBlock {
     where
         Self: 'static,
     {
-        async move { self.gas_oracle().suggest_tip_cap().await.map_err(Self::Error::from_eth_err) }
+        async move {
+            let tip =
+                self.gas_oracle().suggest_tip_cap().await.map_err(Self::Error::from_eth_err)?;
+            Ok(self.gas_oracle().clamp_price(tip))
+        }
+    }
+}
+
+/// Returns the header of an empty block on top of the given latest block, to project the fees of
+/// the pending block.
+fn empty_pending_header(latest: &SealedHeader, chain_spec: &ChainSpec) -> Header {
+    let base_fee_params = chain_spec.base_fee_params_at_timestamp(latest.timestamp);
+    Header {
+        parent_hash: latest.hash(),
+        number: latest.number + 1,
+        timestamp: latest.timestamp,
+        gas_limit: latest.gas_limit,
+        gas_used: 0,
+        base_fee_per_gas: latest.next_block_base_fee(base_fee_params),
+        blob_gas_used: latest.blob_gas_used.map(|_| 0),
+        excess_blob_gas: latest.next_block_excess_blob_gas(),
+        ..Default::default()
     }
 }
@@ -26,8 +26,8 @@ use reth_revm::{
     database::StateProviderDatabase, state_change::post_block_withdrawals_balance_increments,
 };
 use reth_rpc_eth_types::{
-    pending_block::pre_block_blockhashes_update, EthApiError, PendingBlock, PendingBlockEnv,
-    PendingBlockEnvOrigin,
+    pending_block::{pre_block_blockhashes_update, DEFAULT_PENDING_BLOCK_CACHE_TTL},
+    EthApiError, PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin,
 };
 use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
 use revm::{db::states::bundle_state::BundleRetention, DatabaseCommit, State};
@@ -64,6 +64,14 @@ pub trait LoadPendingBlock: EthApiTypes {
     /// Data access in default (L1) trait method implementations.
     fn evm_config(&self) -> &impl ConfigureEvm;
 
+    /// Returns how long a pending block assembled by [`Self::local_pending_block`] is served
+    /// before it is assembled again.
+    ///
+    /// If `None`, [`DEFAULT_PENDING_BLOCK_CACHE_TTL`] applies, which is the default.
+    fn pending_block_cache_ttl(&self) -> Option<Duration> {
+        None
+    }
+
     /// Configures the [`CfgEnvWithHandlerCfg`] and [`BlockEnv`] for the pending block
     ///
     /// If no pending block is available, this will derive it from the `latest` block
@@ -163,7 +171,8 @@ pub trait LoadPendingBlock: EthApiTypes {
             };
 
             let now = Instant::now();
-            *lock = Some(PendingBlock::new(pending_block.clone(), now + Duration::from_secs(1)));
+            let ttl = self.pending_block_cache_ttl().unwrap_or(DEFAULT_PENDING_BLOCK_CACHE_TTL);
+            *lock = Some(PendingBlock::new(pending_block.clone(), now + ttl));
 
             Ok(Some(pending_block))
         }
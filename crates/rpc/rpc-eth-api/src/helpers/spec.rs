@@ -37,7 +37,7 @@ pub trait EthApiSpec: Send + Sync {
         }
     }
 
-    /// Returns the chain id
+    /// Returns the chain id reported by `eth_chainId` and `net_version`
     fn chain_id(&self) -> U64 {
         U64::from(self.network().chain_id())
     }
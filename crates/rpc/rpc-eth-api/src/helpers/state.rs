@@ -8,7 +8,9 @@ use reth_primitives::{Address, BlockId, Bytes, Header, B256, U256};
 use reth_provider::{
     BlockIdReader, ChainSpecProvider, StateProvider, StateProviderBox, StateProviderFactory,
 };
-use reth_rpc_eth_types::{EthApiError, EthStateCache, PendingBlockEnv, RpcInvalidTransactionError};
+use reth_rpc_eth_types::{
+    EthApiError, EthStateCache, PendingBlockEnv, ProofPermitMetrics, RpcInvalidTransactionError,
+};
 use reth_rpc_types::{serde_helpers::JsonStorageKey, EIP1186AccountProofResponse};
 use reth_rpc_types_compat::proof::from_primitive_account_proof;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
@@ -24,6 +26,13 @@ pub trait EthState: LoadState + SpawnBlocking {
     /// Returns the maximum number of blocks into the past for generating state proofs.
     fn max_proof_window(&self) -> u64;
 
+    /// Returns a handle to the metrics for the permits that bound concurrent state proofs.
+    ///
+    /// Defaults to [`ProofPermitMetrics::shared`].
+    fn proof_permit_metrics(&self) -> &ProofPermitMetrics {
+        ProofPermitMetrics::shared()
+    }
+
     /// Returns the number of transactions sent from an address at the given block identifier.
     ///
     /// If this is [`BlockNumberOrTag::Pending`](reth_primitives::BlockNumberOrTag) then this will
@@ -113,7 +122,8 @@ pub trait EthState: LoadState + SpawnBlocking {
 
         Ok(async move {
             let _permit = self
-                .acquire_owned()
+                .proof_permit_metrics()
+                .acquire(self.tracing_task_guard())
                 .await
                 .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
             self.spawn_blocking_io(move |this| {
@@ -200,8 +210,9 @@ pub trait LoadState: EthApiTypes {
                 Ok((cfg, block_env, origin.state_block_id()))
             } else {
                 // Use cached values if there is no pending block
-                let block_hash = LoadPendingBlock::provider(self)
-                    .block_hash_for_id(at)
+                let block_hash = self
+                    .cache()
+                    .block_hash_for_id(&LoadPendingBlock::provider(self), at)
                     .map_err(Self::Error::from_eth_err)?
                     .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
                 let (cfg, env) = self
@@ -2,20 +2,23 @@
 
 use futures::Future;
 use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
-use reth_primitives::B256;
+use reth_primitives::{SealedBlockWithSenders, B256};
+use reth_provider::{BlockIdReader, BlockNumReader};
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_eth_types::{
     cache::db::{StateCacheDb, StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
-    EthApiError,
+    CancellableInspector, Cancellation, EthApiError, EthStateCache,
 };
 use reth_rpc_types::{BlockId, TransactionInfo};
 use revm::{db::CacheDB, Database, DatabaseCommit, GetInspector, Inspector};
 use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
-use revm_primitives::{EnvWithHandlerCfg, EvmState, ExecutionResult, ResultAndState};
+use revm_primitives::{
+    BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, EvmState, ExecutionResult, ResultAndState,
+};
 
-use crate::FromEvmError;
+use crate::{FromEthApiError, FromEvmError};
 
-use super::{Call, LoadBlock, LoadPendingBlock, LoadState, LoadTransaction};
+use super::{Call, LoadBlock, LoadPendingBlock, LoadState, LoadTransaction, SpawnBlocking};
 
 /// Executes CPU heavy tasks.
 pub trait Trace: LoadState {
@@ -24,6 +27,103 @@ pub trait Trace: LoadState {
     /// Data access in default (L1) trait method implementations.
     fn evm_config(&self) -> &impl ConfigureEvm;
 
+    /// Returns the maximum number of blocks into the past that can be traced.
+    ///
+    /// If `None` then blocks of any age can be traced, which is the default.
+    fn max_trace_window(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the cache that tracing calls load blocks and EVM environments from.
+    ///
+    /// Defaults to the cache of regular requests. Implementations can return a separate cache, so
+    /// that tracing historical blocks doesn't evict the entries of regular requests.
+    fn trace_cache(&self) -> &EthStateCache {
+        LoadState::cache(self)
+    }
+
+    /// Returns the block with senders for the given block id, loaded from the
+    /// [`trace_cache`](Self::trace_cache).
+    ///
+    /// Like [`LoadBlock::block_with_senders`], except that pending blocks are never cached.
+    fn trace_block_with_senders(
+        &self,
+        block_id: BlockId,
+    ) -> impl Future<Output = Result<Option<SealedBlockWithSenders>, Self::Error>> + Send
+    where
+        Self: LoadBlock,
+    {
+        async move {
+            if block_id.is_pending() {
+                return self.block_with_senders(block_id).await
+            }
+
+            let Some(block_hash) = self
+                .trace_cache()
+                .block_hash_for_id(&LoadPendingBlock::provider(self), block_id)
+                .map_err(Self::Error::from_eth_err)?
+            else {
+                return Ok(None)
+            };
+
+            self.trace_cache()
+                .get_sealed_block_with_senders(block_hash)
+                .await
+                .map_err(Self::Error::from_eth_err)
+        }
+    }
+
+    /// Returns the EVM environment of the given block, loaded from the
+    /// [`trace_cache`](Self::trace_cache).
+    ///
+    /// Like [`LoadState::evm_env_at`], except that pending environments are never cached.
+    fn trace_evm_env_at(
+        &self,
+        at: BlockId,
+    ) -> impl Future<Output = Result<(CfgEnvWithHandlerCfg, BlockEnv, BlockId), Self::Error>> + Send
+    where
+        Self: LoadPendingBlock + SpawnBlocking,
+    {
+        async move {
+            if at.is_pending() {
+                return self.evm_env_at(at).await
+            }
+
+            let block_hash = self
+                .trace_cache()
+                .block_hash_for_id(&LoadPendingBlock::provider(self), at)
+                .map_err(Self::Error::from_eth_err)?
+                .ok_or(EthApiError::UnknownBlockNumber)?;
+            let (cfg, block_env) = self
+                .trace_cache()
+                .get_evm_env(block_hash)
+                .await
+                .map_err(Self::Error::from_eth_err)?;
+            Ok((cfg, block_env, block_hash.into()))
+        }
+    }
+
+    /// Returns an error if the given block is further in the past than the
+    /// [`max_trace_window`](Self::max_trace_window).
+    ///
+    /// Blocks that don't exist pass the check, loading their state fails later on.
+    fn ensure_within_trace_window(&self, block_id: BlockId) -> Result<(), Self::Error> {
+        let Some(max_window) = self.max_trace_window() else { return Ok(()) };
+
+        let provider = LoadState::provider(self);
+        let Some(block_number) =
+            provider.block_number_for_id(block_id).map_err(Self::Error::from_eth_err)?
+        else {
+            return Ok(())
+        };
+        let best_number = provider.best_block_number().map_err(Self::Error::from_eth_err)?;
+        if best_number.saturating_sub(block_number) > max_window {
+            return Err(EthApiError::ExceedsMaxTraceWindow.into())
+        }
+
+        Ok(())
+    }
+
     /// Executes the [`EnvWithHandlerCfg`] against the given [Database] without committing state
     /// changes.
     fn inspect<DB, I>(
@@ -57,8 +157,15 @@ pub trait Trace: LoadState {
 
         I: GetInspector<DB>,
     {
-        let mut evm = self.evm_config().evm_with_env_and_inspector(db, env, inspector);
+        // the execution is aborted if the work on this thread is cancelled
+        let cancellation = Cancellation::current().unwrap_or_default();
+        let mut evm = self.evm_config().evm_with_env_and_inspector(
+            db,
+            env,
+            CancellableInspector::new(inspector, cancellation.clone()),
+        );
         let res = evm.transact().map_err(Self::Error::from_evm_err)?;
+        cancellation.ensure_not_cancelled().map_err(Self::Error::from_eth_err)?;
         let (db, env) = evm.into_db_and_env_with_handler_cfg();
         Ok((res, env, db))
     }
@@ -81,6 +188,7 @@ pub trait Trace: LoadState {
         Self: Call,
         F: FnOnce(TracingInspector, ResultAndState) -> Result<R, Self::Error>,
     {
+        self.ensure_within_trace_window(at)?;
         self.with_state_at_block(at, |state| {
             let mut db = CacheDB::new(StateProviderDatabase::new(state));
             let mut inspector = TracingInspector::new(config);
@@ -110,13 +218,19 @@ pub trait Trace: LoadState {
             + 'static,
         R: Send + 'static,
     {
-        let this = self.clone();
-        self.spawn_with_state_at_block(at, move |state| {
-            let mut db = CacheDB::new(StateProviderDatabase::new(state));
-            let mut inspector = TracingInspector::new(config);
-            let (res, _) = this.inspect(StateCacheDbRefMutWrapper(&mut db), env, &mut inspector)?;
-            f(inspector, res, db)
-        })
+        async move {
+            self.ensure_within_trace_window(at)?;
+
+            let this = self.clone();
+            self.spawn_with_state_at_block(at, move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                let mut inspector = TracingInspector::new(config);
+                let (res, _) =
+                    this.inspect(StateCacheDbRefMutWrapper(&mut db), env, &mut inspector)?;
+                f(inspector, res, db)
+            })
+            .await
+        }
     }
 
     /// Retrieves the transaction if it exists and returns its trace.
@@ -182,9 +296,10 @@ pub trait Trace: LoadState {
                 None => return Ok(None),
                 Some(res) => res,
             };
+            self.ensure_within_trace_window(block.number.into())?;
             let (tx, tx_info) = transaction.split();
 
-            let (cfg, block_env, _) = self.evm_env_at(block.hash().into()).await?;
+            let (cfg, block_env, _) = self.trace_evm_env_at(block.hash().into()).await?;
 
             // we need to get the state of the parent block because we're essentially replaying the
             // block the transaction is included in
@@ -285,8 +400,12 @@ pub trait Trace: LoadState {
         R: Send + 'static,
     {
         async move {
-            let ((cfg, block_env, _), block) =
-                futures::try_join!(self.evm_env_at(block_id), self.block_with_senders(block_id))?;
+            self.ensure_within_trace_window(block_id)?;
+
+            let ((cfg, block_env, _), block) = futures::try_join!(
+                self.trace_evm_env_at(block_id),
+                self.trace_block_with_senders(block_id)
+            )?;
 
             let Some(block) = block else { return Ok(None) };
 
@@ -5,9 +5,10 @@ use std::{fmt, ops::Deref, sync::Arc};
 
 use alloy_dyn_abi::TypedData;
 use futures::Future;
+use reth_network_api::NetworkInfo;
 use reth_primitives::{
     Address, BlockId, Bytes, Receipt, SealedBlockWithSenders, TransactionMeta, TransactionSigned,
-    TxHash, TxKind, B256, U256,
+    TxHash, TxKind, B256, U256, U64,
 };
 use reth_provider::{BlockReaderIdExt, ReceiptProvider, TransactionsProvider};
 use reth_rpc_eth_types::{
@@ -257,9 +258,12 @@ pub trait EthTransactions: LoadTransaction {
             // blocks that it builds.
             if let Some(client) = self.raw_tx_forwarder().as_ref() {
                 tracing::debug!( target: "rpc::eth",  "forwarding raw transaction to");
-                let _ = client.forward_raw_transaction(&tx).await.inspect_err(|err| {
+                if let Err(err) = client.forward_raw_transaction(&tx).await {
                     tracing::debug!(target: "rpc::eth", %err, hash=% *pool_transaction.hash(), "failed to forward raw transaction");
-                });
+                    if let Some(err) = client.map_forward_error(err) {
+                        return Err(Self::Error::from_eth_err(err))
+                    }
+                }
             }
 
             // submit the transaction to the pool with a `Local` origin
@@ -299,7 +303,8 @@ pub trait EthTransactions: LoadTransaction {
                 request.nonce = Some(nonce.to::<u64>());
             }
 
-            let chain_id = self.chain_id();
+            // sign with the network's chain id, the reported chain id may be overridden
+            let chain_id = U64::from(self.network().chain_id());
 
             let estimated_gas =
                 self.estimate_gas_at(request.clone(), BlockId::pending(), None).await?;
@@ -668,6 +673,63 @@ pub trait LoadTransaction: SpawnBlocking {
 pub trait RawTransactionForwarder: fmt::Debug + Send + Sync + 'static {
     /// Forwards raw transaction bytes for `eth_sendRawTransaction`
     async fn forward_raw_transaction(&self, raw: &[u8]) -> EthResult<()>;
+
+    /// Maps an error of [`Self::forward_raw_transaction`] to the error that is returned to the
+    /// caller of `eth_sendRawTransaction`, e.g. to translate the error of an upstream node into a
+    /// standard error like [`RpcPoolError::ReplaceUnderpriced`].
+    ///
+    /// If `None` is returned, the error is only logged and the transaction is still submitted to
+    /// the local pool. By default `None` is returned, so forwarding failures, e.g. an unreachable
+    /// sequencer, don't fail the request.
+    ///
+    /// [`RpcPoolError::ReplaceUnderpriced`]: reth_rpc_eth_types::error::RpcPoolError::ReplaceUnderpriced
+    fn map_forward_error(&self, _err: EthApiError) -> Option<EthApiError> {
+        None
+    }
+}
+
+/// Maps errors of a [`RawTransactionForwarder`], see
+/// [`RawTransactionForwarder::map_forward_error`].
+pub type DynForwardErrorMapper = Arc<dyn Fn(EthApiError) -> Option<EthApiError> + Send + Sync>;
+
+/// A [`RawTransactionForwarder`] that forwards transactions with the wrapped forwarder and maps
+/// its errors with the given function.
+#[derive(Clone)]
+pub struct MapForwardError<F> {
+    forwarder: F,
+    map_err: DynForwardErrorMapper,
+}
+
+impl<F> MapForwardError<F> {
+    /// Wraps the given forwarder, mapping its errors with `map_err`.
+    ///
+    /// Use `Some` to return the errors of the forwarder to the caller unchanged, instead of only
+    /// logging them and still submitting the transaction to the local pool.
+    pub fn new(
+        forwarder: F,
+        map_err: impl Fn(EthApiError) -> Option<EthApiError> + Send + Sync + 'static,
+    ) -> Self {
+        Self { forwarder, map_err: Arc::new(map_err) }
+    }
+}
+
+impl<F: fmt::Debug> fmt::Debug for MapForwardError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapForwardError")
+            .field("forwarder", &self.forwarder)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait::async_trait]
+impl<F: RawTransactionForwarder> RawTransactionForwarder for MapForwardError<F> {
+    async fn forward_raw_transaction(&self, raw: &[u8]) -> EthResult<()> {
+        self.forwarder.forward_raw_transaction(raw).await
+    }
+
+    fn map_forward_error(&self, err: EthApiError) -> Option<EthApiError> {
+        (self.map_err)(err)
+    }
 }
 
 /// Configure server's forwarder for `eth_sendRawTransaction`, at runtime.
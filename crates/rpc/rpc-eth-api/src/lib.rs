@@ -18,7 +18,7 @@ pub mod filter;
 pub mod helpers;
 pub mod pubsub;
 
-pub use bundle::{EthBundleApiServer, EthCallBundleApiServer};
+pub use bundle::{EthBundleApiServer, EthCallBundleApiServer, EthSimulateBundleApiServer};
 pub use core::{EthApiServer, FullEthApiServer};
 pub use filter::EthFilterApiServer;
 pub use helpers::{
@@ -27,10 +27,10 @@ pub use helpers::{
 };
 pub use pubsub::EthPubSubApiServer;
 
-pub use helpers::transaction::RawTransactionForwarder;
+pub use helpers::transaction::{DynForwardErrorMapper, MapForwardError, RawTransactionForwarder};
 
 #[cfg(feature = "client")]
-pub use bundle::{EthBundleApiClient, EthCallBundleApiClient};
+pub use bundle::{EthBundleApiClient, EthCallBundleApiClient, EthSimulateBundleApiClient};
 #[cfg(feature = "client")]
 pub use core::EthApiClient;
 #[cfg(feature = "client")]
@@ -1,54 +1,291 @@
 //! Configuration for `eth` namespace APIs.
 
-use std::time::Duration;
+use std::{collections::BTreeMap, time::Duration};
 
 use crate::{
-    EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
+    EthStateCacheConfig, FeeHistoryCacheConfig, FeeHistoryStoreHandle, GasPriceOracleConfig,
+    NumberEncoding, ResponseCacheConfig, RPC_DEFAULT_GAS_CAP,
 };
+use rayon::ThreadPoolBuildError;
+use reth_chainspec::{Chain, NamedChain};
+use reth_primitives::{keccak256, U256};
 use reth_rpc_server_types::constants::{
     default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
-    DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_PROOF_PERMITS,
+    DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_PROOF_PERMITS, MAX_ETH_PROOF_WINDOW,
 };
+use reth_tasks::pool::BlockingTaskPool;
 use serde::{Deserialize, Serialize};
 
 /// Default value for stale filter ttl
 pub const DEFAULT_STALE_FILTER_TTL: Duration = Duration::from_secs(5 * 60);
 
+/// Upper bound for the default interval at which stale filters are reaped
+pub const DEFAULT_STALE_FILTER_REAP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Returns the default interval at which stale filters are reaped for the given stale filter ttl,
+/// which is `min(ttl, 30s)`.
+pub const fn default_stale_filter_reap_interval(stale_filter_ttl: Duration) -> Duration {
+    if stale_filter_ttl.as_nanos() < DEFAULT_STALE_FILTER_REAP_INTERVAL.as_nanos() {
+        stale_filter_ttl
+    } else {
+        DEFAULT_STALE_FILTER_REAP_INTERVAL
+    }
+}
+
 /// Additional config values for the eth namespace.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct EthConfig {
     /// Settings for the caching layer
     pub cache: EthStateCacheConfig,
+    /// Settings for a separate cache that tracing calls, e.g. `debug_traceBlock`, load blocks and
+    /// EVM environments from, so that tracing historical blocks doesn't evict the entries of
+    /// regular requests.
+    ///
+    /// If `None` then tracing calls use the regular cache.
+    pub trace_cache: Option<EthStateCacheConfig>,
     /// Settings for the gas price oracle
     pub gas_oracle: GasPriceOracleConfig,
+    /// Lower bound for `eth_gasPrice` and `eth_maxPriorityFeePerGas` suggestions.
+    ///
+    /// If `None` then suggestions are not clamped from below.
+    pub gas_price_floor: Option<U256>,
+    /// Upper bound for `eth_gasPrice` and `eth_maxPriorityFeePerGas` suggestions.
+    ///
+    /// If `None` then suggestions are not clamped from above.
+    pub gas_price_ceiling: Option<U256>,
     /// The maximum number of blocks into the past for generating state proofs.
     pub eth_proof_window: u64,
     /// The maximum number of tracing calls that can be executed in concurrently.
     pub max_tracing_requests: usize,
+    /// The maximum number of blocks into the past that can be traced, e.g. with `debug_trace*`
+    /// and `trace_*` calls.
+    ///
+    /// Traces of older blocks are rejected, which bounds the cost of tracing on public nodes. If
+    /// `None` then blocks of any age can be traced.
+    pub trace_block_window: Option<u64>,
+    /// Maximum memory in bytes used by the struct logs of a single `debug_trace*` request.
+    ///
+    /// Traces that exceed the budget are aborted with an error. Together with
+    /// `max_tracing_requests` this bounds the total memory used by struct log traces. If `None`
+    /// then traces are only bounded by the gas of the traced transactions, which is capped by
+    /// `rpc_gas_cap` for `debug_traceCall` and `debug_traceCallMany`.
+    pub max_trace_memory_bytes: Option<usize>,
     /// Maximum number of blocks that could be scanned per filter request in `eth_getLogs` calls.
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
+    /// Maximum number of items returned by a single `eth_getFilterChanges` poll, i.e. block
+    /// hashes, transactions or logs.
+    ///
+    /// Changes beyond the limit are retained by the filter and returned by the next poll, which
+    /// bounds the memory and response size of clients that poll infrequently. The logs of a single
+    /// block are never split, so a poll only exceeds the limit if one block has more matching
+    /// logs. If `None` then every poll returns all changes since the last poll.
+    pub max_filter_changes_per_poll: Option<usize>,
+    /// Whether range log queries skip blocks whose logs bloom can't match the queried addresses
+    /// and topics, instead of fetching the receipts of every block in the range.
+    ///
+    /// A bloom only yields false positives, so this never omits matching logs.
+    pub use_bloom_prefilter: bool,
+    /// Maximum number of active `eth_subscribe` subscriptions per connection.
+    ///
+    /// If `None` then no limit is enforced.
+    pub max_subscriptions_per_connection: Option<usize>,
+    /// Maximum number of active `eth_subscribe` subscriptions across all connections.
+    ///
+    /// If `None` then no limit is enforced.
+    pub max_total_subscriptions: Option<usize>,
+    /// Whether `logs` subscriptions with a `fromBlock` in the past first receive the matching
+    /// historical logs, before they switch to live logs.
+    ///
+    /// The backfill is bounded by `max_blocks_per_filter` and `max_logs_per_response`,
+    /// subscriptions that exceed either are rejected. Disabled by default, since every backfill
+    /// reads the receipts of a range of blocks.
+    pub subscription_log_backfill: bool,
+    /// Maximum number of blocks that can be requested in a single `eth_feeHistory` call.
+    ///
+    /// Requests for more blocks are rejected. If `None` then the block count of larger requests
+    /// is truncated to the history limits of the gas price oracle instead.
+    pub max_fee_history_blocks: Option<u64>,
     /// Gas limit for `eth_call` and call tracing RPC methods.
     ///
     /// Defaults to [`RPC_DEFAULT_GAS_CAP`]
     pub rpc_gas_cap: u64,
+    /// Gas limits of specific RPC methods by method name, e.g. `debug_traceCall`, which take
+    /// precedence over `rpc_gas_cap`.
+    ///
+    /// Methods without an entry use `rpc_gas_cap`. An entry for `eth_estimateGas` bounds the gas
+    /// limit that estimation searches up to, which is otherwise only bounded by the block gas
+    /// limit.
+    pub method_gas_caps: BTreeMap<String, u64>,
+    /// Maximum duration of a single `eth_call` execution.
+    ///
+    /// If `None` then calls are only bounded by the gas cap.
+    pub call_timeout: Option<Duration>,
+    /// Percentage that is added to the gas limit found by `eth_estimateGas`, as a margin for
+    /// state changes between estimation and inclusion.
+    ///
+    /// A buffer replaces the estimation error of the search: without a buffer, the search stops
+    /// once its estimate is within 1.5% above the minimum gas limit of the transaction. With a
+    /// buffer, the search finds the minimum gas limit and the buffer is added to it. The buffered
+    /// estimate never exceeds the gas limit the search was bounded by, e.g. the block gas limit.
+    /// Simple transfers are always estimated at exactly 21000 gas. Defaults to `0`.
+    pub estimate_gas_buffer_percent: u64,
+    /// How long a pending block that is assembled from the local pool is served to subsequent
+    /// `pending` requests, e.g. `eth_getBlockByNumber("pending")`, before it is assembled again.
+    ///
+    /// Longer durations reduce the load of frequent `pending` requests, at the cost of omitting
+    /// transactions that arrived in the meantime. If `None` then the pending block is
+    /// reassembled after [`DEFAULT_PENDING_BLOCK_CACHE_TTL`]. A new canonical block always
+    /// invalidates the cached pending block.
+    ///
+    /// [`DEFAULT_PENDING_BLOCK_CACHE_TTL`]: crate::pending_block::DEFAULT_PENDING_BLOCK_CACHE_TTL
+    pub pending_block_cache_ttl: Option<Duration>,
+    /// How long the blocks that the `latest`, `safe` and `finalized` tags resolve to are cached,
+    /// to save provider lookups under bursts of requests for the same tag.
+    ///
+    /// Cached tags are invalidated when the canonical chain changes. Safe and finalized blocks
+    /// can change without a new canonical block, so these may lag for up to the configured
+    /// duration. The `pending` tag is never cached. If `None` then tags are not cached.
+    pub tag_cache_ttl: Option<Duration>,
     ///
     /// Sets TTL for stale filters
     pub stale_filter_ttl: Duration,
+    /// Interval at which stale filters are reaped.
+    ///
+    /// Defaults to `min(stale_filter_ttl, 30s)` if unset.
+    pub stale_filter_reap_interval: Option<Duration>,
     /// Settings for the fee history cache
     pub fee_history_cache: FeeHistoryCacheConfig,
+    /// Store that fee history entries are persisted to, e.g. to share a warm cache between nodes.
+    ///
+    /// Stored entries are loaded once the fee history cache task starts, and the changes of every
+    /// update are flushed to the store.
+    ///
+    /// Default is a no-op store, i.e. entries are only kept in memory.
+    #[serde(skip)]
+    pub fee_history_store: FeeHistoryStoreHandle,
+    /// Whether `eth_feeHistory` is disabled.
+    ///
+    /// If `true`, the fee history cache is not populated and `eth_feeHistory` requests are
+    /// rejected.
+    pub disable_fee_history: bool,
     /// The maximum number of getproof calls that can be executed concurrently.
     pub proof_permits: usize,
+    /// Number of threads of the blocking task pool, which runs CPU-bound RPC work like tracing,
+    /// simulation and proof generation.
+    ///
+    /// If `None` then rayon's default is used, i.e. one thread per logical CPU, which may
+    /// oversubscribe containers with a CPU quota.
+    pub blocking_task_threads: Option<usize>,
+    /// Number of canonical state notifications that are buffered for each task that consumes the
+    /// canonical state stream, e.g. the fee history task.
+    ///
+    /// This is in addition to the capacity of the broadcast channel the notifications are
+    /// received from. If zero, notifications are consumed directly from the broadcast channel.
+    /// The state cache task always consumes the broadcast channel directly, see
+    /// [`EthStateCache::notification_overflows`](crate::EthStateCache::notification_overflows).
+    pub canonical_stream_buffer: usize,
+    /// Whether the task that keeps the state cache up to date with the canonical chain is
+    /// critical, i.e. whether a panic in the task shuts down the node.
+    ///
+    /// If `false`, the task is restarted when it panics. Note that notifications received while
+    /// the task is down are lost, so the cache may serve stale data, e.g. blocks that were
+    /// reorged out, until they are evicted.
+    pub cache_task_critical: bool,
+    /// Chain id reported by `eth_chainId` and `net_version`, e.g. for a staging node that mimics
+    /// the id of another chain while running a fork.
+    ///
+    /// Transactions are still signed and validated with the chain id of the network. If `None`
+    /// then the chain id of the network is reported.
+    pub chain_id_override: Option<u64>,
+    /// How quantities are encoded in the responses of `eth` namespace methods.
+    ///
+    /// Defaults to [`NumberEncoding::Hex`] as specified by the JSON-RPC API. This applies to all
+    /// transports, overrides in the configs of single transports are ignored.
+    pub number_encoding: NumberEncoding,
+    /// Settings for the cache of responses of deterministic `eth` namespace methods, e.g.
+    /// `eth_getTransactionReceipt` of old transactions.
+    ///
+    /// Only the allowlisted methods of the config are cached, and requests for blocks by a tag
+    /// like `latest` or `pending` are never cached. The cache is cleared on every reorg. This
+    /// applies to all transports, overrides in the configs of single transports are ignored. If
+    /// `None` then responses are not cached.
+    pub response_cache: Option<ResponseCacheConfig>,
+    /// Whether the revert data is stripped from the errors of reverted `eth_call` and
+    /// `eth_estimateGas` requests, so that these only report `execution reverted`.
+    ///
+    /// Revert data can leak details of internal contracts on public nodes. This only applies to
+    /// the `eth` namespace of the regular transports, the `eth_` methods of the authenticated
+    /// engine API keep the full revert data.
+    pub strip_revert_data: bool,
+    /// Maximum size of the serialized result of any `eth` namespace method in bytes.
+    ///
+    /// Results that exceed it are rejected with a `response too large` error, as a safety net
+    /// against unexpectedly large results on top of the limits of specific methods, e.g.
+    /// `max_logs_per_response`. This applies to all transports, overrides in the configs of single
+    /// transports are ignored. If `None` then no limit is enforced.
+    pub max_response_bytes: Option<usize>,
 }
 
 impl EthConfig {
+    /// Returns the default config for the given chain.
+    ///
+    /// Known chains start from [`EthConfig::default`] with these overrides:
+    ///  - dev chain: historical proofs for the full [`MAX_ETH_PROOF_WINDOW`] and an unlimited
+    ///    `eth_call` gas cap, since the node is local and only serves its own developer.
+    ///  - Sepolia and Holesky: historical proofs for the full [`MAX_ETH_PROOF_WINDOW`], since
+    ///    testnet state is small enough to serve them cheaply.
+    ///
+    /// All other chains, including mainnet, use [`EthConfig::default`].
+    pub fn for_chain(chain: Chain) -> Self {
+        let config = Self::default();
+        match chain.named() {
+            Some(NamedChain::Dev) => {
+                config.eth_proof_window(MAX_ETH_PROOF_WINDOW).rpc_gas_cap_unlimited()
+            }
+            Some(NamedChain::Sepolia | NamedChain::Holesky) => {
+                config.eth_proof_window(MAX_ETH_PROOF_WINDOW)
+            }
+            _ => config,
+        }
+    }
+
+    /// Returns a fingerprint of the config, e.g. to assert that all nodes of a fleet run with
+    /// identical settings.
+    ///
+    /// This is the keccak256 hash of the JSON encoding of the config, which encodes all fields,
+    /// including nested configs and durations, in declaration order. Values that are not
+    /// serialized, i.e. [`EthStateCacheConfig::remote_fallback`] and
+    /// [`EthConfig::fee_history_store`], are not part of the fingerprint.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        let encoded = serde_json::to_vec(self).expect("config is serializable");
+        keccak256(encoded).0
+    }
+
     /// Returns the filter config for the `eth_filter` handler.
     pub fn filter_config(&self) -> EthFilterConfig {
         EthFilterConfig::default()
             .max_blocks_per_filter(self.max_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
+            .max_changes_per_poll(self.max_filter_changes_per_poll)
+            .use_bloom_prefilter(self.use_bloom_prefilter)
             .stale_filter_ttl(self.stale_filter_ttl)
+            .reap_interval(
+                self.stale_filter_reap_interval
+                    .unwrap_or_else(|| default_stale_filter_reap_interval(self.stale_filter_ttl)),
+            )
+    }
+
+    /// Builds a new [`BlockingTaskPool`] with the configured number of threads, see
+    /// [`EthConfig::blocking_task_threads`].
+    pub fn build_blocking_task_pool(&self) -> Result<BlockingTaskPool, ThreadPoolBuildError> {
+        match self.blocking_task_threads {
+            Some(threads) => {
+                BlockingTaskPool::builder().num_threads(threads).build().map(BlockingTaskPool::new)
+            }
+            None => BlockingTaskPool::build(),
+        }
     }
 }
 
@@ -56,38 +293,101 @@ impl Default for EthConfig {
     fn default() -> Self {
         Self {
             cache: EthStateCacheConfig::default(),
+            trace_cache: None,
             gas_oracle: GasPriceOracleConfig::default(),
+            gas_price_floor: None,
+            gas_price_ceiling: None,
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
             max_tracing_requests: default_max_tracing_requests(),
+            trace_block_window: None,
+            max_trace_memory_bytes: None,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
+            max_filter_changes_per_poll: None,
+            use_bloom_prefilter: true,
+            max_subscriptions_per_connection: None,
+            max_total_subscriptions: None,
+            subscription_log_backfill: false,
+            max_fee_history_blocks: None,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            method_gas_caps: BTreeMap::new(),
+            call_timeout: None,
+            estimate_gas_buffer_percent: 0,
+            pending_block_cache_ttl: None,
+            tag_cache_ttl: None,
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
+            stale_filter_reap_interval: None,
             fee_history_cache: FeeHistoryCacheConfig::default(),
+            fee_history_store: FeeHistoryStoreHandle::default(),
+            disable_fee_history: false,
             proof_permits: DEFAULT_PROOF_PERMITS,
+            blocking_task_threads: None,
+            canonical_stream_buffer: 0,
+            cache_task_critical: true,
+            chain_id_override: None,
+            number_encoding: NumberEncoding::Hex,
+            response_cache: None,
+            strip_revert_data: false,
+            max_response_bytes: None,
         }
     }
 }
 
 impl EthConfig {
     /// Configures the caching layer settings
-    pub const fn state_cache(mut self, cache: EthStateCacheConfig) -> Self {
+    pub fn state_cache(mut self, cache: EthStateCacheConfig) -> Self {
         self.cache = cache;
         self
     }
 
+    /// Configures a separate cache for tracing calls
+    pub fn trace_cache(mut self, cache: EthStateCacheConfig) -> Self {
+        self.trace_cache = Some(cache);
+        self
+    }
+
     /// Configures the gas price oracle settings
     pub const fn gpo_config(mut self, gas_oracle_config: GasPriceOracleConfig) -> Self {
         self.gas_oracle = gas_oracle_config;
         self
     }
 
+    /// Configures the minimum gas price, under which samples are ignored by the gas price oracle
+    pub const fn gpo_ignore_price(mut self, price: U256) -> Self {
+        self.gas_oracle.ignore_price = Some(price);
+        self
+    }
+
+    /// Configures the lower bound for gas price and priority fee suggestions
+    pub const fn gas_price_floor(mut self, floor: U256) -> Self {
+        self.gas_price_floor = Some(floor);
+        self
+    }
+
+    /// Configures the upper bound for gas price and priority fee suggestions
+    pub const fn gas_price_ceiling(mut self, ceiling: U256) -> Self {
+        self.gas_price_ceiling = Some(ceiling);
+        self
+    }
+
     /// Configures the maximum number of tracing requests
     pub const fn max_tracing_requests(mut self, max_requests: usize) -> Self {
         self.max_tracing_requests = max_requests;
         self
     }
 
+    /// Configures the maximum number of blocks into the past that can be traced
+    pub const fn trace_block_window(mut self, window: u64) -> Self {
+        self.trace_block_window = Some(window);
+        self
+    }
+
+    /// Configures the maximum memory in bytes used by the struct logs of a single trace request
+    pub const fn max_trace_memory_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_trace_memory_bytes = Some(max_bytes);
+        self
+    }
+
     /// Configures the maximum block length to scan per `eth_getLogs` request
     pub const fn max_blocks_per_filter(mut self, max_blocks: u64) -> Self {
         self.max_blocks_per_filter = max_blocks;
@@ -100,23 +400,183 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum number of items returned by a single `eth_getFilterChanges` poll
+    pub const fn max_filter_changes_per_poll(mut self, max_changes: usize) -> Self {
+        self.max_filter_changes_per_poll = Some(max_changes);
+        self
+    }
+
+    /// Configures whether range log queries skip blocks by their logs bloom
+    pub const fn use_bloom_prefilter(mut self, enabled: bool) -> Self {
+        self.use_bloom_prefilter = enabled;
+        self
+    }
+
+    /// Configures the maximum number of active subscriptions per connection
+    pub const fn max_subscriptions_per_connection(mut self, max_subscriptions: usize) -> Self {
+        self.max_subscriptions_per_connection = Some(max_subscriptions);
+        self
+    }
+
+    /// Configures the maximum number of active subscriptions across all connections
+    pub const fn max_total_subscriptions(mut self, max_subscriptions: usize) -> Self {
+        self.max_total_subscriptions = Some(max_subscriptions);
+        self
+    }
+
+    /// Configures whether `logs` subscriptions backfill historical logs
+    pub const fn subscription_log_backfill(mut self, enabled: bool) -> Self {
+        self.subscription_log_backfill = enabled;
+        self
+    }
+
+    /// Configures the maximum number of blocks per `eth_feeHistory` request, larger requests are
+    /// rejected
+    pub const fn max_fee_history_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_fee_history_blocks = Some(max_blocks);
+        self
+    }
+
     /// Configures the maximum gas limit for `eth_call` and call tracing RPC methods
     pub const fn rpc_gas_cap(mut self, rpc_gas_cap: u64) -> Self {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
 
+    /// Disables the gas limit cap for `eth_call` and call tracing RPC methods.
+    ///
+    /// This stores `u64::MAX`, which is treated as "no cap".
+    pub const fn rpc_gas_cap_unlimited(mut self) -> Self {
+        self.rpc_gas_cap = u64::MAX;
+        self
+    }
+
+    /// Configures the gas limit of the given RPC method, overriding the `rpc_gas_cap`
+    pub fn with_method_gas_cap(mut self, method: impl Into<String>, gas_cap: u64) -> Self {
+        self.method_gas_caps.insert(method.into(), gas_cap);
+        self
+    }
+
+    /// Returns the gas limit of the given RPC method.
+    ///
+    /// This is the method's entry in [`Self::method_gas_caps`] if any, and
+    /// [`Self::rpc_gas_cap`] otherwise.
+    pub fn method_gas_cap(&self, method: &str) -> u64 {
+        self.method_gas_caps.get(method).copied().unwrap_or(self.rpc_gas_cap)
+    }
+
+    /// Configures the maximum duration of a single `eth_call` execution
+    pub const fn call_timeout(mut self, timeout: Duration) -> Self {
+        self.call_timeout = Some(timeout);
+        self
+    }
+
+    /// Configures the percentage that is added to the gas limit found by `eth_estimateGas`
+    pub const fn estimate_gas_buffer_percent(mut self, buffer_percent: u64) -> Self {
+        self.estimate_gas_buffer_percent = buffer_percent;
+        self
+    }
+
+    /// Configures how long an assembled pending block is served before it is assembled again
+    pub const fn pending_block_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.pending_block_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Configures how long the blocks that block tags resolve to are cached
+    pub const fn tag_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.tag_cache_ttl = Some(ttl);
+        self
+    }
+
     /// Configures the maximum proof window for historical proof generation.
     pub const fn eth_proof_window(mut self, window: u64) -> Self {
         self.eth_proof_window = window;
         self
     }
 
+    /// Configures the interval at which stale filters are reaped
+    pub const fn stale_filter_reap_interval(mut self, interval: Duration) -> Self {
+        self.stale_filter_reap_interval = Some(interval);
+        self
+    }
+
+    /// Configures whether `eth_feeHistory` is disabled, which skips populating the fee history
+    /// cache
+    pub const fn disable_fee_history(mut self, disable: bool) -> Self {
+        self.disable_fee_history = disable;
+        self
+    }
+
+    /// Configures whether `eth_feeHistory` responses include the EIP-4844 blob fee fields
+    pub const fn fee_history_blob_fees(mut self, include: bool) -> Self {
+        self.fee_history_cache.include_blob_fees = include;
+        self
+    }
+
+    /// Configures the store that fee history entries are persisted to
+    pub fn fee_history_store(mut self, store: FeeHistoryStoreHandle) -> Self {
+        self.fee_history_store = store;
+        self
+    }
+
     /// Configures the number of getproof requests
     pub const fn proof_permits(mut self, permits: usize) -> Self {
         self.proof_permits = permits;
         self
     }
+
+    /// Configures the number of threads of the blocking task pool
+    pub const fn blocking_task_threads(mut self, threads: usize) -> Self {
+        self.blocking_task_threads = Some(threads);
+        self
+    }
+
+    /// Configures the number of buffered canonical state notifications per consuming task
+    pub const fn canonical_stream_buffer(mut self, slots: usize) -> Self {
+        self.canonical_stream_buffer = slots;
+        self
+    }
+
+    /// Configures whether a panic in the state cache update task shuts down the node, or
+    /// restarts the task instead.
+    ///
+    /// See [`EthConfig::cache_task_critical`] for the staleness risk of non-critical mode.
+    pub const fn cache_task_critical(mut self, critical: bool) -> Self {
+        self.cache_task_critical = critical;
+        self
+    }
+
+    /// Configures the chain id reported by `eth_chainId` and `net_version`
+    pub const fn chain_id_override(mut self, chain_id: u64) -> Self {
+        self.chain_id_override = Some(chain_id);
+        self
+    }
+
+    /// Configures how quantities are encoded in the responses of `eth` namespace methods
+    pub const fn number_encoding(mut self, encoding: NumberEncoding) -> Self {
+        self.number_encoding = encoding;
+        self
+    }
+
+    /// Configures the cache of responses of deterministic `eth` namespace methods
+    pub fn response_cache(mut self, config: ResponseCacheConfig) -> Self {
+        self.response_cache = Some(config);
+        self
+    }
+
+    /// Configures whether the revert data is stripped from the errors of reverted `eth_call` and
+    /// `eth_estimateGas` requests
+    pub const fn strip_revert_data(mut self, strip: bool) -> Self {
+        self.strip_revert_data = strip;
+        self
+    }
+
+    /// Configures the maximum size of the serialized result of any `eth` namespace method
+    pub const fn max_response_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_response_bytes = Some(max_bytes);
+        self
+    }
 }
 
 /// Config for the filter
@@ -130,11 +590,23 @@ pub struct EthFilterConfig {
     ///
     /// If `None` then no limit is enforced.
     pub max_logs_per_response: Option<usize>,
+    /// Maximum number of items returned by a single `eth_getFilterChanges` poll, the remaining
+    /// changes are returned by the next poll.
+    ///
+    /// If `None` then every poll returns all changes since the last poll.
+    pub max_changes_per_poll: Option<usize>,
+    /// Whether range log queries skip blocks whose logs bloom can't match the filter.
+    pub use_bloom_prefilter: bool,
     /// How long a filter remains valid after the last poll.
     ///
     /// A filter is considered stale if it has not been polled for longer than this duration and
     /// will be removed.
     pub stale_filter_ttl: Duration,
+    /// How often stale filters are reaped.
+    ///
+    /// This is independent of [`EthFilterConfig::stale_filter_ttl`], so that expired filters can
+    /// be reclaimed promptly even if the TTL is long.
+    pub reap_interval: Duration,
 }
 
 impl EthFilterConfig {
@@ -151,11 +623,29 @@ impl EthFilterConfig {
         self
     }
 
+    /// Sets the maximum number of items returned by a single `eth_getFilterChanges` poll.
+    pub const fn max_changes_per_poll(mut self, num: Option<usize>) -> Self {
+        self.max_changes_per_poll = num;
+        self
+    }
+
+    /// Sets whether range log queries skip blocks whose logs bloom can't match the filter.
+    pub const fn use_bloom_prefilter(mut self, enabled: bool) -> Self {
+        self.use_bloom_prefilter = enabled;
+        self
+    }
+
     /// Sets how long a filter remains valid after the last poll before it will be removed.
     pub const fn stale_filter_ttl(mut self, duration: Duration) -> Self {
         self.stale_filter_ttl = duration;
         self
     }
+
+    /// Sets how often stale filters are reaped.
+    pub const fn reap_interval(mut self, interval: Duration) -> Self {
+        self.reap_interval = interval;
+        self
+    }
 }
 
 impl Default for EthFilterConfig {
@@ -163,8 +653,41 @@ impl Default for EthFilterConfig {
         Self {
             max_blocks_per_filter: None,
             max_logs_per_response: None,
-            // 5min
-            stale_filter_ttl: Duration::from_secs(5 * 60),
+            max_changes_per_poll: None,
+            use_bloom_prefilter: true,
+            stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
+            reap_interval: default_stale_filter_reap_interval(DEFAULT_STALE_FILTER_TTL),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_is_stable() {
+        let config = EthConfig::default();
+        assert_eq!(config.fingerprint(), EthConfig::default().fingerprint());
+        assert_eq!(config.fingerprint(), config.clone().fingerprint());
+
+        let changed = config.clone().stale_filter_ttl(Duration::from_secs(1));
+        assert_ne!(config.fingerprint(), changed.fingerprint());
+    }
+
+    #[test]
+    fn method_gas_cap_overrides_rpc_gas_cap() {
+        let config = EthConfig::default()
+            .rpc_gas_cap(1_000)
+            .with_method_gas_cap("debug_traceCall", 5_000)
+            .with_method_gas_cap("eth_estimateGas", 500);
+
+        assert_eq!(config.method_gas_cap("debug_traceCall"), 5_000);
+        assert_eq!(config.method_gas_cap("eth_estimateGas"), 500);
+        assert_eq!(config.method_gas_cap("eth_call"), 1_000);
+
+        // the last override of a method wins
+        let config = config.with_method_gas_cap("debug_traceCall", 2_000);
+        assert_eq!(config.method_gas_cap("debug_traceCall"), 2_000);
+    }
+}
@@ -1,13 +1,27 @@
 //! Context required for building `eth` namespace APIs.
 
-use reth_chain_state::CanonStateSubscriptions;
+use std::{collections::BTreeSet, pin::Pin};
+
+use futures::{future, Stream, StreamExt};
+use reth_chain_state::{CanonStateNotification, CanonStateSubscriptions};
 use reth_chainspec::ChainSpecProvider;
-use reth_storage_api::BlockReaderIdExt;
-use reth_tasks::TaskSpawner;
+use reth_errors::ProviderResult;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives::{BlockHashOrNumber, SealedHeader, TxHash};
+use reth_storage_api::{BlockReaderIdExt, StateProviderBox, StateProviderFactory};
+use reth_tasks::{
+    pool::{BlockingTaskGuard, BlockingTaskPool},
+    TaskSpawner,
+};
+use reth_transaction_pool::TransactionPool;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream};
+use tracing::debug;
 
 use crate::{
-    fee_history::fee_history_cache_new_blocks_task, EthConfig, EthStateCache, FeeHistoryCache,
-    GasPriceOracle,
+    fee_history::fee_history_cache_new_blocks_task, DynEthResponseMiddleware, EthConfig,
+    EthStateCache, EthStateCacheConfig, FeeHistoryCache, GasCapMetrics, GasPriceOracle,
+    NumberEncoding, ProofPermitMetrics,
 };
 
 /// Context for building the `eth` namespace API.
@@ -29,6 +43,218 @@ pub struct EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events> {
     pub events: Events,
     /// RPC cache handle.
     pub cache: EthStateCache,
+    /// Separate cache for tracing calls, if configured with [`EthConfig::trace_cache`].
+    pub trace_cache: Option<EthStateCache>,
+    /// Pool for CPU heavy blocking tasks, shared by all namespace builders.
+    ///
+    /// Built with [`EthConfig::build_blocking_task_pool`].
+    pub blocking_task_pool: BlockingTaskPool,
+    /// Metrics for requests exceeding the RPC gas cap, shared by all namespace builders.
+    pub gas_cap_metrics: GasCapMetrics,
+    /// Metrics for the permits of `eth_getProof` calls, shared by all namespace builders.
+    pub proof_permit_metrics: ProofPermitMetrics,
+    /// Restricts the number of concurrent tracing and simulation calls, shared by all namespace
+    /// builders.
+    pub tracing_task_guard: BlockingTaskGuard,
+    /// Post-processes the responses of the `eth` namespace, if set.
+    pub response_middleware: Option<DynEthResponseMiddleware>,
+}
+
+impl<Provider, Pool, EvmConfig, Network, Tasks, Events>
+    EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>
+{
+    /// Returns the [`BlockingTaskPool`] shared by all APIs built from this context.
+    pub fn blocking_task_pool(&self) -> BlockingTaskPool {
+        self.blocking_task_pool.clone()
+    }
+
+    /// Returns a new stream of canonical state notifications, for tasks that keep components
+    /// built from this context up to date.
+    ///
+    /// If the configured `canonical_stream_buffer` is non-zero, notifications are eagerly drained
+    /// from the broadcast channel into a buffer with that many slots, so that a consumer that is
+    /// briefly slow doesn't cause notifications to be dropped. Dropped notifications are recorded
+    /// in metrics.
+    pub fn canonical_state_stream(
+        &self,
+    ) -> Pin<Box<dyn Stream<Item = CanonStateNotification> + Send + 'static>>
+    where
+        Tasks: TaskSpawner,
+        Events: CanonStateSubscriptions,
+    {
+        new_canonical_state_stream(
+            &self.events,
+            &self.executor,
+            self.config.canonical_stream_buffer,
+        )
+    }
+
+    /// Returns a new stream of canonical state notifications that are received directly from the
+    /// broadcast channel, without the configured `canonical_stream_buffer`.
+    ///
+    /// Unlike [`Self::canonical_state_stream`], the stream yields
+    /// [`BroadcastStreamRecvError::Lagged`] if the consumer fell behind and notifications were
+    /// dropped, e.g. so that the consumer can account for them.
+    pub fn canonical_state_broadcast_stream(&self) -> BroadcastStream<CanonStateNotification>
+    where
+        Events: CanonStateSubscriptions,
+    {
+        BroadcastStream::new(self.events.subscribe_to_canonical_state())
+    }
+
+    /// Returns a function that creates new streams of canonical state notifications, see
+    /// [`Self::canonical_state_broadcast_stream`].
+    ///
+    /// This can be used to re-subscribe from within a task, e.g. after it was restarted.
+    pub fn canonical_state_broadcast_stream_factory(
+        &self,
+    ) -> impl Fn() -> BroadcastStream<CanonStateNotification> + Send + Sync + 'static
+    where
+        Events: CanonStateSubscriptions + Clone + 'static,
+    {
+        let events = self.events.clone();
+        move || BroadcastStream::new(events.subscribe_to_canonical_state())
+    }
+
+    /// Returns a read-only provider of the state after the given block, e.g. for custom tracing
+    /// or simulation APIs that execute on top of a pinned historical block.
+    ///
+    /// Fails if the block is not known or its state has been pruned.
+    pub fn state_at(&self, block: BlockHashOrNumber) -> ProviderResult<StateProviderBox>
+    where
+        Provider: StateProviderFactory,
+    {
+        match block {
+            BlockHashOrNumber::Hash(hash) => self.provider.history_by_block_hash(hash),
+            BlockHashOrNumber::Number(number) => self.provider.history_by_block_number(number),
+        }
+    }
+
+    /// Returns a new stream of the hashes of transactions that are added to the pending
+    /// sub-pool of the transaction pool and are allowed to be propagated.
+    ///
+    /// This is the same listener the `eth_subscribe` `newPendingTransactions` subscription uses.
+    pub fn pending_transactions_stream(&self) -> ReceiverStream<TxHash>
+    where
+        Pool: TransactionPool,
+    {
+        ReceiverStream::new(self.pool.pending_transactions_listener())
+    }
+
+    /// Returns a summary of the configuration of this context, e.g. to log it or to assert in
+    /// tests that the intended [`EthConfig`] was threaded through to a builder.
+    ///
+    /// The chain id is not known without a [`ChainSpecProvider`], see
+    /// [`Self::describe_with_chain`].
+    pub fn describe(&self) -> CtxDescription {
+        let config = &self.config;
+        let features = [
+            ("bloom_prefilter", config.use_bloom_prefilter),
+            ("call_timeout", config.call_timeout.is_some()),
+            ("chain_id_override", config.chain_id_override.is_some()),
+            ("decimal_quantities", config.number_encoding == NumberEncoding::Decimal),
+            ("fee_history", !config.disable_fee_history),
+            ("log_backfill", config.subscription_log_backfill),
+            ("max_response_bytes", config.max_response_bytes.is_some()),
+            ("response_cache", config.response_cache.is_some()),
+            ("response_middleware", self.response_middleware.is_some()),
+            ("strip_revert_data", config.strip_revert_data),
+            ("tag_cache", config.tag_cache_ttl.is_some()),
+            ("trace_block_window", config.trace_block_window.is_some()),
+            ("trace_cache", self.trace_cache.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(feature, enabled)| enabled.then_some(feature))
+        .collect();
+
+        CtxDescription {
+            chain_id: None,
+            rpc_gas_cap: config.rpc_gas_cap,
+            cache: config.cache.clone(),
+            trace_cache: config.trace_cache.clone(),
+            features,
+        }
+    }
+
+    /// Returns a summary of the configuration of this context, including the chain id of the
+    /// provider's chain spec, see [`Self::describe`].
+    pub fn describe_with_chain(&self) -> CtxDescription
+    where
+        Provider: ChainSpecProvider,
+    {
+        CtxDescription {
+            chain_id: Some(self.provider.chain_spec().chain().id()),
+            ..self.describe()
+        }
+    }
+}
+
+/// Summary of the configuration of an [`EthApiBuilderCtx`], see [`EthApiBuilderCtx::describe`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CtxDescription {
+    /// The chain id of the provider's chain spec, if known.
+    ///
+    /// This is the chain id of the network, regardless of [`EthConfig::chain_id_override`].
+    pub chain_id: Option<u64>,
+    /// Gas limit for `eth_call` and call tracing methods, see [`EthConfig::rpc_gas_cap`].
+    pub rpc_gas_cap: u64,
+    /// Settings of the state cache.
+    pub cache: EthStateCacheConfig,
+    /// Settings of the separate cache for tracing calls, if configured.
+    pub trace_cache: Option<EthStateCacheConfig>,
+    /// Names of the optional features that are enabled, e.g. `response_cache` or
+    /// `strip_revert_data`.
+    pub features: BTreeSet<&'static str>,
+}
+
+impl CtxDescription {
+    /// Returns `true` if the optional feature with the given name is enabled.
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.contains(feature)
+    }
+}
+
+/// Returns a new stream of canonical state notifications, that is buffered with `buffer` slots
+/// if non-zero.
+fn new_canonical_state_stream<Tasks, Events>(
+    events: &Events,
+    executor: &Tasks,
+    buffer: usize,
+) -> Pin<Box<dyn Stream<Item = CanonStateNotification> + Send + 'static>>
+where
+    Tasks: TaskSpawner,
+    Events: CanonStateSubscriptions,
+{
+    let metrics = CanonicalStreamMetrics::default();
+    let stream = BroadcastStream::new(events.subscribe_to_canonical_state()).filter_map(
+        move |notification| {
+            future::ready(match notification {
+                Ok(notification) => Some(notification),
+                Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                    debug!(target: "rpc::eth", skipped, "canonical state notification stream lagging behind");
+                    metrics.lagged_notifications_total.increment(skipped);
+                    None
+                }
+            })
+        },
+    );
+
+    if buffer == 0 {
+        return Box::pin(stream)
+    }
+
+    let (tx, rx) = mpsc::channel(buffer);
+    executor.spawn(Box::pin(async move {
+        let mut stream = std::pin::pin!(stream);
+        while let Some(notification) = stream.next().await {
+            if tx.send(notification).await.is_err() {
+                // the consumer was dropped
+                break
+            }
+        }
+    }));
+
+    Box::pin(ReceiverStream::new(rx))
 }
 
 impl<Provider, Pool, EvmConfig, Network, Tasks, Events>
@@ -50,6 +276,12 @@ where
     pub fn new_gas_price_oracle(&self) -> GasPriceOracle<Provider> {
         GasPriceOracleBuilder::build(self)
     }
+
+    /// Returns the header of the latest block, e.g. to seed caches and oracles built from this
+    /// context.
+    pub fn latest_header(&self) -> ProviderResult<Option<SealedHeader>> {
+        self.provider.latest_header()
+    }
 }
 
 /// Builds `eth_` core api component [`GasPriceOracle`], for given context.
@@ -65,6 +297,7 @@ impl GasPriceOracleBuilder {
         Provider: BlockReaderIdExt + Clone,
     {
         GasPriceOracle::new(ctx.provider.clone(), ctx.config.gas_oracle, ctx.cache.clone())
+            .with_price_bounds(ctx.config.gas_price_floor, ctx.config.gas_price_ceiling)
     }
 }
 
@@ -74,6 +307,9 @@ pub struct FeeHistoryCacheBuilder;
 
 impl FeeHistoryCacheBuilder {
     /// Builds a [`FeeHistoryCache`], for given context.
+    ///
+    /// If fee history is disabled in the config, this returns a disabled cache and does not spawn
+    /// the task that populates it.
     pub fn build<Provider, Pool, EvmConfig, Network, Tasks, Events>(
         ctx: &EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events>,
     ) -> FeeHistoryCache
@@ -82,10 +318,17 @@ impl FeeHistoryCacheBuilder {
         Tasks: TaskSpawner,
         Events: CanonStateSubscriptions,
     {
-        let fee_history_cache =
-            FeeHistoryCache::new(ctx.cache.clone(), ctx.config.fee_history_cache);
+        if ctx.config.disable_fee_history {
+            return FeeHistoryCache::disabled(ctx.cache.clone(), ctx.config.fee_history_cache)
+        }
 
-        let new_canonical_blocks = ctx.events.canonical_state_stream();
+        let fee_history_cache = FeeHistoryCache::new_with_store(
+            ctx.cache.clone(),
+            ctx.config.fee_history_cache,
+            ctx.config.fee_history_store.clone(),
+        );
+
+        let new_canonical_blocks = ctx.canonical_state_stream();
         let fhc = fee_history_cache.clone();
         let provider = ctx.provider.clone();
         ctx.executor.spawn_critical(
@@ -98,3 +341,12 @@ impl FeeHistoryCacheBuilder {
         fee_history_cache
     }
 }
+
+/// Metrics for the canonical state streams consumed by `eth` namespace tasks.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.eth_canonical_stream")]
+struct CanonicalStreamMetrics {
+    /// The number of canonical state notifications that were dropped because a consumer lagged
+    /// behind.
+    lagged_notifications_total: Counter,
+}
@@ -0,0 +1,193 @@
+//! Post-processing of `eth` namespace responses.
+
+use std::{fmt, sync::Arc};
+
+use reth_primitives::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Post-processes the responses of `eth` namespace methods, e.g. to redact fields.
+pub trait EthResponseMiddleware: fmt::Debug + Send + Sync + 'static {
+    /// Returns the response to send for the given method, given the serialized `result` of a
+    /// successful call.
+    ///
+    /// Error responses are not passed through the middleware.
+    fn on_response(&self, method: &str, result: Value) -> Value;
+}
+
+/// Shared handle to an [`EthResponseMiddleware`].
+pub type DynEthResponseMiddleware = Arc<dyn EthResponseMiddleware>;
+
+/// How quantities are encoded in the responses of `eth` namespace methods.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NumberEncoding {
+    /// Hex strings with a `0x` prefix, as specified by the JSON-RPC API.
+    #[default]
+    Hex,
+    /// Decimal strings, for consumers that can't handle hex quantities, see
+    /// [`DecimalQuantities`].
+    Decimal,
+}
+
+/// Methods whose result is a single quantity.
+const QUANTITY_METHODS: &[&str] = &[
+    "eth_blobBaseFee",
+    "eth_blockNumber",
+    "eth_chainId",
+    "eth_estimateGas",
+    "eth_gasPrice",
+    "eth_getBalance",
+    "eth_getBlockTransactionCountByHash",
+    "eth_getBlockTransactionCountByNumber",
+    "eth_getTransactionCount",
+    "eth_getUncleCountByBlockHash",
+    "eth_getUncleCountByBlockNumber",
+    "eth_maxPriorityFeePerGas",
+];
+
+/// Fields of blocks, transactions, receipts, logs and fee histories that hold quantities, or
+/// arrays of quantities.
+const QUANTITY_FIELDS: &[&str] = &[
+    "baseFeePerGas",
+    "baseFeePerBlobGas",
+    "blobGasPrice",
+    "blobGasUsed",
+    "blockNumber",
+    "chainId",
+    "cumulativeGasUsed",
+    "difficulty",
+    "effectiveGasPrice",
+    "excessBlobGas",
+    "gas",
+    "gasLimit",
+    "gasPrice",
+    "gasUsed",
+    "logIndex",
+    "maxFeePerBlobGas",
+    "maxFeePerGas",
+    "maxPriorityFeePerGas",
+    "nonce",
+    "number",
+    "oldestBlock",
+    "reward",
+    "size",
+    "timestamp",
+    "totalDifficulty",
+    "transactionIndex",
+    "value",
+];
+
+/// An [`EthResponseMiddleware`] that encodes quantities as decimal strings instead of hex
+/// strings, see [`NumberEncoding::Decimal`].
+///
+/// Hex strings and quantities can't be told apart by their value, so only the results of methods
+/// that return a single quantity and the known quantity fields of the returned objects are
+/// converted. The `nonce` of a block is 8 bytes of data and is kept as is.
+#[derive(Debug, Default)]
+pub struct DecimalQuantities {
+    /// Middleware that processes the responses before their quantities are converted.
+    inner: Option<DynEthResponseMiddleware>,
+}
+
+impl DecimalQuantities {
+    /// Creates a new middleware that converts the quantities of the responses returned by the
+    /// given middleware, if any.
+    pub const fn new(inner: Option<DynEthResponseMiddleware>) -> Self {
+        Self { inner }
+    }
+}
+
+impl EthResponseMiddleware for DecimalQuantities {
+    fn on_response(&self, method: &str, result: Value) -> Value {
+        let mut result = match &self.inner {
+            Some(inner) => inner.on_response(method, result),
+            None => result,
+        };
+        decimal_quantities(&mut result, QUANTITY_METHODS.contains(&method));
+        result
+    }
+}
+
+/// Converts the quantities of the value to decimal strings.
+fn decimal_quantities(value: &mut Value, is_quantity: bool) {
+    match value {
+        Value::String(value) if is_quantity => {
+            if let Some(decimal) = hex_quantity_to_decimal(value) {
+                *value = decimal;
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                decimal_quantities(value, is_quantity);
+            }
+        }
+        Value::Object(fields) => {
+            let is_block = fields.contains_key("parentHash");
+            for (field, value) in fields.iter_mut() {
+                let is_quantity =
+                    QUANTITY_FIELDS.contains(&field.as_str()) && !(is_block && field == "nonce");
+                decimal_quantities(value, is_quantity);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the decimal encoding of the given hex encoded quantity, or `None` if the string is not
+/// a valid quantity, i.e. has leading zeros or exceeds 256 bits.
+fn hex_quantity_to_decimal(value: &str) -> Option<String> {
+    let digits = value.strip_prefix("0x")?;
+    if digits.is_empty() || digits.len() > 64 || (digits.len() > 1 && digits.starts_with('0')) {
+        return None
+    }
+    U256::from_str_radix(digits, 16).ok().map(|quantity| quantity.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn converts_known_quantities() {
+        let middleware = DecimalQuantities::default();
+
+        assert_eq!(middleware.on_response("eth_blockNumber", json!("0x1a")), json!("26"));
+        // data is never converted
+        assert_eq!(middleware.on_response("eth_getCode", json!("0x1a")), json!("0x1a"));
+
+        let block = json!({
+            "number": "0x10",
+            "hash": "0x1234",
+            "parentHash": "0x5678",
+            "nonce": "0x1000000000000000",
+            "transactions": [{ "nonce": "0x2", "input": "0x01", "value": "0x0" }],
+        });
+        assert_eq!(
+            middleware.on_response("eth_getBlockByNumber", block),
+            json!({
+                "number": "16",
+                "hash": "0x1234",
+                "parentHash": "0x5678",
+                "nonce": "0x1000000000000000",
+                "transactions": [{ "nonce": "2", "input": "0x01", "value": "0" }],
+            })
+        );
+
+        let fee_history = json!({ "oldestBlock": "0x1", "reward": [["0xa", "0xb"]] });
+        assert_eq!(
+            middleware.on_response("eth_feeHistory", fee_history),
+            json!({ "oldestBlock": "1", "reward": [["10", "11"]] })
+        );
+    }
+
+    #[test]
+    fn keeps_invalid_quantities() {
+        assert_eq!(hex_quantity_to_decimal("0x0"), Some("0".to_string()));
+        assert_eq!(hex_quantity_to_decimal("0x01"), None);
+        assert_eq!(hex_quantity_to_decimal("0x"), None);
+        assert_eq!(hex_quantity_to_decimal("26"), None);
+        assert_eq!(hex_quantity_to_decimal(&format!("0x1{}", "0".repeat(64))), None);
+    }
+}
@@ -2,3 +2,6 @@
 
 pub mod config;
 pub mod ctx;
+pub mod middleware;
+pub mod rate_limit;
+pub mod response_cache;
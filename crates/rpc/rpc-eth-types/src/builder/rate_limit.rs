@@ -0,0 +1,29 @@
+//! Rate limiting of `eth` namespace requests.
+
+use std::{fmt, sync::Arc};
+
+/// Error code of the `Limit exceeded` error, returned for throttled requests.
+///
+/// See also <https://eips.ethereum.org/EIPS/eip-1474#error-codes>
+pub const LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// Outcome of a [`RateLimiter`] check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimit {
+    /// The request can be served.
+    Ready,
+    /// The request exceeds the limit and is rejected.
+    Throttled,
+}
+
+/// Limits the rate of requests to `eth` namespace methods, keyed by method name, e.g. with a
+/// token or leaky bucket per method.
+pub trait RateLimiter: fmt::Debug + Send + Sync + 'static {
+    /// Returns whether a request to the given method can be served now.
+    ///
+    /// Called once per request, before the request is handled.
+    fn check(&self, method: &str) -> RateLimit;
+}
+
+/// Shared handle to a [`RateLimiter`].
+pub type DynRateLimiter = Arc<dyn RateLimiter>;
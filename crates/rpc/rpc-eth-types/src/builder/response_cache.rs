@@ -0,0 +1,315 @@
+//! Cache for the responses of deterministic `eth` namespace methods.
+
+use std::{
+    collections::BTreeSet,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
+use reth_chain_state::CanonStateNotification;
+use schnellru::{ByLength, LruMap};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+
+/// Default maximum number of cached responses.
+pub const DEFAULT_RESPONSE_CACHE_MAX_ENTRIES: u32 = 10_000;
+
+/// Default duration for which a cached response is served.
+pub const DEFAULT_RESPONSE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Block tags whose block changes over time, so requests that contain them are never cached.
+const MOVING_BLOCK_TAGS: [&str; 4] = ["latest", "pending", "safe", "finalized"];
+
+/// Methods with an optional block param, by the index of the param.
+///
+/// These default to the `latest` block if the param is omitted, so requests without the param are
+/// never cached.
+const OPTIONAL_BLOCK_PARAMS: [(&str, usize); 8] = [
+    ("eth_call", 1),
+    ("eth_createAccessList", 1),
+    ("eth_estimateGas", 1),
+    ("eth_getBalance", 1),
+    ("eth_getCode", 1),
+    ("eth_getProof", 2),
+    ("eth_getStorageAt", 2),
+    ("eth_getTransactionCount", 1),
+];
+
+/// Settings for the [`ResponseCache`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseCacheConfig {
+    /// Maximum number of cached responses, the least recently used response is evicted first.
+    pub max_entries: u32,
+    /// How long a cached response is served.
+    ///
+    /// The cache is cleared on every reorg, see [`response_cache_reorg_task`], so this only bounds
+    /// how long a response that changed otherwise is served from the cache.
+    pub ttl: Duration,
+    /// The methods whose responses are cached, e.g. `eth_getTransactionReceipt`.
+    ///
+    /// Only methods whose response is fully determined by their params should be listed. Requests
+    /// that omit the optional block param of a method, e.g. of `eth_getBalance`, default to the
+    /// `latest` block and are not cached.
+    pub methods: BTreeSet<String>,
+}
+
+impl ResponseCacheConfig {
+    /// Configures the maximum number of cached responses
+    pub const fn max_entries(mut self, max_entries: u32) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Configures how long a cached response is served
+    pub const fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Adds the given method to the methods whose responses are cached
+    pub fn with_method(mut self, method: impl Into<String>) -> Self {
+        self.methods.insert(method.into());
+        self
+    }
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_RESPONSE_CACHE_MAX_ENTRIES,
+            ttl: DEFAULT_RESPONSE_CACHE_TTL,
+            methods: BTreeSet::new(),
+        }
+    }
+}
+
+/// Key of a cached response: the method and its normalized JSON params.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResponseCacheKey {
+    method: String,
+    params: String,
+}
+
+/// Caches the successful responses of the allowlisted methods of a [`ResponseCacheConfig`] by
+/// method and params, for the configured duration.
+///
+/// Requests are not cached if any of their params is a block tag that moves with the chain, i.e.
+/// `latest`, `pending`, `safe` or `finalized`, or if they omit an optional block param. Empty
+/// responses, i.e. `null`, are not cached either, since these typically turn into a result later
+/// on, e.g. the receipt of a transaction that is not yet included.
+#[derive(Debug)]
+pub struct ResponseCache {
+    ttl: Duration,
+    methods: BTreeSet<String>,
+    entries: Mutex<LruMap<ResponseCacheKey, (Value, Instant), ByLength>>,
+}
+
+impl ResponseCache {
+    /// Creates a new, empty cache with the given settings.
+    pub fn new(config: ResponseCacheConfig) -> Self {
+        let ResponseCacheConfig { max_entries, ttl, methods } = config;
+        Self { ttl, methods, entries: Mutex::new(LruMap::new(ByLength::new(max_entries))) }
+    }
+
+    /// Returns `true` if responses of the given method are cached.
+    pub fn is_cached_method(&self, method: &str) -> bool {
+        self.methods.contains(method)
+    }
+
+    /// Returns the key for a request to the given method with the given raw JSON params, or
+    /// `None` if the request must not be cached.
+    pub fn key(&self, method: &str, params: Option<&str>) -> Option<ResponseCacheKey> {
+        if !self.is_cached_method(method) {
+            return None
+        }
+
+        let params = match params {
+            Some(params) => serde_json::from_str::<Value>(params).ok()?,
+            None => Value::Null,
+        };
+        if contains_moving_block_tag(&params) || omits_block_param(method, &params) {
+            return None
+        }
+
+        Some(ResponseCacheKey { method: method.to_string(), params: params.to_string() })
+    }
+
+    /// Returns the cached response for the given key, if it is not older than the configured
+    /// duration.
+    pub fn get(&self, key: &ResponseCacheKey) -> Option<Value> {
+        let mut entries = self.entries.lock();
+        let (response, cached_at) = entries.get(key)?;
+        if cached_at.elapsed() < self.ttl {
+            return Some(response.clone())
+        }
+        entries.remove(key);
+        None
+    }
+
+    /// Caches the response for the given key, unless it is empty.
+    pub fn insert(&self, key: ResponseCacheKey, response: &Value) {
+        if response.is_null() {
+            return
+        }
+        self.entries.lock().insert(key, (response.clone(), Instant::now()));
+    }
+
+    /// Returns the number of cached responses, including expired ones that were not yet
+    /// requested again.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Returns `true` if no responses are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached responses.
+    pub fn clear(&self) {
+        self.entries.lock().clear();
+    }
+}
+
+/// Awaits canonical state notifications and clears the cache on every reorg, so that responses
+/// that depend on reorged out blocks, e.g. the receipt of a transaction requested by its hash, are
+/// not served until they expire.
+///
+/// Notifications that were dropped because the task lagged behind the broadcast channel may have
+/// been reorgs, so the cache is cleared on lag as well. The task ends once the stream ends.
+pub async fn response_cache_reorg_task<St>(cache: Arc<ResponseCache>, mut events: St)
+where
+    St: Stream<Item = Result<CanonStateNotification, BroadcastStreamRecvError>> + Unpin + 'static,
+{
+    while let Some(event) = events.next().await {
+        match event {
+            Ok(CanonStateNotification::Commit { .. }) => {}
+            Ok(CanonStateNotification::Reorg { .. }) | Err(BroadcastStreamRecvError::Lagged(_)) => {
+                cache.clear()
+            }
+        }
+    }
+}
+
+/// Returns `true` if any string in the given params is a block tag that moves with the chain.
+fn contains_moving_block_tag(params: &Value) -> bool {
+    match params {
+        Value::String(value) => MOVING_BLOCK_TAGS.contains(&value.as_str()),
+        Value::Array(values) => values.iter().any(contains_moving_block_tag),
+        Value::Object(values) => values.values().any(contains_moving_block_tag),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the given method has an optional block param that is missing from the given
+/// params, in which case the `latest` block is used.
+fn omits_block_param(method: &str, params: &Value) -> bool {
+    let Some((_, index)) = OPTIONAL_BLOCK_PARAMS.iter().find(|(name, _)| *name == method) else {
+        return false
+    };
+    match params {
+        Value::Array(values) => values.get(*index).map_or(true, Value::is_null),
+        // named params are not resolved, so they are treated as omitted
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_execution_types::Chain;
+    use tokio::sync::broadcast;
+    use tokio_stream::wrappers::BroadcastStream;
+
+    fn cache() -> ResponseCache {
+        ResponseCache::new(ResponseCacheConfig::default().with_method("eth_getBlockByNumber"))
+    }
+
+    #[test]
+    fn only_caches_allowlisted_methods_of_fixed_blocks() {
+        let cache = cache();
+
+        assert!(cache.key("eth_getBlockByNumber", Some(r#"["0x1", false]"#)).is_some());
+        assert!(cache.key("eth_getBlockByNumber", Some(r#"["latest", false]"#)).is_none());
+        assert!(cache
+            .key("eth_getBlockByNumber", Some(r#"[{"blockNumber": "pending"}, false]"#))
+            .is_none());
+        assert!(cache.key("eth_blockNumber", None).is_none());
+
+        // params are normalized
+        assert_eq!(
+            cache.key("eth_getBlockByNumber", Some(r#"["0x1",false]"#)),
+            cache.key("eth_getBlockByNumber", Some(r#"[ "0x1", false ]"#))
+        );
+    }
+
+    #[test]
+    fn does_not_cache_omitted_block_params() {
+        let cache = ResponseCache::new(
+            ResponseCacheConfig::default()
+                .with_method("eth_getBalance")
+                .with_method("eth_getStorageAt"),
+        );
+        let address = r#""0x0000000000000000000000000000000000000001""#;
+
+        assert!(cache.key("eth_getBalance", Some(&format!("[{address}, \"0x1\"]"))).is_some());
+        assert!(cache.key("eth_getBalance", Some(&format!("[{address}]"))).is_none());
+        assert!(cache.key("eth_getBalance", Some(&format!("[{address}, null]"))).is_none());
+        assert!(cache.key("eth_getBalance", None).is_none());
+
+        assert!(cache
+            .key("eth_getStorageAt", Some(&format!("[{address}, \"0x0\", \"0x1\"]")))
+            .is_some());
+        assert!(cache.key("eth_getStorageAt", Some(&format!("[{address}, \"0x0\"]"))).is_none());
+    }
+
+    #[test]
+    fn serves_responses_until_expired() {
+        let cache = cache();
+        let key = cache.key("eth_getBlockByNumber", Some(r#"["0x1", false]"#)).unwrap();
+
+        cache.insert(key.clone(), &Value::Null);
+        assert!(cache.get(&key).is_none());
+
+        cache.insert(key.clone(), &Value::from("0x2"));
+        assert_eq!(cache.get(&key), Some(Value::from("0x2")));
+
+        let cache = ResponseCache::new(
+            ResponseCacheConfig::default().with_method("eth_getBlockByNumber").ttl(Duration::ZERO),
+        );
+        cache.insert(key.clone(), &Value::from("0x2"));
+        assert!(cache.get(&key).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn clears_responses_on_reorg() {
+        let cache = Arc::new(cache());
+        let key = cache.key("eth_getBlockByNumber", Some(r#"["0x1", false]"#)).unwrap();
+        cache.insert(key.clone(), &Value::from("0x2"));
+
+        let (tx, rx) = broadcast::channel(16);
+        let task = tokio::spawn(response_cache_reorg_task(cache.clone(), BroadcastStream::new(rx)));
+
+        tx.send(CanonStateNotification::Commit { new: Arc::new(Chain::default()) }).unwrap();
+        tx.send(CanonStateNotification::Commit { new: Arc::new(Chain::default()) }).unwrap();
+        drop(tx);
+        task.await.unwrap();
+        assert_eq!(cache.get(&key), Some(Value::from("0x2")));
+
+        let (tx, rx) = broadcast::channel(16);
+        let task = tokio::spawn(response_cache_reorg_task(cache.clone(), BroadcastStream::new(rx)));
+
+        tx.send(CanonStateNotification::Reorg {
+            old: Arc::new(Chain::default()),
+            new: Arc::new(Chain::default()),
+        })
+        .unwrap();
+        drop(tx);
+        task.await.unwrap();
+        assert!(cache.is_empty());
+    }
+}
@@ -2,13 +2,14 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::remote::RemoteStateSource;
 use reth_rpc_server_types::constants::cache::{
     DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_ENV_CACHE_MAX_LEN,
     DEFAULT_RECEIPT_CACHE_MAX_LEN,
 };
 
 /// Settings for the [`EthStateCache`](super::EthStateCache).
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EthStateCacheConfig {
     /// Max number of blocks in cache.
@@ -27,6 +28,24 @@ pub struct EthStateCacheConfig {
     ///
     /// Default is 512.
     pub max_concurrent_db_requests: usize,
+    /// Max total estimated size in bytes of cached blocks and receipts.
+    ///
+    /// The budget is split evenly between the block and receipts caches, which evict their least
+    /// recently used entries to stay under it, in addition to the count based limits. The env
+    /// cache, whose entries have a fixed size, is only limited by [`Self::max_envs`].
+    ///
+    /// Default is `None`, i.e. only count based limits are enforced.
+    #[serde(default)]
+    pub max_bytes: Option<usize>,
+    /// Source for historical blocks and receipts that are no longer retained by the local
+    /// database, e.g. on a pruned node.
+    ///
+    /// Local hits are unaffected, only blocks that are known to the local database but whose data
+    /// is missing are fetched from the remote source and then cached as usual.
+    ///
+    /// Default is `None`.
+    #[serde(skip)]
+    pub remote_fallback: Option<RemoteStateSource>,
 }
 
 impl Default for EthStateCacheConfig {
@@ -36,6 +55,8 @@ impl Default for EthStateCacheConfig {
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_envs: DEFAULT_ENV_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            max_bytes: None,
+            remote_fallback: None,
         }
     }
 }
@@ -0,0 +1,37 @@
+//! Snapshot of the contents of the [`EthStateCache`](super::EthStateCache), for debugging.
+
+use reth_primitives::{BlockNumber, B256};
+
+/// The contents of all caches of the [`EthStateCache`](super::EthStateCache) at one point in
+/// time, see [`EthStateCache::debug_dump`](super::EthStateCache::debug_dump).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheDump {
+    /// The cached blocks.
+    pub blocks: CacheCategoryDump,
+    /// The cached receipts.
+    pub receipts: CacheCategoryDump,
+    /// The cached EVM environments.
+    pub evm_envs: CacheCategoryDump,
+}
+
+/// The contents of a single cache, see [`CacheDump`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheCategoryDump {
+    /// The cached entries, from the most to the least recently used.
+    pub entries: Vec<CachedBlock>,
+    /// The estimated total size of the cached values in bytes.
+    pub estimated_size: usize,
+    /// The number of blocks for which requests are waiting on a fetch from the provider.
+    pub queued: usize,
+}
+
+/// The block of a cached entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedBlock {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The number of the block, if the cached value contains it.
+    ///
+    /// Cached receipts don't include the number of their block.
+    pub number: Option<BlockNumber>,
+}
@@ -0,0 +1,170 @@
+//! Limiter for the caches of the [`EthStateCache`](super::EthStateCache), which bounds the number
+//! of entries and optionally their estimated total size.
+
+use std::{mem, sync::Arc};
+
+use reth_primitives::{Address, BlockWithSenders, Log, Receipt, B256};
+use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
+use schnellru::Limiter;
+
+/// Heuristic for the in-memory size of a cached value.
+pub trait EstimatedSize {
+    /// Returns the estimated size of the value in bytes.
+    fn estimated_size(&self) -> usize;
+}
+
+impl EstimatedSize for BlockWithSenders {
+    fn estimated_size(&self) -> usize {
+        self.block.size() + self.senders.capacity() * mem::size_of::<Address>()
+    }
+}
+
+impl EstimatedSize for Arc<Vec<Receipt>> {
+    fn estimated_size(&self) -> usize {
+        self.iter()
+            .map(|receipt| {
+                mem::size_of::<Receipt>() +
+                    receipt
+                        .logs
+                        .iter()
+                        .map(|log| {
+                            mem::size_of::<Log>() +
+                                log.data.topics().len() * mem::size_of::<B256>() +
+                                log.data.data.len()
+                        })
+                        .sum::<usize>()
+            })
+            .sum()
+    }
+}
+
+impl EstimatedSize for (CfgEnvWithHandlerCfg, BlockEnv) {
+    fn estimated_size(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+/// Limits a cache to a maximum number of entries and, if set, a maximum total
+/// [`EstimatedSize`] of the cached values.
+///
+/// Without a size budget this behaves like [`ByLength`](schnellru::ByLength).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByLengthAndSize {
+    /// Maximum number of entries.
+    max_length: u32,
+    /// Maximum total estimated size of all entries in bytes, if any.
+    max_bytes: Option<usize>,
+    /// Total estimated size of all entries in bytes.
+    used_bytes: usize,
+}
+
+impl ByLengthAndSize {
+    /// Creates a new limiter with the given maximum number of entries and optional maximum total
+    /// size in bytes.
+    pub const fn new(max_length: u32, max_bytes: Option<usize>) -> Self {
+        Self { max_length, max_bytes, used_bytes: 0 }
+    }
+
+    /// Returns the total estimated size of all entries in bytes.
+    pub const fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+
+    /// Returns true if a value of the given size exceeds the size budget on its own.
+    fn exceeds_budget(&self, size: usize) -> bool {
+        self.max_bytes.is_some_and(|max_bytes| size > max_bytes)
+    }
+}
+
+impl<K, V: EstimatedSize> Limiter<K, V> for ByLengthAndSize {
+    type KeyToInsert<'a> = K;
+    type LinkType = u32;
+
+    fn is_over_the_limit(&self, length: usize) -> bool {
+        length > self.max_length as usize || self.exceeds_budget(self.used_bytes)
+    }
+
+    fn on_insert(
+        &mut self,
+        _length: usize,
+        key: Self::KeyToInsert<'_>,
+        value: V,
+    ) -> Option<(K, V)> {
+        let size = value.estimated_size();
+        if self.max_length == 0 || self.exceeds_budget(size) {
+            return None
+        }
+
+        self.used_bytes += size;
+        Some((key, value))
+    }
+
+    fn on_replace(
+        &mut self,
+        _length: usize,
+        _old_key: &mut K,
+        _new_key: Self::KeyToInsert<'_>,
+        old_value: &mut V,
+        new_value: &mut V,
+    ) -> bool {
+        let new_size = new_value.estimated_size();
+        if self.exceeds_budget(new_size) {
+            // the old value is removed and accounted for in `on_removed`
+            return false
+        }
+
+        self.used_bytes = self.used_bytes - old_value.estimated_size() + new_size;
+        true
+    }
+
+    fn on_removed(&mut self, _key: &mut K, value: &mut V) {
+        self.used_bytes = self.used_bytes.saturating_sub(value.estimated_size());
+    }
+
+    fn on_cleared(&mut self) {
+        self.used_bytes = 0;
+    }
+
+    fn on_grow(&mut self, _new_memory_usage: usize) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schnellru::LruMap;
+
+    impl EstimatedSize for Vec<u8> {
+        fn estimated_size(&self) -> usize {
+            self.len()
+        }
+    }
+
+    #[test]
+    fn evicts_to_stay_under_size_budget() {
+        let mut cache = LruMap::new(ByLengthAndSize::new(10, Some(100)));
+        assert!(cache.insert(1u64, vec![0u8; 40]));
+        assert!(cache.insert(2, vec![0u8; 40]));
+        assert_eq!(cache.limiter().used_bytes(), 80);
+
+        // evicts the least recently used entry
+        assert!(cache.insert(3, vec![0u8; 40]));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.peek(&1).is_none());
+        assert_eq!(cache.limiter().used_bytes(), 80);
+
+        // rejects values that exceed the budget on their own
+        assert!(!cache.insert(4, vec![0u8; 101]));
+        assert_eq!(cache.limiter().used_bytes(), 80);
+    }
+
+    #[test]
+    fn without_size_budget_limits_length() {
+        let mut cache = LruMap::new(ByLengthAndSize::new(2, None));
+        for key in 0u64..3 {
+            assert!(cache.insert(key, vec![0u8; 1024]));
+        }
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -14,4 +14,9 @@ pub(crate) struct CacheMetrics {
     pub(crate) hits_total: Counter,
     /// The number of cache misses.
     pub(crate) misses_total: Counter,
+    /// The number of entries evicted to make room for new entries.
+    pub(crate) evictions_total: Counter,
+    /// The number of requests that waited for the in-flight fetch of another request for the
+    /// same key instead of fetching the entry themselves.
+    pub(crate) coalesced_total: Counter,
 }
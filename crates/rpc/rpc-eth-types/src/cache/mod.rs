@@ -6,31 +6,49 @@ use reth_errors::{ProviderError, ProviderResult};
 use reth_evm::{provider::EvmEnvProvider, ConfigureEvm};
 use reth_execution_types::Chain;
 use reth_primitives::{
-    Block, BlockHashOrNumber, BlockWithSenders, Receipt, SealedBlock, SealedBlockWithSenders,
-    TransactionSigned, TransactionSignedEcRecovered, B256,
+    Block, BlockHashOrNumber, BlockId, BlockNumber, BlockWithSenders, Receipt, SealedBlock,
+    SealedBlockWithSenders, TransactionSigned, TransactionSignedEcRecovered, B256,
 };
-use reth_storage_api::{BlockReader, StateProviderFactory, TransactionVariant};
+use reth_storage_api::{BlockIdReader, BlockReader, StateProviderFactory, TransactionVariant};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use revm::primitives::{BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, SpecId};
-use schnellru::{ByLength, Limiter};
+use schnellru::Limiter;
 use std::{
+    collections::VecDeque,
     future::Future,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     task::{ready, Context, Poll},
+    time::Duration,
 };
-use tokio::sync::{
-    mpsc::{unbounded_channel, UnboundedSender},
-    oneshot, Semaphore,
+use tokio::{
+    sync::{
+        mpsc::{unbounded_channel, UnboundedSender},
+        oneshot, watch, Semaphore,
+    },
+    task::JoinHandle,
 };
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, UnboundedReceiverStream};
+use tracing::warn;
 
 use super::{EthStateCacheConfig, MultiConsumerLruCache};
+use limiter::ByLengthAndSize;
+use multi_consumer::{CoalescedCounter, EvictionCounter};
+use remote::RemoteStateSource;
+use tags::BlockTagCache;
 
 pub mod config;
 pub mod db;
+#[cfg(feature = "debug")]
+pub mod dump;
+pub mod limiter;
 pub mod metrics;
 pub mod multi_consumer;
+pub mod remote;
+pub mod tags;
 
 /// The type that can send the response to a requested [`Block`]
 type BlockTransactionsResponseSender =
@@ -65,6 +83,20 @@ type EnvLruCache<L> =
 #[derive(Debug, Clone)]
 pub struct EthStateCache {
     to_service: UnboundedSender<CacheAction>,
+    /// The number of the tip of the last canonical chain the service ingested, if any, see
+    /// [`Self::is_warm`] and [`Self::wait_for_block`].
+    latest_block: watch::Receiver<Option<BlockNumber>>,
+    /// Blocks that block tags resolved to, see [`Self::block_hash_for_id`].
+    tags: BlockTagCache,
+    /// Evictions of the blocks, receipts and evm env caches, in that order.
+    evictions: [EvictionCounter; 3],
+    /// Coalesced requests of the blocks, receipts and evm env caches, in that order.
+    coalesced: [CoalescedCounter; 3],
+    /// Whether [`cache_new_blocks_task`] is paused, see [`Self::pause`].
+    paused: Arc<watch::Sender<bool>>,
+    /// The number of canonical state notifications that [`cache_new_blocks_task`] dropped, see
+    /// [`Self::notification_overflows`].
+    notification_overflows: Arc<AtomicU64>,
 }
 
 impl EthStateCache {
@@ -76,21 +108,55 @@ impl EthStateCache {
         max_blocks: u32,
         max_receipts: u32,
         max_envs: u32,
+        max_bytes: Option<usize>,
         max_concurrent_db_operations: usize,
+        remote_fallback: Option<RemoteStateSource>,
     ) -> (Self, EthStateCacheService<Provider, Tasks, EvmConfig>) {
         let (to_service, rx) = unbounded_channel();
+        let (latest_block_tx, latest_block) = watch::channel(None);
+        // split the size budget evenly between the caches with variable entry sizes
+        let max_cache_bytes = max_bytes.map(|max_bytes| max_bytes / 2);
         let service = EthStateCacheService {
             provider,
-            full_block_cache: BlockLruCache::new(max_blocks, "blocks"),
-            receipts_cache: ReceiptsLruCache::new(max_receipts, "receipts"),
-            evm_env_cache: EnvLruCache::new(max_envs, "evm_env"),
+            full_block_cache: BlockLruCache::with_limiter(
+                ByLengthAndSize::new(max_blocks, max_cache_bytes),
+                "blocks",
+            ),
+            receipts_cache: ReceiptsLruCache::with_limiter(
+                ByLengthAndSize::new(max_receipts, max_cache_bytes),
+                "receipts",
+            ),
+            evm_env_cache: EnvLruCache::with_limiter(
+                ByLengthAndSize::new(max_envs, None),
+                "evm_env",
+            ),
             action_tx: to_service.clone(),
             action_rx: UnboundedReceiverStream::new(rx),
             action_task_spawner,
             rate_limiter: Arc::new(Semaphore::new(max_concurrent_db_operations)),
             evm_config,
+            remote_fallback,
+            latest_block: latest_block_tx,
+        };
+        let evictions = [
+            service.full_block_cache.evictions(),
+            service.receipts_cache.evictions(),
+            service.evm_env_cache.evictions(),
+        ];
+        let coalesced = [
+            service.full_block_cache.coalesced(),
+            service.receipts_cache.coalesced(),
+            service.evm_env_cache.coalesced(),
+        ];
+        let cache = Self {
+            to_service,
+            latest_block,
+            tags: BlockTagCache::default(),
+            evictions,
+            coalesced,
+            paused: Arc::new(watch::Sender::new(false)),
+            notification_overflows: Default::default(),
         };
-        let cache = Self { to_service };
         (cache, service)
     }
 
@@ -125,8 +191,30 @@ impl EthStateCache {
         Tasks: TaskSpawner + Clone + 'static,
         EvmConfig: ConfigureEvm,
     {
-        let EthStateCacheConfig { max_blocks, max_receipts, max_envs, max_concurrent_db_requests } =
-            config;
+        Self::spawn_with_handle(provider, config, executor, evm_config).0
+    }
+
+    /// Same as [`Self::spawn_with`], but also returns the handle of the spawned service task,
+    /// e.g. to supervise it.
+    pub fn spawn_with_handle<Provider, Tasks, EvmConfig>(
+        provider: Provider,
+        config: EthStateCacheConfig,
+        executor: Tasks,
+        evm_config: EvmConfig,
+    ) -> (Self, JoinHandle<()>)
+    where
+        Provider: StateProviderFactory + BlockReader + EvmEnvProvider + Clone + Unpin + 'static,
+        Tasks: TaskSpawner + Clone + 'static,
+        EvmConfig: ConfigureEvm,
+    {
+        let EthStateCacheConfig {
+            max_blocks,
+            max_receipts,
+            max_envs,
+            max_concurrent_db_requests,
+            max_bytes,
+            remote_fallback,
+        } = config;
         let (this, service) = Self::create(
             provider,
             executor.clone(),
@@ -134,10 +222,42 @@ impl EthStateCache {
             max_blocks,
             max_receipts,
             max_envs,
+            max_bytes,
             max_concurrent_db_requests,
+            remote_fallback,
         );
-        executor.spawn_critical("eth state cache", Box::pin(service));
-        this
+        let handle = executor.spawn_critical("eth state cache", Box::pin(service));
+        (this, handle)
+    }
+
+    /// Configures how long the blocks that the `latest`, `safe` and `finalized` tags resolve to
+    /// are cached by [`Self::block_hash_for_id`].
+    ///
+    /// If `None`, tags are resolved with the provider on every call. See [`BlockTagCache`].
+    pub fn with_tag_cache_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.tags = BlockTagCache::new(ttl);
+        self
+    }
+
+    /// Returns the hash of the block with the given id, resolving block tags through the tag
+    /// cache, see [`Self::with_tag_cache_ttl`].
+    pub fn block_hash_for_id<Provider: BlockIdReader>(
+        &self,
+        provider: &Provider,
+        block_id: BlockId,
+    ) -> ProviderResult<Option<B256>> {
+        self.tags.block_hash_for_id(provider, block_id)
+    }
+
+    /// Returns the blocks that are currently cached and the estimated size of each cache, e.g. to
+    /// tell whether stale data is served from the cache or by the provider.
+    ///
+    /// Only available with the `debug` feature.
+    #[cfg(feature = "debug")]
+    pub async fn debug_dump(&self) -> ProviderResult<dump::CacheDump> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::DebugDump { response_tx });
+        rx.await.map_err(|_| ProviderError::CacheServiceUnavailable)
     }
 
     /// Requests the [`Block`] for the block hash
@@ -235,6 +355,26 @@ impl EthStateCache {
         rx.await.map_err(|_| ProviderError::CacheServiceUnavailable)?
     }
 
+    /// Requests all [Receipt]s of the given block, which is loaded and cached as a whole.
+    ///
+    /// If the block is given by number, it is resolved to the hash of the canonical block with
+    /// that number first. Concurrent requests for the same block share a single fetch.
+    ///
+    /// Returns `None` if the block was not found.
+    pub async fn get_block_receipts(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Arc<Vec<Receipt>>>> {
+        let block_number = match block {
+            BlockHashOrNumber::Hash(block_hash) => return self.get_receipts(block_hash).await,
+            BlockHashOrNumber::Number(block_number) => block_number,
+        };
+        let (response_tx, rx) = oneshot::channel();
+        let _ =
+            self.to_service.send(CacheAction::GetReceiptsByNumber { block_number, response_tx });
+        rx.await.map_err(|_| ProviderError::CacheServiceUnavailable)?
+    }
+
     /// Fetches both receipts and block for the given block hash.
     pub async fn get_block_and_receipts(
         &self,
@@ -260,6 +400,175 @@ impl EthStateCache {
         let _ = self.to_service.send(CacheAction::GetEnv { block_hash, response_tx });
         rx.await.map_err(|_| ProviderError::CacheServiceUnavailable)?
     }
+
+    /// Returns `true` once the cache is warm.
+    ///
+    /// The cache is warm once it ingested at least one block from the canonical state
+    /// notifications (see [`cache_new_blocks_task`]) since it was spawned. From then on the
+    /// blocks and receipts at the tip of the chain are served from memory, while before, e.g.
+    /// right after startup, every request has to fetch its data from disk.
+    ///
+    /// Note: the cache never becomes warm if no canonical state notifications are fed into it.
+    pub fn is_warm(&self) -> bool {
+        self.latest_block.borrow().is_some()
+    }
+
+    /// Returns the number of entries that each cache evicted to make room for new entries since
+    /// the cache was spawned.
+    ///
+    /// Frequent evictions in a category indicate that its cache is too small for the workload.
+    pub fn eviction_stats(&self) -> CacheEvictionStats {
+        let [blocks, receipts, evm_envs] = &self.evictions;
+        CacheEvictionStats {
+            blocks: blocks.get(),
+            receipts: receipts.get(),
+            evm_envs: evm_envs.get(),
+        }
+    }
+
+    /// Returns the number of requests to each cache that missed and waited for the in-flight fetch
+    /// of an earlier request for the same block, instead of fetching the data themselves, since
+    /// the cache was spawned.
+    ///
+    /// High counts indicate contention on hot blocks that the cache absorbs, e.g. many requests
+    /// for a new block right after it was announced.
+    pub fn coalescing_stats(&self) -> CacheCoalescingStats {
+        let [blocks, receipts, evm_envs] = &self.coalesced;
+        CacheCoalescingStats {
+            blocks: blocks.get(),
+            receipts: receipts.get(),
+            evm_envs: evm_envs.get(),
+        }
+    }
+
+    /// Returns the number of canonical state notifications that [`cache_new_blocks_task`] dropped
+    /// because it couldn't keep up, since the cache was spawned.
+    ///
+    /// Notifications are dropped if [`cache_new_blocks_task_with_lag`] lags behind the broadcast
+    /// channel it receives them from, or if more than [`MAX_PAUSED_NOTIFICATIONS`] arrive while the
+    /// cache is paused. The blocks of dropped notifications are not inserted into the cache,
+    /// and blocks that were reorged out by them may be served until they are evicted, so a
+    /// non-zero count explains a cache that misses recent blocks.
+    pub fn notification_overflows(&self) -> u64 {
+        self.notification_overflows.load(Ordering::Relaxed)
+    }
+
+    /// Records canonical state notifications that were dropped by [`cache_new_blocks_task`].
+    fn record_notification_overflows(&self, dropped: u64, reason: &str) {
+        let overflows = self.notification_overflows.fetch_add(dropped, Ordering::Relaxed) + dropped;
+        warn!(
+            target: "rpc::eth",
+            dropped,
+            overflows,
+            reason,
+            "eth state cache can't keep up with canonical state notifications, dropped notifications"
+        );
+    }
+
+    /// Returns a future that resolves once the cache ingested the canonical block with the given
+    /// number from the canonical state notifications (see [`cache_new_blocks_task`]), or
+    /// immediately if it already did.
+    ///
+    /// A block counts as ingested once the tip of an ingested canonical chain is at or above it.
+    /// The future also resolves if the cache service terminated.
+    pub fn wait_for_block(&self, block: BlockNumber) -> impl Future<Output = ()> + Send + 'static {
+        let mut latest_block = self.latest_block.clone();
+        async move {
+            let _ =
+                latest_block.wait_for(|latest| latest.is_some_and(|latest| latest >= block)).await;
+        }
+    }
+
+    /// Pauses the ingestion of canonical state notifications by [`cache_new_blocks_task`], e.g. to
+    /// keep the cache at a fixed point during database maintenance.
+    ///
+    /// While paused, the cache keeps serving the entries it holds and fetches missing data from
+    /// the provider as usual, but it falls behind the canonical chain:
+    ///  - new canonical blocks are not inserted, so they are fetched from the provider on demand
+    ///  - blocks that are reorged out stay cached, so they can still be served by hash
+    ///  - [`Self::is_warm`] and [`Self::wait_for_block`] don't advance
+    ///
+    /// The task keeps consuming notifications while paused and buffers the latest
+    /// [`MAX_PAUSED_NOTIFICATIONS`], which are applied on [`Self::resume`]. If more notifications
+    /// arrive, the oldest are dropped, and blocks that were reorged out by dropped notifications
+    /// may be served until they are evicted. Cached block tags are still invalidated by every
+    /// notification.
+    pub fn pause(&self) {
+        self.paused.send_replace(true);
+    }
+
+    /// Resumes the ingestion of canonical state notifications after [`Self::pause`], applying the
+    /// notifications that were buffered while paused.
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    /// Returns `true` if the ingestion of canonical state notifications is paused, see
+    /// [`Self::pause`].
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// Removes the cached block, receipts and evm env of the given block, so that subsequent
+    /// requests fetch them from the provider again.
+    ///
+    /// If the block is given by number, it is resolved to the hash of the canonical block with
+    /// that number before the entries are removed.
+    ///
+    /// Note: this is best-effort and racy with concurrent inserts, e.g. a fetch for the block
+    /// that is already in flight can put the stale data back into the cache after it has been
+    /// invalidated.
+    pub fn invalidate(&self, block: BlockHashOrNumber) {
+        let _ = self.to_service.send(CacheAction::Invalidate { block });
+    }
+
+    /// Pins the block, receipts and evm env of the given block, so that they are never evicted
+    /// until the block is unpinned with [`Self::unpin`], e.g. for the genesis block or a block
+    /// that a long running subscription refers to.
+    ///
+    /// Pinning doesn't fetch the data, it is kept once it is requested or ingested. Pinned
+    /// entries are kept outside of the LRU caches, so they don't count towards the entry and size
+    /// limits of the [`EthStateCacheConfig`], but at most [`MAX_PINNED_BLOCKS`] blocks can be
+    /// pinned at once. Returns `false` if the block can't be pinned because the cap is reached.
+    ///
+    /// Pinned entries are still removed if the block is reorged out or invalidated with
+    /// [`Self::invalidate`], but the block stays pinned.
+    pub async fn pin(&self, block_hash: B256) -> ProviderResult<bool> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::Pin { block_hash, response_tx });
+        rx.await.map_err(|_| ProviderError::CacheServiceUnavailable)
+    }
+
+    /// Unpins the given block, see [`Self::pin`].
+    ///
+    /// Its cached entries are moved back into the LRU caches, where they may be evicted again.
+    pub fn unpin(&self, block_hash: B256) {
+        let _ = self.to_service.send(CacheAction::Unpin { block_hash });
+    }
+}
+
+/// Number of entries evicted from each cache of an [`EthStateCache`], see
+/// [`EthStateCache::eviction_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheEvictionStats {
+    /// Evictions of the blocks cache.
+    pub blocks: u64,
+    /// Evictions of the receipts cache.
+    pub receipts: u64,
+    /// Evictions of the evm env cache.
+    pub evm_envs: u64,
+}
+
+/// Number of coalesced requests to each cache of an [`EthStateCache`], see
+/// [`EthStateCache::coalescing_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheCoalescingStats {
+    /// Coalesced requests to the blocks cache.
+    pub blocks: u64,
+    /// Coalesced requests to the receipts cache.
+    pub receipts: u64,
+    /// Coalesced requests to the evm env cache.
+    pub evm_envs: u64,
 }
 
 /// A task than manages caches for data required by the `eth` rpc implementation.
@@ -283,9 +592,9 @@ pub(crate) struct EthStateCacheService<
     Provider,
     Tasks,
     EvmConfig,
-    LimitBlocks = ByLength,
-    LimitReceipts = ByLength,
-    LimitEnvs = ByLength,
+    LimitBlocks = ByLengthAndSize,
+    LimitReceipts = ByLengthAndSize,
+    LimitEnvs = ByLengthAndSize,
 > where
     LimitBlocks: Limiter<B256, BlockWithSenders>,
     LimitReceipts: Limiter<B256, Arc<Vec<Receipt>>>,
@@ -309,6 +618,11 @@ pub(crate) struct EthStateCacheService<
     rate_limiter: Arc<Semaphore>,
     /// The type that determines how to configure the EVM.
     evm_config: EvmConfig,
+    /// Fallback for historical data that is no longer retained by the local database.
+    remote_fallback: Option<RemoteStateSource>,
+    /// Shared with the [`EthStateCache`] frontends, updated with the number of the tip of every
+    /// ingested canonical chain.
+    latest_block: watch::Sender<Option<BlockNumber>>,
 }
 
 impl<Provider, Tasks, EvmConfig> EthStateCacheService<Provider, Tasks, EvmConfig>
@@ -391,11 +705,68 @@ where
         }
     }
 
+    fn on_invalidate(&mut self, block_hash: B256) {
+        self.full_block_cache.evict(&block_hash);
+        self.receipts_cache.evict(&block_hash);
+        self.evm_env_cache.evict(&block_hash);
+    }
+
+    fn on_pin(&mut self, block_hash: B256) -> bool {
+        // all caches pin the same blocks, so either all or none reach the cap
+        self.full_block_cache.pin(block_hash, MAX_PINNED_BLOCKS) &&
+            self.receipts_cache.pin(block_hash, MAX_PINNED_BLOCKS) &&
+            self.evm_env_cache.pin(block_hash, MAX_PINNED_BLOCKS)
+    }
+
+    fn on_unpin(&mut self, block_hash: B256) {
+        self.full_block_cache.unpin(&block_hash);
+        self.receipts_cache.unpin(&block_hash);
+        self.evm_env_cache.unpin(&block_hash);
+    }
+
     fn update_cached_metrics(&self) {
         self.full_block_cache.update_cached_metrics();
         self.receipts_cache.update_cached_metrics();
         self.evm_env_cache.update_cached_metrics();
     }
+
+    #[cfg(feature = "debug")]
+    fn debug_dump(&self) -> dump::CacheDump {
+        use dump::{CacheCategoryDump, CachedBlock};
+
+        dump::CacheDump {
+            blocks: CacheCategoryDump {
+                entries: self
+                    .full_block_cache
+                    .iter()
+                    .map(|(hash, block)| CachedBlock { hash: *hash, number: Some(block.number) })
+                    .collect(),
+                estimated_size: self.full_block_cache.limiter().used_bytes(),
+                queued: self.full_block_cache.queued_len(),
+            },
+            receipts: CacheCategoryDump {
+                entries: self
+                    .receipts_cache
+                    .iter()
+                    .map(|(hash, _)| CachedBlock { hash: *hash, number: None })
+                    .collect(),
+                estimated_size: self.receipts_cache.limiter().used_bytes(),
+                queued: self.receipts_cache.queued_len(),
+            },
+            evm_envs: CacheCategoryDump {
+                entries: self
+                    .evm_env_cache
+                    .iter()
+                    .map(|(hash, (_, block_env))| CachedBlock {
+                        hash: *hash,
+                        number: Some(block_env.number.saturating_to()),
+                    })
+                    .collect(),
+                estimated_size: self.evm_env_cache.limiter().used_bytes(),
+                queued: self.evm_env_cache.queued_len(),
+            },
+        }
+    }
 }
 
 impl<Provider, Tasks, EvmConfig> Future for EthStateCacheService<Provider, Tasks, EvmConfig>
@@ -427,15 +798,24 @@ where
                                 let provider = this.provider.clone();
                                 let action_tx = this.action_tx.clone();
                                 let rate_limiter = this.rate_limiter.clone();
+                                let remote_fallback = this.remote_fallback.clone();
                                 this.action_task_spawner.spawn_blocking(Box::pin(async move {
                                     // Acquire permit
                                     let _permit = rate_limiter.acquire().await;
                                     // Only look in the database to prevent situations where we
                                     // looking up the tree is blocking
-                                    let block_sender = provider.block_with_senders(
+                                    let mut block_sender = provider.block_with_senders(
                                         BlockHashOrNumber::Hash(block_hash),
                                         TransactionVariant::WithHash,
                                     );
+                                    if let Some(remote) = remote_fallback {
+                                        block_sender = remote.read_through(
+                                            &provider,
+                                            block_hash,
+                                            block_sender,
+                                            |source| source.block_with_senders(block_hash),
+                                        );
+                                    }
                                     let _ = action_tx.send(CacheAction::BlockWithSendersResult {
                                         block_hash,
                                         res: block_sender,
@@ -455,15 +835,24 @@ where
                                 let provider = this.provider.clone();
                                 let action_tx = this.action_tx.clone();
                                 let rate_limiter = this.rate_limiter.clone();
+                                let remote_fallback = this.remote_fallback.clone();
                                 this.action_task_spawner.spawn_blocking(Box::pin(async move {
                                     // Acquire permit
                                     let _permit = rate_limiter.acquire().await;
                                     // Only look in the database to prevent situations where we
                                     // looking up the tree is blocking
-                                    let res = provider.block_with_senders(
+                                    let mut res = provider.block_with_senders(
                                         BlockHashOrNumber::Hash(block_hash),
                                         TransactionVariant::WithHash,
                                     );
+                                    if let Some(remote) = remote_fallback {
+                                        res = remote.read_through(
+                                            &provider,
+                                            block_hash,
+                                            res,
+                                            |source| source.block_with_senders(block_hash),
+                                        );
+                                    }
                                     let _ = action_tx.send(CacheAction::BlockWithSendersResult {
                                         block_hash,
                                         res,
@@ -483,12 +872,21 @@ where
                                 let provider = this.provider.clone();
                                 let action_tx = this.action_tx.clone();
                                 let rate_limiter = this.rate_limiter.clone();
+                                let remote_fallback = this.remote_fallback.clone();
                                 this.action_task_spawner.spawn_blocking(Box::pin(async move {
                                     // Acquire permit
                                     let _permit = rate_limiter.acquire().await;
-                                    let res = provider
-                                        .receipts_by_block(block_hash.into())
-                                        .map(|maybe_receipts| maybe_receipts.map(Arc::new));
+                                    let mut res = provider.receipts_by_block(block_hash.into());
+                                    if let Some(remote) = remote_fallback {
+                                        res = remote.read_through(
+                                            &provider,
+                                            block_hash,
+                                            res,
+                                            |source| source.receipts(block_hash),
+                                        );
+                                    }
+                                    let res =
+                                        res.map(|maybe_receipts| maybe_receipts.map(Arc::new));
 
                                     let _ = action_tx
                                         .send(CacheAction::ReceiptsResult { block_hash, res });
@@ -561,6 +959,8 @@ where
                             }
                         }
                         CacheAction::CacheNewCanonicalChain { chain_change } => {
+                            let tip = chain_change.blocks.last().map(|block| block.number);
+
                             for block in chain_change.blocks {
                                 this.on_new_block(block.hash(), Ok(Some(block.unseal())));
                             }
@@ -573,6 +973,61 @@ where
                                     ))),
                                 );
                             }
+
+                            // only signal the tip once its receipts are cached as well
+                            if let Some(tip) = tip {
+                                this.latest_block.send_replace(Some(tip));
+                            }
+                        }
+                        CacheAction::GetReceiptsByNumber { block_number, response_tx } => {
+                            // resolve the canonical hash of the block first, the receipts are then
+                            // requested by hash so concurrent requests are coalesced
+                            let provider = this.provider.clone();
+                            let action_tx = this.action_tx.clone();
+                            this.action_task_spawner.spawn_blocking(Box::pin(async move {
+                                match provider.block_hash(block_number) {
+                                    Ok(Some(block_hash)) => {
+                                        let _ = action_tx.send(CacheAction::GetReceipts {
+                                            block_hash,
+                                            response_tx,
+                                        });
+                                    }
+                                    Ok(None) => {
+                                        let _ = response_tx.send(Ok(None));
+                                    }
+                                    Err(err) => {
+                                        let _ = response_tx.send(Err(err));
+                                    }
+                                }
+                            }));
+                        }
+                        CacheAction::Invalidate { block } => match block {
+                            BlockHashOrNumber::Hash(block_hash) => {
+                                this.on_invalidate(block_hash);
+                            }
+                            BlockHashOrNumber::Number(block_number) => {
+                                // resolve the canonical hash of the block first
+                                let provider = this.provider.clone();
+                                let action_tx = this.action_tx.clone();
+                                this.action_task_spawner.spawn_blocking(Box::pin(async move {
+                                    if let Ok(Some(block_hash)) = provider.block_hash(block_number)
+                                    {
+                                        let _ = action_tx.send(CacheAction::Invalidate {
+                                            block: block_hash.into(),
+                                        });
+                                    }
+                                }));
+                            }
+                        },
+                        CacheAction::Pin { block_hash, response_tx } => {
+                            let _ = response_tx.send(this.on_pin(block_hash));
+                        }
+                        CacheAction::Unpin { block_hash } => {
+                            this.on_unpin(block_hash);
+                        }
+                        #[cfg(feature = "debug")]
+                        CacheAction::DebugDump { response_tx } => {
+                            let _ = response_tx.send(this.debug_dump());
                         }
                         CacheAction::RemoveReorgedChain { chain_change } => {
                             for block in chain_change.blocks {
@@ -598,15 +1053,58 @@ where
 
 /// All message variants sent through the channel
 enum CacheAction {
-    GetBlockWithSenders { block_hash: B256, response_tx: BlockWithSendersResponseSender },
-    GetBlockTransactions { block_hash: B256, response_tx: BlockTransactionsResponseSender },
-    GetEnv { block_hash: B256, response_tx: EnvResponseSender },
-    GetReceipts { block_hash: B256, response_tx: ReceiptsResponseSender },
-    BlockWithSendersResult { block_hash: B256, res: ProviderResult<Option<BlockWithSenders>> },
-    ReceiptsResult { block_hash: B256, res: ProviderResult<Option<Arc<Vec<Receipt>>>> },
-    EnvResult { block_hash: B256, res: Box<ProviderResult<(CfgEnvWithHandlerCfg, BlockEnv)>> },
-    CacheNewCanonicalChain { chain_change: ChainChange },
-    RemoveReorgedChain { chain_change: ChainChange },
+    GetBlockWithSenders {
+        block_hash: B256,
+        response_tx: BlockWithSendersResponseSender,
+    },
+    GetBlockTransactions {
+        block_hash: B256,
+        response_tx: BlockTransactionsResponseSender,
+    },
+    GetEnv {
+        block_hash: B256,
+        response_tx: EnvResponseSender,
+    },
+    GetReceipts {
+        block_hash: B256,
+        response_tx: ReceiptsResponseSender,
+    },
+    GetReceiptsByNumber {
+        block_number: BlockNumber,
+        response_tx: ReceiptsResponseSender,
+    },
+    BlockWithSendersResult {
+        block_hash: B256,
+        res: ProviderResult<Option<BlockWithSenders>>,
+    },
+    ReceiptsResult {
+        block_hash: B256,
+        res: ProviderResult<Option<Arc<Vec<Receipt>>>>,
+    },
+    EnvResult {
+        block_hash: B256,
+        res: Box<ProviderResult<(CfgEnvWithHandlerCfg, BlockEnv)>>,
+    },
+    CacheNewCanonicalChain {
+        chain_change: ChainChange,
+    },
+    RemoveReorgedChain {
+        chain_change: ChainChange,
+    },
+    Invalidate {
+        block: BlockHashOrNumber,
+    },
+    Pin {
+        block_hash: B256,
+        response_tx: oneshot::Sender<bool>,
+    },
+    Unpin {
+        block_hash: B256,
+    },
+    #[cfg(feature = "debug")]
+    DebugDump {
+        response_tx: oneshot::Sender<dump::CacheDump>,
+    },
 }
 
 struct BlockReceipts {
@@ -634,25 +1132,199 @@ impl ChainChange {
     }
 }
 
+/// The maximum number of blocks that can be pinned in an [`EthStateCache`] at once, see
+/// [`EthStateCache::pin`].
+pub const MAX_PINNED_BLOCKS: usize = 64;
+
+/// The maximum number of canonical state notifications that [`cache_new_blocks_task`] buffers
+/// while the cache is paused, see [`EthStateCache::pause`].
+pub const MAX_PAUSED_NOTIFICATIONS: usize = 64;
+
 /// Awaits for new chain events and directly inserts them into the cache so they're available
 /// immediately before they need to be fetched from disk.
 ///
-/// Reorged blocks are removed from the cache.
-pub async fn cache_new_blocks_task<St>(eth_state_cache: EthStateCache, mut events: St)
+/// Reorged blocks are removed from the cache, and the cached block tags are invalidated on every
+/// event. While the cache is paused, events are buffered and applied on resume, see
+/// [`EthStateCache::pause`].
+///
+/// Events that don't fit into the buffer while paused are counted, see
+/// [`EthStateCache::notification_overflows`]. Events that the given stream skipped can't be
+/// counted, use [`cache_new_blocks_task_with_lag`] to also count the events that are dropped
+/// because the task lagged behind the broadcast channel.
+pub async fn cache_new_blocks_task<St>(eth_state_cache: EthStateCache, events: St)
 where
     St: Stream<Item = CanonStateNotification> + Unpin + 'static,
 {
-    while let Some(event) = events.next().await {
+    cache_new_blocks_task_with_lag(eth_state_cache, events.map(Ok)).await
+}
+
+/// Like [`cache_new_blocks_task`], but receives the events from a broadcast channel, e.g. the
+/// stream of [`CanonStateSubscriptions::subscribe_to_canonical_state`].
+///
+/// Events that are dropped because the task lagged behind the channel, or that don't fit into the
+/// buffer while paused, are counted, see [`EthStateCache::notification_overflows`].
+///
+/// [`CanonStateSubscriptions::subscribe_to_canonical_state`]: reth_chain_state::CanonStateSubscriptions::subscribe_to_canonical_state
+pub async fn cache_new_blocks_task_with_lag<St>(eth_state_cache: EthStateCache, mut events: St)
+where
+    St: Stream<Item = Result<CanonStateNotification, BroadcastStreamRecvError>> + Unpin + 'static,
+{
+    let mut paused = eth_state_cache.paused.subscribe();
+    let mut buffered = VecDeque::new();
+
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                // the canonical chain changed, so previously resolved tags may be outdated
+                eth_state_cache.tags.invalidate();
+
+                let event = match event {
+                    Some(Ok(event)) => event,
+                    Some(Err(BroadcastStreamRecvError::Lagged(skipped))) => {
+                        eth_state_cache.record_notification_overflows(skipped, "lagged behind");
+                        continue
+                    }
+                    // the stream ended, we are done
+                    None => break,
+                };
+
+                if *paused.borrow() {
+                    if buffered.len() == MAX_PAUSED_NOTIFICATIONS {
+                        buffered.pop_front();
+                        eth_state_cache.record_notification_overflows(1, "paused buffer full");
+                    }
+                    buffered.push_back(event);
+                    continue
+                }
+
+                eth_state_cache.apply_canonical_notification(event);
+            }
+            Ok(()) = paused.changed() => {
+                if !*paused.borrow_and_update() {
+                    for event in buffered.drain(..) {
+                        eth_state_cache.apply_canonical_notification(event);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl EthStateCache {
+    /// Removes the reorged blocks of the notification from the cache and inserts the new
+    /// canonical blocks.
+    fn apply_canonical_notification(&self, event: CanonStateNotification) {
         if let Some(reverted) = event.reverted() {
             let chain_change = ChainChange::new(reverted);
 
-            let _ =
-                eth_state_cache.to_service.send(CacheAction::RemoveReorgedChain { chain_change });
+            let _ = self.to_service.send(CacheAction::RemoveReorgedChain { chain_change });
         }
 
         let chain_change = ChainChange::new(event.committed());
 
-        let _ =
-            eth_state_cache.to_service.send(CacheAction::CacheNewCanonicalChain { chain_change });
+        let _ = self.to_service.send(CacheAction::CacheNewCanonicalChain { chain_change });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_execution_types::ExecutionOutcome;
+    use reth_primitives::{Header, Receipts};
+    use reth_provider::test_utils::NoopProvider;
+    use tokio::sync::broadcast;
+    use tokio_stream::wrappers::BroadcastStream;
+
+    fn spawn_cache() -> EthStateCache {
+        EthStateCache::spawn(NoopProvider::default(), Default::default(), EthEvmConfig::default())
+    }
+
+    /// Returns a notification that commits an empty block with the given number.
+    fn commit(number: BlockNumber) -> CanonStateNotification {
+        let header = Header { number, ..Default::default() }.seal_slow();
+        let block =
+            SealedBlockWithSenders::new(SealedBlock { header, ..Default::default() }, Vec::new())
+                .unwrap();
+        let outcome = ExecutionOutcome::new(
+            Default::default(),
+            Receipts { receipt_vec: vec![Vec::new()] },
+            number,
+            Vec::new(),
+        );
+        CanonStateNotification::Commit { new: Arc::new(Chain::from_block(block, outcome, None)) }
+    }
+
+    #[tokio::test]
+    async fn applies_notifications_of_plain_streams() {
+        let cache = spawn_cache();
+        let events = futures::stream::iter([commit(1), commit(2)]);
+        tokio::spawn(cache_new_blocks_task(cache.clone(), events));
+
+        tokio::time::timeout(Duration::from_secs(5), cache.wait_for_block(2)).await.unwrap();
+        assert_eq!(cache.notification_overflows(), 0);
+    }
+
+    #[tokio::test]
+    async fn counts_lagged_notifications() {
+        let cache = spawn_cache();
+        let (tx, rx) = broadcast::channel(1);
+        for number in 1..=3 {
+            tx.send(commit(number)).unwrap();
+        }
+        tokio::spawn(cache_new_blocks_task_with_lag(cache.clone(), BroadcastStream::new(rx)));
+
+        // only the last notification is still in the channel, the first two were dropped
+        tokio::time::timeout(Duration::from_secs(5), cache.wait_for_block(3)).await.unwrap();
+        assert_eq!(cache.notification_overflows(), 2);
+    }
+
+    #[tokio::test]
+    async fn applies_buffered_notifications_on_resume() {
+        let cache = spawn_cache();
+        let (tx, rx) = broadcast::channel(16);
+        tokio::spawn(cache_new_blocks_task_with_lag(cache.clone(), BroadcastStream::new(rx)));
+
+        cache.pause();
+        assert!(cache.is_paused());
+        tx.send(commit(1)).unwrap();
+
+        // the notification is buffered, not applied
+        let waited =
+            tokio::time::timeout(Duration::from_millis(100), cache.wait_for_block(1)).await;
+        assert!(waited.is_err());
+        assert!(!cache.is_warm());
+
+        cache.resume();
+        tokio::time::timeout(Duration::from_secs(5), cache.wait_for_block(1)).await.unwrap();
+        assert!(cache.is_warm());
+        assert_eq!(cache.notification_overflows(), 0);
+    }
+
+    #[tokio::test]
+    async fn drops_oldest_notifications_while_paused() {
+        let cache = spawn_cache();
+        let (tx, rx) = broadcast::channel(2 * MAX_PAUSED_NOTIFICATIONS);
+        cache.pause();
+        tokio::spawn(cache_new_blocks_task_with_lag(cache.clone(), BroadcastStream::new(rx)));
+
+        let last = MAX_PAUSED_NOTIFICATIONS as u64 + 2;
+        for number in 1..=last {
+            tx.send(commit(number)).unwrap();
+        }
+
+        // wait until the task buffered all notifications
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while cache.notification_overflows() < 2 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert!(!cache.is_warm());
+
+        cache.resume();
+        tokio::time::timeout(Duration::from_secs(5), cache.wait_for_block(last)).await.unwrap();
+        assert_eq!(cache.notification_overflows(), 2);
     }
 }
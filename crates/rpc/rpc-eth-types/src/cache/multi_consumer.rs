@@ -5,6 +5,10 @@ use std::{
     collections::{hash_map::Entry, HashMap},
     fmt::{self, Debug, Formatter},
     hash::Hash,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 
 use schnellru::{ByLength, Limiter, LruMap};
@@ -23,6 +27,13 @@ where
     queued: HashMap<K, Vec<S>>,
     /// Cache metrics
     metrics: CacheMetrics,
+    /// Number of entries evicted to make room for new entries.
+    evictions: EvictionCounter,
+    /// Number of consumers that were queued behind the first consumer of a key.
+    coalesced: CoalescedCounter,
+    /// Pinned keys and their values, which are kept outside of the LRU cache so that they are
+    /// never evicted, see [`Self::pin`]. `None` until a value is inserted for the key.
+    pinned: HashMap<K, Option<V>>,
 }
 
 impl<K, V, L, S> Debug for MultiConsumerLruCache<K, V, L, S>
@@ -35,6 +46,7 @@ where
             .field("cache_length", &self.cache.len())
             .field("cache_memory_usage", &self.cache.memory_usage())
             .field("queued_length", &self.queued.len())
+            .field("pinned_length", &self.pinned.len())
             .finish()
     }
 }
@@ -52,6 +64,8 @@ where
         match self.queued.entry(key) {
             Entry::Occupied(mut entry) => {
                 entry.get_mut().push(sender);
+                self.metrics.coalesced_total.increment(1);
+                self.coalesced.0.fetch_add(1, Ordering::Relaxed);
                 false
             }
             Entry::Vacant(entry) => {
@@ -62,16 +76,33 @@ where
     }
 
     /// Remove consumers for a given key, this will also remove the key from the cache.
+    ///
+    /// A pinned key stays pinned, only its value is removed.
     pub fn remove(&mut self, key: &K) -> Option<Vec<S>> {
         let _ = self.cache.remove(key);
+        if let Some(pinned) = self.pinned.get_mut(key) {
+            *pinned = None;
+        }
         self.queued
             .remove(key)
             .inspect(|removed| self.metrics.queued_consumers_count.decrement(removed.len() as f64))
     }
 
+    /// Removes the cached value for a given key, keeping any queued consumers.
+    ///
+    /// A pinned key stays pinned, only its value is removed.
+    pub fn evict(&mut self, key: &K) -> Option<V> {
+        self.cache.remove(key).or_else(|| self.pinned.get_mut(key).and_then(Option::take))
+    }
+
     /// Returns a reference to the value for a given key and promotes that element to be the most
     /// recently used.
     pub fn get(&mut self, key: &K) -> Option<&mut V> {
+        if let Some(Some(value)) = self.pinned.get_mut(key) {
+            self.metrics.hits_total.increment(1);
+            return Some(value)
+        }
+
         let entry = self.cache.get(key);
         if entry.is_some() {
             self.metrics.hits_total.increment(1);
@@ -84,19 +115,116 @@ where
     /// Inserts a new element into the map.
     ///
     /// Can fail if the element is rejected by the limiter or if we fail to grow an empty map.
+    /// Values of pinned keys are kept outside of the LRU cache and are never rejected.
     ///
     /// See [`Schnellru::insert`](LruMap::insert) for more info.
     pub fn insert<'a>(&mut self, key: L::KeyToInsert<'a>, value: V) -> bool
     where
         L::KeyToInsert<'a>: Hash + PartialEq<K>,
     {
-        self.cache.insert(key, value)
+        if let Some((_, pinned)) =
+            self.pinned.iter_mut().find(|(pinned_key, _)| key == **pinned_key)
+        {
+            *pinned = Some(value);
+            return true
+        }
+
+        let replaces = self.cache.peek(&key).is_some();
+        let len = self.cache.len();
+
+        let inserted = self.cache.insert(key, value);
+
+        // a rejected value also removes the value it would have replaced, which isn't an eviction
+        let expected_len = match (inserted, replaces) {
+            (true, false) => len + 1,
+            (false, true) => len - 1,
+            _ => len,
+        };
+        let evicted = expected_len.saturating_sub(self.cache.len()) as u64;
+        if evicted > 0 {
+            self.metrics.evictions_total.increment(evicted);
+            self.evictions.0.fetch_add(evicted, Ordering::Relaxed);
+        }
+
+        inserted
     }
 
     /// Update metrics for the inner cache.
     #[inline]
     pub fn update_cached_metrics(&self) {
-        self.metrics.cached_count.set(self.cache.len() as f64);
+        let pinned = self.pinned.values().filter(|value| value.is_some()).count();
+        self.metrics.cached_count.set((self.cache.len() + pinned) as f64);
+    }
+
+    /// Returns an iterator over the cached entries, the pinned entries first and then the other
+    /// entries from the most to the least recently used.
+    ///
+    /// This does not promote the entries.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.pinned
+            .iter()
+            .filter_map(|(key, value)| value.as_ref().map(|value| (key, value)))
+            .chain(self.cache.iter())
+    }
+
+    /// Pins the given key, so that its value is never evicted until the key is unpinned, see
+    /// [`Self::unpin`].
+    ///
+    /// A cached value is moved out of the LRU cache, and values that are inserted for the key
+    /// while it is pinned are kept outside of it as well, so pinned values don't count towards
+    /// the limits of the limiter. Returns `false` if the key can't be pinned because `max_pinned`
+    /// keys are pinned already.
+    pub fn pin(&mut self, key: K, max_pinned: usize) -> bool {
+        if self.pinned.contains_key(&key) {
+            return true
+        }
+        if self.pinned.len() >= max_pinned {
+            return false
+        }
+
+        let value = self.cache.remove(&key);
+        self.pinned.insert(key, value);
+        true
+    }
+
+    /// Unpins the given key, moving its value back into the LRU cache as the most recently used
+    /// entry, where it may be evicted again.
+    pub fn unpin(&mut self, key: &K)
+    where
+        L: for<'a> Limiter<K, V, KeyToInsert<'a> = K>,
+    {
+        if let Some((key, Some(value))) = self.pinned.remove_entry(key) {
+            self.insert(key, value);
+        }
+    }
+
+    /// Returns the number of pinned keys.
+    pub fn pinned_len(&self) -> usize {
+        self.pinned.len()
+    }
+
+    /// Returns the limiter of the cache.
+    pub fn limiter(&self) -> &L {
+        self.cache.limiter()
+    }
+
+    /// Returns the number of keys with queued consumers.
+    pub fn queued_len(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// Returns a handle to the number of entries that were evicted to make room for new entries.
+    ///
+    /// Entries that are removed explicitly, e.g. with [`Self::evict`], are not counted.
+    pub fn evictions(&self) -> EvictionCounter {
+        self.evictions.clone()
+    }
+
+    /// Returns a handle to the number of consumers that were queued for a key that already had a
+    /// queued consumer, i.e. that wait for the fetch of the first consumer instead of issuing
+    /// their own.
+    pub fn coalesced(&self) -> CoalescedCounter {
+        self.coalesced.clone()
     }
 }
 
@@ -106,10 +234,125 @@ where
 {
     /// Creates a new empty map with a given `max_len` and metric label.
     pub fn new(max_len: u32, cache_id: &str) -> Self {
+        Self::with_limiter(ByLength::new(max_len), cache_id)
+    }
+}
+
+impl<K, V, L, S> MultiConsumerLruCache<K, V, L, S>
+where
+    K: Hash + Eq,
+    L: Limiter<K, V>,
+{
+    /// Creates a new empty map with the given limiter and metric label.
+    pub fn with_limiter(limiter: L, cache_id: &str) -> Self {
         Self {
-            cache: LruMap::new(ByLength::new(max_len)),
+            cache: LruMap::new(limiter),
             queued: Default::default(),
             metrics: CacheMetrics::new_with_labels(&[("cache", cache_id.to_string())]),
+            evictions: Default::default(),
+            coalesced: Default::default(),
+            pinned: Default::default(),
         }
     }
 }
+
+/// Shareable count of the entries evicted from a [`MultiConsumerLruCache`], see
+/// [`MultiConsumerLruCache::evictions`].
+#[derive(Debug, Clone, Default)]
+pub struct EvictionCounter(Arc<AtomicU64>);
+
+impl EvictionCounter {
+    /// Returns the number of evicted entries.
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Shareable count of the coalesced consumers of a [`MultiConsumerLruCache`], see
+/// [`MultiConsumerLruCache::coalesced`].
+#[derive(Debug, Clone, Default)]
+pub struct CoalescedCounter(Arc<AtomicU64>);
+
+impl CoalescedCounter {
+    /// Returns the number of coalesced consumers.
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_evictions() {
+        let mut cache = MultiConsumerLruCache::<u64, u64, ByLength, ()>::new(2, "test");
+        let evictions = cache.evictions();
+
+        assert!(cache.insert(1, 1));
+        assert!(cache.insert(2, 2));
+        assert_eq!(evictions.get(), 0);
+
+        // replacing a cached value evicts nothing
+        assert!(cache.insert(2, 3));
+        assert_eq!(evictions.get(), 0);
+
+        // explicit removals are not evictions
+        assert_eq!(cache.evict(&1), Some(1));
+        assert!(cache.insert(3, 3));
+        assert_eq!(evictions.get(), 0);
+
+        assert!(cache.insert(4, 4));
+        assert_eq!(evictions.get(), 1);
+    }
+
+    #[test]
+    fn pinned_values_are_not_evicted() {
+        let mut cache = MultiConsumerLruCache::<u64, u64, ByLength, ()>::new(2, "test");
+
+        // pinning a cached value moves it out of the LRU cache
+        assert!(cache.insert(1, 1));
+        assert!(cache.pin(1, 2));
+        assert!(cache.pin(2, 2));
+        assert!(!cache.pin(3, 2), "pins are capped");
+        assert!(cache.insert(2, 2));
+
+        for key in 3..10 {
+            assert!(cache.insert(key, key));
+        }
+        assert_eq!(cache.get(&1), Some(&mut 1));
+        assert_eq!(cache.get(&2), Some(&mut 2));
+        assert_eq!(cache.evictions().get(), 5);
+
+        // invalidating a pinned key keeps the pin
+        assert_eq!(cache.evict(&2), Some(2));
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.pinned_len(), 2);
+
+        // an unpinned value is the most recently used entry of the LRU cache
+        cache.unpin(&1);
+        assert!(cache.insert(10, 10));
+        assert_eq!(cache.get(&1), Some(&mut 1));
+        assert_eq!(cache.get(&8), None);
+        assert!(cache.pin(3, 2));
+    }
+
+    #[test]
+    fn counts_coalesced_consumers() {
+        let mut cache = MultiConsumerLruCache::<u64, u64, ByLength, ()>::new(2, "test");
+        let coalesced = cache.coalesced();
+
+        assert!(cache.queue(1, ()));
+        assert!(cache.queue(2, ()));
+        assert_eq!(coalesced.get(), 0);
+
+        assert!(!cache.queue(1, ()));
+        assert!(!cache.queue(1, ()));
+        assert_eq!(coalesced.get(), 2);
+
+        // once the consumers are served, the next consumer fetches again
+        assert_eq!(cache.remove(&1).map(|consumers| consumers.len()), Some(3));
+        assert!(cache.queue(1, ()));
+        assert_eq!(coalesced.get(), 2);
+    }
+}
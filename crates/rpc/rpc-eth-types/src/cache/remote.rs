@@ -0,0 +1,78 @@
+//! Read-through fallback for historical data that is no longer available locally.
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use reth_errors::ProviderResult;
+use reth_primitives::{BlockWithSenders, Receipt, B256};
+use reth_storage_api::BlockNumReader;
+
+/// A source for historical block data, e.g. an archive node reachable over RPC.
+///
+/// This is consulted by the [`EthStateCache`](super::EthStateCache) if the local database knows
+/// the requested block but no longer retains its data, for example because it was pruned.
+///
+/// Note: calls are made from a blocking task, so implementations are free to do blocking IO.
+pub trait RemoteBlockSource: Send + Sync + 'static {
+    /// Fetches the [`BlockWithSenders`] for the given block hash.
+    ///
+    /// Returns `None` if the remote does not know the block.
+    fn block_with_senders(&self, block_hash: B256) -> ProviderResult<Option<BlockWithSenders>>;
+
+    /// Fetches all receipts of the block with the given hash.
+    ///
+    /// Returns `None` if the remote does not know the block.
+    fn receipts(&self, block_hash: B256) -> ProviderResult<Option<Vec<Receipt>>>;
+}
+
+/// Shareable handle to a [`RemoteBlockSource`] that can be configured on the
+/// [`EthStateCacheConfig`](super::EthStateCacheConfig).
+#[derive(Clone)]
+pub struct RemoteStateSource {
+    inner: Arc<dyn RemoteBlockSource>,
+}
+
+impl RemoteStateSource {
+    /// Creates a new handle for the given [`RemoteBlockSource`].
+    pub fn new<S: RemoteBlockSource>(source: S) -> Self {
+        Self { inner: Arc::new(source) }
+    }
+
+    /// Returns the locally fetched value, or reads it from the remote source if the block is
+    /// known to the local database but its data is missing.
+    ///
+    /// Local hits and local errors are returned as is.
+    pub(crate) fn read_through<Provider, T>(
+        &self,
+        provider: &Provider,
+        block_hash: B256,
+        local: ProviderResult<Option<T>>,
+        fetch: impl FnOnce(&dyn RemoteBlockSource) -> ProviderResult<Option<T>>,
+    ) -> ProviderResult<Option<T>>
+    where
+        Provider: BlockNumReader,
+    {
+        match local {
+            // only blocks that are part of the local chain but whose data is no longer retained
+            // are fetched remotely
+            Ok(None) if provider.block_number(block_hash)?.is_some() => fetch(&*self.inner),
+            local => local,
+        }
+    }
+}
+
+impl Debug for RemoteStateSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteStateSource").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for RemoteStateSource {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for RemoteStateSource {}
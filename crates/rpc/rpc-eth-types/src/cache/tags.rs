@@ -0,0 +1,152 @@
+//! Short-lived cache for the blocks that block tags resolve to.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+use reth_errors::ProviderResult;
+use reth_primitives::{BlockId, BlockNumberOrTag, B256};
+use reth_storage_api::BlockIdReader;
+
+/// Caches the block hashes that the `latest`, `safe` and `finalized` tags resolve to, for a
+/// configured duration.
+///
+/// All entries are invalidated by [`cache_new_blocks_task`](super::cache_new_blocks_task) when the
+/// canonical chain changes. The safe and finalized blocks can also change without a canonical
+/// state notification, so entries for these tags may be stale for up to the configured duration.
+///
+/// The `pending` tag is never cached. If no duration is configured, nothing is cached.
+#[derive(Debug, Clone, Default)]
+pub struct BlockTagCache {
+    ttl: Option<Duration>,
+    entries: Arc<Mutex<TagEntries>>,
+}
+
+impl BlockTagCache {
+    /// Creates a new cache that keeps resolved tags for the given duration.
+    pub fn new(ttl: Option<Duration>) -> Self {
+        Self { ttl, entries: Default::default() }
+    }
+
+    /// Returns the hash of the block with the given id.
+    ///
+    /// Cacheable tags are served from the cache if their entry is not older than the configured
+    /// duration, and are resolved with the provider and cached otherwise.
+    pub fn block_hash_for_id<Provider: BlockIdReader>(
+        &self,
+        provider: &Provider,
+        block_id: BlockId,
+    ) -> ProviderResult<Option<B256>> {
+        let (Some(ttl), Some(index)) = (self.ttl, Self::index(&block_id)) else {
+            return provider.block_hash_for_id(block_id)
+        };
+
+        let generation = {
+            let entries = self.entries.lock();
+            if let Some(hash) = entries.get(index, ttl) {
+                return Ok(Some(hash))
+            }
+            entries.generation
+        };
+
+        let resolved_at = Instant::now();
+        let hash = provider.block_hash_for_id(block_id)?;
+        if let Some(hash) = hash {
+            self.entries.lock().insert(index, generation, hash, resolved_at);
+        }
+        Ok(hash)
+    }
+
+    /// Removes all cached entries.
+    ///
+    /// Tags that are being resolved concurrently are not cached once they are resolved, since they
+    /// may have been resolved before the invalidation.
+    pub fn invalidate(&self) {
+        let mut entries = self.entries.lock();
+        entries.hashes = Default::default();
+        entries.generation += 1;
+    }
+
+    /// Returns the slot of the given block id, if it is a cacheable tag.
+    const fn index(block_id: &BlockId) -> Option<usize> {
+        match block_id {
+            BlockId::Number(BlockNumberOrTag::Latest) => Some(0),
+            BlockId::Number(BlockNumberOrTag::Safe) => Some(1),
+            BlockId::Number(BlockNumberOrTag::Finalized) => Some(2),
+            _ => None,
+        }
+    }
+}
+
+/// The entries of a [`BlockTagCache`].
+#[derive(Debug, Default)]
+struct TagEntries {
+    /// Resolved hashes of the `latest`, `safe` and `finalized` tags, in that order.
+    hashes: [Option<(B256, Instant)>; 3],
+    /// Incremented on every invalidation.
+    generation: u64,
+}
+
+impl TagEntries {
+    /// Returns the hash in the given slot, if it is not older than the given duration.
+    fn get(&self, index: usize, ttl: Duration) -> Option<B256> {
+        self.hashes[index]
+            .filter(|(_, resolved_at)| resolved_at.elapsed() < ttl)
+            .map(|(hash, _)| hash)
+    }
+
+    /// Caches the hash in the given slot, unless the entries were invalidated since the given
+    /// generation.
+    fn insert(&mut self, index: usize, generation: u64, hash: B256, resolved_at: Instant) {
+        if self.generation == generation {
+            self.hashes[index] = Some((hash, resolved_at));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn serves_cached_entries() {
+        let cache = BlockTagCache::new(Some(TTL));
+        let hash = B256::with_last_byte(1);
+
+        let mut entries = cache.entries.lock();
+        let generation = entries.generation;
+        entries.insert(0, generation, hash, Instant::now());
+        assert_eq!(entries.get(0, TTL), Some(hash));
+        assert_eq!(entries.get(1, TTL), None);
+        assert_eq!(entries.get(0, Duration::ZERO), None);
+    }
+
+    #[test]
+    fn invalidate_removes_entries() {
+        let cache = BlockTagCache::new(Some(TTL));
+        let generation = cache.entries.lock().generation;
+        cache.entries.lock().insert(0, generation, B256::with_last_byte(1), Instant::now());
+
+        cache.invalidate();
+        assert_eq!(cache.entries.lock().get(0, TTL), None);
+    }
+
+    #[test]
+    fn skips_entries_resolved_before_invalidation() {
+        let cache = BlockTagCache::new(Some(TTL));
+        // the tag is resolved concurrently with an invalidation
+        let generation = cache.entries.lock().generation;
+        cache.invalidate();
+        cache.entries.lock().insert(0, generation, B256::with_last_byte(1), Instant::now());
+        assert_eq!(cache.entries.lock().get(0, TTL), None);
+
+        // tags resolved after the invalidation are cached again
+        let generation = cache.entries.lock().generation;
+        cache.entries.lock().insert(0, generation, B256::with_last_byte(2), Instant::now());
+        assert_eq!(cache.entries.lock().get(0, TTL), Some(B256::with_last_byte(2)));
+    }
+}
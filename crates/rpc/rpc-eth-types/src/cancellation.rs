@@ -0,0 +1,313 @@
+//! Cooperative cancellation of blocking work whose request was dropped, e.g. because the client
+//! disconnected.
+
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use revm::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, InstructionResult,
+        Interpreter,
+    },
+    primitives::{Address, Log, U256},
+    Database, EvmContext, GetInspector, Inspector,
+};
+
+use super::{EthApiError, EthResult};
+
+thread_local! {
+    /// The cancellation of the blocking work that runs on the current thread.
+    static CURRENT: RefCell<Option<Cancellation>> = const { RefCell::new(None) };
+}
+
+/// Shareable flag that is set once the request that spawned blocking work is dropped.
+///
+/// The work checks the flag cooperatively, i.e. between transactions and every
+/// [`CancellableInspector::CHECK_INTERVAL`] steps of inspected executions, and aborts with
+/// [`EthApiError::Cancelled`] once it is set.
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Cancellation {
+    /// Returns the cancellation of the blocking work that runs on the current thread, see
+    /// [`Cancellation::enter`].
+    pub fn current() -> Option<Self> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Runs the closure with this as the [`Cancellation::current`] cancellation of the thread.
+    pub fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        /// Restores the previous cancellation of the thread, even if the closure panics.
+        struct Restore(Option<Cancellation>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.clone()))));
+        f()
+    }
+
+    /// Marks the work as cancelled.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed)
+    }
+
+    /// Returns `true` if the work was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Returns [`EthApiError::Cancelled`] if the work was cancelled.
+    pub fn ensure_not_cancelled(&self) -> EthResult<()> {
+        if self.is_cancelled() {
+            return Err(EthApiError::Cancelled)
+        }
+        Ok(())
+    }
+
+    /// Returns [`EthApiError::Cancelled`] if the work that runs on the current thread was
+    /// cancelled.
+    pub fn ensure_current_not_cancelled() -> EthResult<()> {
+        Self::current().map_or(Ok(()), |cancellation| cancellation.ensure_not_cancelled())
+    }
+
+    /// Returns a guard that cancels the work once dropped, e.g. to be held by the future of the
+    /// request.
+    pub fn cancel_on_drop(&self) -> CancelOnDrop {
+        CancelOnDrop { cancellation: self.clone() }
+    }
+}
+
+/// Cancels the work once dropped, see [`Cancellation::cancel_on_drop`].
+///
+/// Cancelling work that already finished has no effect.
+#[derive(Debug)]
+pub struct CancelOnDrop {
+    cancellation: Cancellation,
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.cancellation.cancel()
+    }
+}
+
+/// An [`Inspector`] that wraps another inspector and halts execution once the work was cancelled.
+///
+/// The flag is checked every [`CancellableInspector::CHECK_INTERVAL`] steps. Once cancelled, every
+/// frame is halted, so the result of the execution must be discarded if
+/// [`Cancellation::is_cancelled`] returns `true`.
+#[derive(Debug)]
+pub struct CancellableInspector<I> {
+    inner: I,
+    cancellation: Cancellation,
+    steps: u64,
+    cancelled: bool,
+}
+
+impl<I> CancellableInspector<I> {
+    /// Number of steps between two checks of the cancellation.
+    pub const CHECK_INTERVAL: u64 = 1024;
+
+    /// Wraps the given inspector.
+    pub const fn new(inner: I, cancellation: Cancellation) -> Self {
+        Self { inner, cancellation, steps: 0, cancelled: false }
+    }
+}
+
+impl<DB, I> Inspector<DB> for CancellableInspector<I>
+where
+    DB: Database,
+    I: GetInspector<DB>,
+{
+    #[inline]
+    fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.inner.get_inspector().initialize_interp(interp, context)
+    }
+
+    #[inline]
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.inner.get_inspector().step(interp, context);
+        if !self.cancelled {
+            self.steps += 1;
+            if self.steps % Self::CHECK_INTERVAL != 0 || !self.cancellation.is_cancelled() {
+                return
+            }
+            self.cancelled = true;
+        }
+        interp.instruction_result = InstructionResult::OutOfGas;
+    }
+
+    #[inline]
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.inner.get_inspector().step_end(interp, context)
+    }
+
+    #[inline]
+    fn log(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>, log: &Log) {
+        self.inner.get_inspector().log(interp, context, log)
+    }
+
+    #[inline]
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.inner.get_inspector().call(context, inputs)
+    }
+
+    #[inline]
+    fn call_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.inner.get_inspector().call_end(context, inputs, outcome)
+    }
+
+    #[inline]
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.inner.get_inspector().create(context, inputs)
+    }
+
+    #[inline]
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.inner.get_inspector().create_end(context, inputs, outcome)
+    }
+
+    #[inline]
+    fn eofcreate(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.inner.get_inspector().eofcreate(context, inputs)
+    }
+
+    #[inline]
+    fn eofcreate_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &EOFCreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.inner.get_inspector().eofcreate_end(context, inputs, outcome)
+    }
+
+    #[inline]
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        Inspector::<DB>::selfdestruct(self.inner.get_inspector(), contract, target, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        inspector_handle_register,
+        primitives::{AccountInfo, Bytecode, ExecutionResult, TxKind},
+        Evm,
+    };
+
+    /// Counts the executed steps.
+    #[derive(Debug, Default)]
+    struct StepCounter(u64);
+
+    impl<DB: Database> Inspector<DB> for StepCounter {
+        fn step(&mut self, _interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+            self.0 += 1;
+        }
+    }
+
+    /// Executes a call to a contract that loops until the gas is exhausted, and returns the
+    /// number of executed steps.
+    fn run_loop(cancellation: Cancellation, gas_limit: u64) -> (ExecutionResult, u64) {
+        // JUMPDEST PUSH1 0 JUMP
+        let code = Bytecode::new_raw(vec![0x5b, 0x60, 0x00, 0x56].into());
+        let contract = Address::with_last_byte(1);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+        );
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(CancellableInspector::new(StepCounter::default(), cancellation))
+            .append_handler_register(inspector_handle_register)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = gas_limit;
+            })
+            .modify_block_env(|block| block.gas_limit = U256::MAX)
+            .build();
+        let result = evm.transact().unwrap().result;
+        (result, evm.context.external.inner.0)
+    }
+
+    #[test]
+    fn cancellable_inspector_halts_cancelled_execution() {
+        let cancellation = Cancellation::default();
+        cancellation.cancel();
+
+        // halted at the first check, long before the gas is exhausted
+        let (result, steps) = run_loop(cancellation, 10_000_000);
+        assert!(matches!(result, ExecutionResult::Halt { .. }));
+        assert_eq!(steps, CancellableInspector::<StepCounter>::CHECK_INTERVAL);
+    }
+
+    #[test]
+    fn cancellable_inspector_runs_uncancelled_execution() {
+        let (_, steps) = run_loop(Cancellation::default(), 100_000);
+        assert!(steps > 4 * CancellableInspector::<StepCounter>::CHECK_INTERVAL);
+    }
+
+    #[test]
+    fn cancels_on_drop() {
+        let cancellation = Cancellation::default();
+        let guard = cancellation.cancel_on_drop();
+        assert!(cancellation.ensure_not_cancelled().is_ok());
+
+        drop(guard);
+        assert!(matches!(cancellation.ensure_not_cancelled(), Err(EthApiError::Cancelled)));
+    }
+
+    #[test]
+    fn enter_sets_current() {
+        assert!(Cancellation::current().is_none());
+
+        let cancellation = Cancellation::default();
+        cancellation.cancel();
+        cancellation.enter(|| {
+            assert!(Cancellation::ensure_current_not_cancelled().is_err());
+            Cancellation::default().enter(|| {
+                assert!(Cancellation::ensure_current_not_cancelled().is_ok());
+            });
+            assert!(Cancellation::ensure_current_not_cancelled().is_err());
+        });
+
+        assert!(Cancellation::current().is_none());
+    }
+}
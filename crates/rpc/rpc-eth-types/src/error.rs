@@ -60,6 +60,13 @@ pub enum EthApiError {
     /// Thrown when the target block for proof computation exceeds the maximum configured window.
     #[error("distance to target block exceeds maximum proof window")]
     ExceedsMaxProofWindow,
+    /// Thrown when the block to trace is older than the maximum configured window.
+    #[error("distance to target block exceeds maximum trace window")]
+    ExceedsMaxTraceWindow,
+    /// Thrown when the number of blocks requested in `eth_feeHistory` exceeds the configured
+    /// maximum.
+    #[error("fee history block count exceeds maximum of {0}")]
+    ExceedsMaxFeeHistoryBlocks(u64),
     /// An internal error where prevrandao is not set in the evm's environment
     #[error("prevrandao not in the EVM's environment after merge")]
     PrevrandaoNotSet,
@@ -112,6 +119,13 @@ pub enum EthApiError {
     /// Error thrown when a (tracing) call exceeds the configured timeout
     #[error("execution aborted (timeout = {0:?})")]
     ExecutionTimedOut(Duration),
+    /// Error thrown when the struct logs of a trace exceed the configured memory budget
+    #[error("trace aborted (memory limit = {0} bytes)")]
+    TraceMemoryLimitExceeded(usize),
+    /// Error thrown when blocking work was aborted because its request was dropped, e.g. because
+    /// the client disconnected
+    #[error("execution aborted (request cancelled)")]
+    Cancelled,
     /// Internal Error thrown by the javascript tracer
     #[error("{0}")]
     InternalJsTracerError(String),
@@ -155,6 +169,8 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             EthApiError::EmptyRawTransactionData |
             EthApiError::InvalidBlockRange |
             EthApiError::ExceedsMaxProofWindow |
+            EthApiError::ExceedsMaxTraceWindow |
+            EthApiError::ExceedsMaxFeeHistoryBlocks(_) |
             EthApiError::ConflictingFeeFieldsInRequest |
             EthApiError::Signing(_) |
             EthApiError::BothStateAndStateDiffInOverride(_) |
@@ -179,7 +195,9 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             EthApiError::Unsupported(msg) => internal_rpc_err(msg),
             EthApiError::InternalJsTracerError(msg) => internal_rpc_err(msg),
             EthApiError::InvalidParams(msg) => invalid_params_rpc_err(msg),
-            err @ EthApiError::ExecutionTimedOut(_) => rpc_error_with_code(
+            err @ EthApiError::ExecutionTimedOut(_) |
+            err @ EthApiError::TraceMemoryLimitExceeded(_) |
+            err @ EthApiError::Cancelled => rpc_error_with_code(
                 jsonrpsee_types::error::CALL_EXECUTION_FAILED_CODE,
                 err.to_string(),
             ),
@@ -543,6 +561,22 @@ impl RevertError {
     const fn error_code(&self) -> i32 {
         EthRpcErrorCode::ExecutionError.code()
     }
+
+    /// Strips the revert data from the given RPC error if it is the error of a reverted
+    /// execution, so that it only reports `execution reverted`, without the decoded reason and
+    /// the raw output.
+    ///
+    /// Other errors are returned unchanged.
+    pub fn strip_data(
+        err: jsonrpsee_types::error::ErrorObject<'static>,
+    ) -> jsonrpsee_types::error::ErrorObject<'static> {
+        if err.code() == EthRpcErrorCode::ExecutionError.code() &&
+            err.message().starts_with("execution reverted")
+        {
+            return RpcInvalidTransactionError::Revert(Self { output: None }).into()
+        }
+        err
+    }
 }
 
 impl std::fmt::Display for RevertError {
@@ -703,4 +737,27 @@ mod tests {
         let err = EthApiError::ExecutionTimedOut(Duration::from_secs(10));
         assert_eq!(err.to_string(), "execution aborted (timeout = 10s)");
     }
+
+    #[test]
+    fn strips_revert_data() {
+        let output = Bytes::from_static(&[0x08, 0xc3, 0x79, 0xa0]);
+        let err: jsonrpsee_types::error::ErrorObject<'static> =
+            RpcInvalidTransactionError::Revert(RevertError::new(output)).into();
+        assert!(err.data().is_some());
+
+        let stripped = RevertError::strip_data(err);
+        assert_eq!(stripped.code(), EthRpcErrorCode::ExecutionError.code());
+        assert_eq!(stripped.message(), "execution reverted");
+        assert!(stripped.data().is_none());
+
+        let other: jsonrpsee_types::error::ErrorObject<'static> =
+            EthApiError::InvalidBlockRange.into();
+        assert_eq!(RevertError::strip_data(other.clone()), other);
+    }
+
+    #[test]
+    fn trace_memory_limit_error() {
+        let err = EthApiError::TraceMemoryLimitExceeded(1024);
+        assert_eq!(err.to_string(), "trace aborted (memory limit = 1024 bytes)");
+    }
 }
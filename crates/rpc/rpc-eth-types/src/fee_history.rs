@@ -13,6 +13,10 @@ use futures::{
 use metrics::atomics::AtomicU64;
 use reth_chain_state::CanonStateNotification;
 use reth_chainspec::{ChainSpec, ChainSpecProvider};
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
 use reth_primitives::{
     basefee::calc_next_block_base_fee,
     eip4844::{calc_blob_gasprice, calculate_excess_blob_gas},
@@ -25,7 +29,7 @@ use tracing::trace;
 
 use reth_rpc_server_types::constants::gas_oracle::MAX_HEADER_HISTORY;
 
-use super::{EthApiError, EthStateCache};
+use super::{EthApiError, EthStateCache, FeeHistoryChanges, FeeHistoryStoreHandle};
 
 /// Contains cached fee history entries for blocks.
 ///
@@ -38,22 +42,62 @@ pub struct FeeHistoryCache {
 impl FeeHistoryCache {
     /// Creates new `FeeHistoryCache` instance, initialize it with the more recent data, set bounds
     pub fn new(eth_cache: EthStateCache, config: FeeHistoryCacheConfig) -> Self {
+        Self::new_with_store(eth_cache, config, FeeHistoryStoreHandle::default())
+    }
+
+    /// Creates new `FeeHistoryCache` instance that persists its entries to the given store, see
+    /// [`FeeHistoryStore`](crate::FeeHistoryStore).
+    pub fn new_with_store(
+        eth_cache: EthStateCache,
+        config: FeeHistoryCacheConfig,
+        store: FeeHistoryStoreHandle,
+    ) -> Self {
+        Self::with_disabled(eth_cache, config, store, false)
+    }
+
+    /// Creates a disabled `FeeHistoryCache` instance, which is never populated.
+    ///
+    /// `eth_feeHistory` requests are rejected if the cache is disabled.
+    pub fn disabled(eth_cache: EthStateCache, config: FeeHistoryCacheConfig) -> Self {
+        Self::with_disabled(eth_cache, config, FeeHistoryStoreHandle::default(), true)
+    }
+
+    fn with_disabled(
+        eth_cache: EthStateCache,
+        config: FeeHistoryCacheConfig,
+        store: FeeHistoryStoreHandle,
+        disabled: bool,
+    ) -> Self {
         let inner = FeeHistoryCacheInner {
             lower_bound: Default::default(),
             upper_bound: Default::default(),
             config,
             entries: Default::default(),
             eth_cache,
+            store,
+            disabled,
         };
         Self { inner: Arc::new(inner) }
     }
 
+    /// Returns `true` if the cache is disabled, see [`FeeHistoryCache::disabled`].
+    #[inline]
+    pub fn is_disabled(&self) -> bool {
+        self.inner.disabled
+    }
+
     /// How the cache is configured.
     #[inline]
     pub fn config(&self) -> &FeeHistoryCacheConfig {
         &self.inner.config
     }
 
+    /// The store that the entries are persisted to.
+    #[inline]
+    pub fn store(&self) -> &FeeHistoryStoreHandle {
+        &self.inner.store
+    }
+
     /// Returns the configured resolution for percentile approximation.
     #[inline]
     pub fn resolution(&self) -> u64 {
@@ -74,36 +118,92 @@ impl FeeHistoryCache {
     }
 
     /// Insert block data into the cache.
-    async fn insert_blocks<I>(&self, blocks: I)
+    async fn insert_blocks<I>(&self, blocks: I) -> FeeHistoryChanges
     where
         I: IntoIterator<Item = (SealedBlock, Arc<Vec<Receipt>>)>,
     {
-        let mut entries = self.inner.entries.write().await;
+        self.update_blocks(None, blocks).await.1
+    }
+
+    /// Replaces all cached entries above the `common_ancestor` of a reorg with the given blocks
+    /// of the new canonical chain.
+    ///
+    /// Entries up to and including the common ancestor are unaffected by the reorg and are kept
+    /// as is. Returns the number of recomputed entries.
+    async fn reorg_blocks<I>(&self, common_ancestor: u64, blocks: I) -> (usize, FeeHistoryChanges)
+    where
+        I: IntoIterator<Item = (SealedBlock, Arc<Vec<Receipt>>)>,
+    {
+        self.update_blocks(Some(common_ancestor), blocks).await
+    }
 
+    /// Removes all entries above `truncate_after`, if set, and inserts the given blocks while
+    /// enforcing the cache bounds.
+    ///
+    /// Returns the number of inserted entries, and the changes to flush to the store.
+    async fn update_blocks<I>(
+        &self,
+        truncate_after: Option<u64>,
+        blocks: I,
+    ) -> (usize, FeeHistoryChanges)
+    where
+        I: IntoIterator<Item = (SealedBlock, Arc<Vec<Receipt>>)>,
+    {
         let percentiles = self.predefined_percentiles();
-        // Insert all new blocks and calculate approximated rewards
-        for (block, receipts) in blocks {
-            let mut fee_history_entry = FeeHistoryEntry::new(&block);
-            fee_history_entry.rewards = calculate_reward_percentiles_for_block(
-                &percentiles,
-                fee_history_entry.gas_used,
-                fee_history_entry.base_fee_per_gas,
-                &block.body,
-                &receipts,
-            )
-            .unwrap_or_default();
-            entries.insert(block.number, fee_history_entry);
+        // calculate approximated rewards for all new blocks
+        let new_entries = blocks
+            .into_iter()
+            .map(|(block, receipts)| {
+                let mut fee_history_entry = FeeHistoryEntry::new(&block);
+                if !self.inner.config.include_blob_fees {
+                    fee_history_entry.clear_blob_fees();
+                }
+                fee_history_entry.rewards = calculate_reward_percentiles_for_block(
+                    &percentiles,
+                    fee_history_entry.gas_used,
+                    fee_history_entry.base_fee_per_gas,
+                    &block.body,
+                    &receipts,
+                    self.inner.config.interpolation,
+                )
+                .unwrap_or_default();
+                (block.number, fee_history_entry)
+            })
+            .collect::<Vec<_>>();
+
+        let inserted = new_entries.len();
+        (inserted, self.update_entries(truncate_after, new_entries).await)
+    }
+
+    /// Removes all entries above `truncate_after`, if set, and inserts the given entries while
+    /// enforcing the cache bounds.
+    ///
+    /// Returns the changes to flush to the store.
+    async fn update_entries(
+        &self,
+        truncate_after: Option<u64>,
+        new_entries: Vec<(u64, FeeHistoryEntry)>,
+    ) -> FeeHistoryChanges {
+        let mut entries = self.inner.entries.write().await;
+        let mut removed = Vec::new();
+
+        if let Some(block_number) = truncate_after {
+            // drop the entries of the reverted chain, including those above the new tip
+            removed.extend(entries.split_off(&(block_number + 1)).into_keys());
         }
 
+        let inserted = new_entries.iter().map(|(block_number, _)| *block_number).collect();
+        entries.extend(new_entries);
+
         // enforce bounds by popping the oldest entries
         while entries.len() > self.inner.config.max_blocks as usize {
-            entries.pop_first();
+            removed.extend(entries.pop_first().map(|(block_number, _)| block_number));
         }
 
         if entries.len() == 0 {
             self.inner.upper_bound.store(0, SeqCst);
             self.inner.lower_bound.store(0, SeqCst);
-            return
+            return self.changes(&entries, inserted, removed)
         }
 
         let upper_bound = *entries.last_entry().expect("Contains at least one entry").key();
@@ -111,12 +211,76 @@ impl FeeHistoryCache {
         // also enforce proper lower bound in case we have gaps
         let target_lower = upper_bound.saturating_sub(self.inner.config.max_blocks);
         while entries.len() > 1 && *entries.first_key_value().unwrap().0 < target_lower {
-            entries.pop_first();
+            removed.extend(entries.pop_first().map(|(block_number, _)| block_number));
         }
 
         let lower_bound = *entries.first_entry().expect("Contains at least one entry").key();
         self.inner.upper_bound.store(upper_bound, SeqCst);
         self.inner.lower_bound.store(lower_bound, SeqCst);
+        self.changes(&entries, inserted, removed)
+    }
+
+    /// Returns the net changes of an update, given the block numbers of the inserted and removed
+    /// entries.
+    ///
+    /// Nothing is collected if no store is configured.
+    fn changes(
+        &self,
+        entries: &BTreeMap<u64, FeeHistoryEntry>,
+        inserted: Vec<u64>,
+        removed: Vec<u64>,
+    ) -> FeeHistoryChanges {
+        if self.inner.store.is_noop() {
+            return FeeHistoryChanges::default()
+        }
+
+        FeeHistoryChanges {
+            inserted: inserted
+                .into_iter()
+                .filter_map(|block_number| {
+                    entries.get(&block_number).map(|entry| (block_number, entry.clone()))
+                })
+                .collect(),
+            removed: removed
+                .into_iter()
+                .filter(|block_number| !entries.contains_key(block_number))
+                .collect(),
+        }
+    }
+
+    /// Loads the entries of the configured store into the cache.
+    ///
+    /// Only entries of blocks that are part of the canonical chain and whose rewards match the
+    /// configured resolution are loaded. The canonical hashes are looked up on a blocking task.
+    /// Returns the number of loaded entries.
+    async fn load_from_store<Provider>(&self, provider: Provider) -> usize
+    where
+        Provider: BlockReaderIdExt + 'static,
+    {
+        let stored = self.inner.store.load().await;
+        if stored.is_empty() {
+            return 0
+        }
+
+        let percentiles = self.predefined_percentiles().len();
+        let Ok(entries) = tokio::task::spawn_blocking(move || {
+            stored
+                .into_iter()
+                .filter(|(block_number, entry)| {
+                    entry.rewards.len() == percentiles &&
+                        provider.block_hash(*block_number).ok().flatten() ==
+                            Some(entry.header_hash)
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        else {
+            return 0
+        };
+
+        let loaded = entries.len();
+        self.update_entries(None, entries).await;
+        loaded
     }
 
     /// Get `UpperBound` value for `FeeHistoryCache`
@@ -181,14 +345,67 @@ pub struct FeeHistoryCacheConfig {
     ///
     /// Default is 4 which means 0.25
     pub resolution: u64,
+    /// How reward percentiles are interpolated between transactions.
+    ///
+    /// Default is [`PercentileInterpolation::Lower`]
+    #[serde(default)]
+    pub interpolation: PercentileInterpolation,
+    /// Whether `eth_feeHistory` responses include the EIP-4844 blob fee fields.
+    ///
+    /// If enabled, the blob fee fields are populated for all blocks, with zeros for blocks before
+    /// Cancun. If disabled, they are omitted entirely, as on pre-Cancun chains.
+    ///
+    /// Default is `true`
+    #[serde(default = "default_include_blob_fees")]
+    pub include_blob_fees: bool,
+    /// Whether `eth_feeHistory` requests for the `pending` block include a projected entry for
+    /// it.
+    ///
+    /// The pending block is projected as an empty block on top of the latest block, so its base
+    /// fees are derived from the latest header and its gas used ratio and rewards are zero. If
+    /// disabled, requests for `pending` are served up to the latest block.
+    ///
+    /// Default is `false`
+    #[serde(default)]
+    pub include_pending: bool,
 }
 
 impl Default for FeeHistoryCacheConfig {
     fn default() -> Self {
-        Self { max_blocks: MAX_HEADER_HISTORY + 100, resolution: 4 }
+        Self {
+            max_blocks: MAX_HEADER_HISTORY + 100,
+            resolution: 4,
+            interpolation: PercentileInterpolation::default(),
+            include_blob_fees: default_include_blob_fees(),
+            include_pending: false,
+        }
     }
 }
 
+const fn default_include_blob_fees() -> bool {
+    true
+}
+
+/// How a reward percentile is computed from the rewards of the transactions in a block.
+///
+/// Transactions are sorted by reward and weighted by the gas they used, so that each transaction
+/// covers a range of the gas used by the block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PercentileInterpolation {
+    /// The reward of the first transaction at which the cumulative gas used reaches the
+    /// percentile of the gas used by the block.
+    ///
+    /// This matches the behaviour of geth.
+    #[default]
+    Lower,
+    /// The reward of the transaction whose gas range midpoint is nearest to the percentile.
+    Nearest,
+    /// The rewards of the two transactions whose gas range midpoints surround the percentile,
+    /// linearly interpolated by the distance to the percentile.
+    Linear,
+}
+
 /// Container type for shared state in [`FeeHistoryCache`]
 #[derive(Debug)]
 struct FeeHistoryCacheInner {
@@ -202,6 +419,10 @@ struct FeeHistoryCacheInner {
     /// Stores the entries of the cache
     entries: tokio::sync::RwLock<BTreeMap<u64, FeeHistoryEntry>>,
     eth_cache: EthStateCache,
+    /// Store that the entries are persisted to.
+    store: FeeHistoryStoreHandle,
+    /// Whether the cache is disabled, in which case fee history is not served.
+    disabled: bool,
 }
 
 /// Awaits for new chain events and directly inserts them into the cache so they're available
@@ -212,12 +433,18 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
     provider: Provider,
 ) where
     St: Stream<Item = CanonStateNotification> + Unpin + 'static,
-    Provider: BlockReaderIdExt + ChainSpecProvider + 'static,
+    Provider: BlockReaderIdExt + ChainSpecProvider + Clone + 'static,
 {
     // We're listening for new blocks emitted when the node is in live sync.
     // If the node transitions to stage sync, we need to fetch the missing blocks
     let mut missing_blocks = VecDeque::new();
     let mut fetch_missing_block = Fuse::terminated();
+    let metrics = FeeHistoryCacheMetrics::default();
+
+    let loaded = fee_history_cache.load_from_store(provider.clone()).await;
+    if loaded > 0 {
+        trace!(target: "rpc::fee", loaded, "Loaded fee history entries from store");
+    }
 
     loop {
         if fetch_missing_block.is_terminated() {
@@ -238,7 +465,8 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
         tokio::select! {
             res = &mut fetch_missing_block =>  {
                 if let Ok(res) = res {
-                    fee_history_cache.insert_blocks(res.into_iter()).await;
+                    let changes = fee_history_cache.insert_blocks(res.into_iter()).await;
+                    fee_history_cache.store().flush(changes).await;
                 }
             }
             event = events.next() =>  {
@@ -246,25 +474,54 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
                      // the stream ended, we are done
                     break;
                 };
-                let (blocks, receipts): (Vec<_>, Vec<_>) = event
-                    .committed()
+                let committed = event.committed();
+                let (blocks, receipts): (Vec<_>, Vec<_>) = committed
                     .blocks_and_receipts()
                     .map(|(block, receipts)| {
                         (block.block.clone(), Arc::new(receipts.iter().flatten().cloned().collect::<Vec<_>>()))
                     })
                     .unzip();
-                fee_history_cache.insert_blocks(blocks.into_iter().zip(receipts)).await;
+                let blocks = blocks.into_iter().zip(receipts);
+                let changes = if event.reverted().is_some() {
+                    // only entries above the common ancestor are affected by the reorg
+                    let common_ancestor = committed.fork_block().number;
+                    let (recomputed, changes) = fee_history_cache.reorg_blocks(common_ancestor, blocks).await;
+                    trace!(target: "rpc::fee", common_ancestor, recomputed, "Recomputed fee history entries after reorg");
+                    metrics.reorg_recomputations_total.increment(1);
+                    metrics.reorg_recomputed_blocks.record(recomputed as f64);
+                    changes
+                } else {
+                    fee_history_cache.insert_blocks(blocks).await
+                };
+                fee_history_cache.store().flush(changes).await;
 
                 // keep track of missing blocks
                 missing_blocks = fee_history_cache.missing_consecutive_blocks().await;
             }
         }
+
+        if let Ok(tip) = provider.best_block_number() {
+            metrics.tip_lag_blocks.set(tip.saturating_sub(fee_history_cache.upper_bound()) as f64);
+        }
     }
 }
 
+/// Metrics for the fee history cache task.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.fee_history_cache")]
+struct FeeHistoryCacheMetrics {
+    /// The number of blocks between the latest block processed by the cache and the canonical
+    /// tip.
+    tip_lag_blocks: Gauge,
+    /// The number of reorgs that caused cached entries to be recomputed.
+    reorg_recomputations_total: Counter,
+    /// The number of cached entries recomputed per reorg.
+    reorg_recomputed_blocks: Histogram,
+}
+
 /// Calculates reward percentiles for transactions in a block header.
 /// Given a list of percentiles and a sealed block header, this function computes
-/// the corresponding rewards for the transactions at each percentile.
+/// the corresponding rewards for the transactions at each percentile, interpolated as configured.
 ///
 /// The results are returned as a vector of U256 values.
 pub fn calculate_reward_percentiles_for_block(
@@ -273,6 +530,7 @@ pub fn calculate_reward_percentiles_for_block(
     base_fee_per_gas: u64,
     transactions: &[TransactionSigned],
     receipts: &[Receipt],
+    interpolation: PercentileInterpolation,
 ) -> Result<Vec<u128>, EthApiError> {
     let mut transactions = transactions
         .iter()
@@ -297,20 +555,38 @@ pub fn calculate_reward_percentiles_for_block(
     // Sort the transactions by their rewards in ascending order
     transactions.sort_by_key(|tx| tx.reward);
 
+    // Empty blocks should return in a zero row
+    if transactions.is_empty() {
+        return Ok(vec![0; percentiles.len()])
+    }
+
+    match interpolation {
+        PercentileInterpolation::Lower => {
+            Ok(lower_reward_percentiles(percentiles, gas_used, &transactions))
+        }
+        PercentileInterpolation::Nearest | PercentileInterpolation::Linear => {
+            Ok(interpolated_reward_percentiles(percentiles, gas_used, &transactions, interpolation))
+        }
+    }
+}
+
+/// Returns the reward of the first transaction at which the cumulative gas used reaches each
+/// percentile, see [`PercentileInterpolation::Lower`].
+///
+/// Expects a non-empty list of transactions sorted by reward.
+fn lower_reward_percentiles(
+    percentiles: &[f64],
+    gas_used: u64,
+    transactions: &[TxGasAndReward],
+) -> Vec<u128> {
     // Find the transaction that corresponds to the given percentile
     //
     // We use a `tx_index` here that is shared across all percentiles, since we know
     // the percentiles are monotonically increasing.
     let mut tx_index = 0;
-    let mut cumulative_gas_used = transactions.first().map(|tx| tx.gas_used).unwrap_or_default();
-    let mut rewards_in_block = Vec::new();
+    let mut cumulative_gas_used = transactions[0].gas_used;
+    let mut rewards_in_block = Vec::with_capacity(percentiles.len());
     for percentile in percentiles {
-        // Empty blocks should return in a zero row
-        if transactions.is_empty() {
-            rewards_in_block.push(0);
-            continue
-        }
-
         let threshold = (gas_used as f64 * percentile / 100.) as u64;
         while cumulative_gas_used < threshold && tx_index < transactions.len() - 1 {
             tx_index += 1;
@@ -319,11 +595,63 @@ pub fn calculate_reward_percentiles_for_block(
         rewards_in_block.push(transactions[tx_index].reward);
     }
 
-    Ok(rewards_in_block)
+    rewards_in_block
+}
+
+/// Returns the reward at each percentile, interpolated between the transactions whose gas range
+/// midpoints surround the percentile, see [`PercentileInterpolation::Nearest`] and
+/// [`PercentileInterpolation::Linear`].
+///
+/// Expects a non-empty list of transactions sorted by reward.
+fn interpolated_reward_percentiles(
+    percentiles: &[f64],
+    gas_used: u64,
+    transactions: &[TxGasAndReward],
+    interpolation: PercentileInterpolation,
+) -> Vec<u128> {
+    let midpoints = transactions
+        .iter()
+        .scan(0u64, |cumulative_gas_used, tx| {
+            let midpoint = *cumulative_gas_used as f64 + tx.gas_used as f64 / 2.;
+            *cumulative_gas_used += tx.gas_used;
+            Some(midpoint)
+        })
+        .collect::<Vec<_>>();
+
+    percentiles
+        .iter()
+        .map(|percentile| {
+            let threshold = gas_used as f64 * percentile / 100.;
+            let upper = midpoints.partition_point(|&midpoint| midpoint < threshold);
+            if upper == 0 {
+                return transactions[0].reward
+            }
+            if upper == transactions.len() {
+                return transactions[upper - 1].reward
+            }
+
+            let (lower, upper) = (upper - 1, upper);
+            let (lower_reward, upper_reward) =
+                (transactions[lower].reward, transactions[upper].reward);
+            let distance = midpoints[upper] - midpoints[lower];
+            let weight = if distance > 0. { (threshold - midpoints[lower]) / distance } else { 0. };
+
+            if interpolation == PercentileInterpolation::Nearest {
+                if weight > 0.5 {
+                    upper_reward
+                } else {
+                    lower_reward
+                }
+            } else {
+                lower_reward + ((upper_reward - lower_reward) as f64 * weight) as u128
+            }
+        })
+        .collect()
 }
 
 /// A cached entry for a block's fee history.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FeeHistoryEntry {
     /// The base fee per gas for this block.
     pub base_fee_per_gas: u64,
@@ -375,6 +703,14 @@ impl FeeHistoryEntry {
         }
     }
 
+    /// Removes the EIP-4844 blob fee fields from the entry, as if the block predates Cancun.
+    pub fn clear_blob_fees(&mut self) {
+        self.base_fee_per_blob_gas = None;
+        self.blob_gas_used_ratio = 0.0;
+        self.excess_blob_gas = None;
+        self.blob_gas_used = None;
+    }
+
     /// Returns the base fee for the next block according to the EIP-1559 spec.
     pub fn next_block_base_fee(&self, chain_spec: &ChainSpec) -> u64 {
         calc_next_block_base_fee(
@@ -401,3 +737,154 @@ impl FeeHistoryEntry {
         Some(calculate_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FeeHistoryStore, FeeHistoryStoreError};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{Block, Header, Transaction, TxEip1559};
+    use reth_provider::test_utils::{MockEthProvider, NoopProvider};
+
+    /// A store that keeps the entries in memory.
+    #[derive(Debug, Clone, Default)]
+    struct MemoryStore(Arc<std::sync::Mutex<BTreeMap<u64, FeeHistoryEntry>>>);
+
+    impl FeeHistoryStore for MemoryStore {
+        fn load(&self) -> Result<BTreeMap<u64, FeeHistoryEntry>, FeeHistoryStoreError> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        fn flush(&self, changes: FeeHistoryChanges) -> Result<(), FeeHistoryStoreError> {
+            let mut entries = self.0.lock().unwrap();
+            for block_number in changes.removed {
+                entries.remove(&block_number);
+            }
+            entries.extend(changes.inserted);
+            Ok(())
+        }
+    }
+
+    fn block(number: u64) -> SealedBlock {
+        let header = Header { number, gas_limit: 30_000_000, ..Default::default() };
+        Block { header, ..Default::default() }.seal_slow()
+    }
+
+    fn fee_history_cache(max_blocks: u64, store: &MemoryStore) -> FeeHistoryCache {
+        let eth_cache = EthStateCache::spawn(
+            NoopProvider::default(),
+            Default::default(),
+            EthEvmConfig::default(),
+        );
+        let config = FeeHistoryCacheConfig { max_blocks, ..Default::default() };
+        FeeHistoryCache::new_with_store(
+            eth_cache,
+            config,
+            FeeHistoryStoreHandle::new(store.clone()),
+        )
+    }
+
+    #[tokio::test]
+    async fn flushes_net_changes_to_store() {
+        let store = MemoryStore::default();
+        let cache = fee_history_cache(2, &store);
+
+        // block 1 is evicted by the same update that inserts it
+        let changes =
+            cache.insert_blocks((1..=3).map(|number| (block(number), Default::default()))).await;
+        assert_eq!(changes.inserted.iter().map(|(number, _)| *number).collect::<Vec<_>>(), [2, 3]);
+        cache.store().flush(changes).await;
+        assert_eq!(store.0.lock().unwrap().keys().copied().collect::<Vec<_>>(), [2, 3]);
+
+        // a reorg above block 2 replaces block 3 and evicts block 2
+        let (recomputed, changes) =
+            cache.reorg_blocks(2, (3..=4).map(|number| (block(number), Default::default()))).await;
+        assert_eq!(recomputed, 2);
+        cache.store().flush(changes).await;
+        assert_eq!(store.0.lock().unwrap().keys().copied().collect::<Vec<_>>(), [3, 4]);
+    }
+
+    #[tokio::test]
+    async fn loads_canonical_entries_from_store() {
+        let store = MemoryStore::default();
+        let provider = MockEthProvider::default();
+        {
+            let writer = fee_history_cache(10, &store);
+            let changes = writer
+                .insert_blocks((1..=3).map(|number| (block(number), Default::default())))
+                .await;
+            writer.store().flush(changes).await;
+        }
+        // only blocks 1 and 2 are canonical
+        for number in 1..=2 {
+            let block = block(number);
+            provider.add_block(block.hash(), block.unseal());
+        }
+        // the rewards of block 2 were computed with a different resolution
+        store.0.lock().unwrap().get_mut(&2).unwrap().rewards.pop();
+
+        let cache = fee_history_cache(10, &store);
+        assert_eq!(cache.load_from_store(provider).await, 1);
+        assert_eq!((cache.lower_bound(), cache.upper_bound()), (1, 1));
+        let entries = cache.get_history(1, 1).await.expect("block 1 is loaded");
+        assert_eq!(entries[0].header_hash, block(1).hash());
+    }
+
+    /// Returns the reward percentiles of a block with three transactions, using 100, 300 and 100
+    /// gas and paying a tip of 10, 20 and 40.
+    fn reward_percentiles(interpolation: PercentileInterpolation) -> Vec<u128> {
+        let (transactions, receipts): (Vec<_>, Vec<_>) = [(10, 100), (20, 400), (40, 500)]
+            .into_iter()
+            .map(|(tip, cumulative_gas_used)| {
+                let transaction = TransactionSigned {
+                    transaction: Transaction::Eip1559(TxEip1559 {
+                        max_priority_fee_per_gas: tip,
+                        max_fee_per_gas: tip,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                };
+                (transaction, Receipt { cumulative_gas_used, ..Default::default() })
+            })
+            .unzip();
+
+        calculate_reward_percentiles_for_block(
+            &[0., 24., 50., 80., 100.],
+            500,
+            0,
+            &transactions,
+            &receipts,
+            interpolation,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn reward_percentiles_interpolation() {
+        assert_eq!(reward_percentiles(PercentileInterpolation::Lower), vec![10, 20, 20, 20, 40]);
+        assert_eq!(reward_percentiles(PercentileInterpolation::Nearest), vec![10, 10, 20, 40, 40]);
+        assert_eq!(reward_percentiles(PercentileInterpolation::Linear), vec![10, 13, 20, 35, 40]);
+    }
+
+    #[test]
+    fn reward_percentiles_empty_block() {
+        for interpolation in [
+            PercentileInterpolation::Lower,
+            PercentileInterpolation::Nearest,
+            PercentileInterpolation::Linear,
+        ] {
+            let rewards =
+                calculate_reward_percentiles_for_block(&[10., 90.], 0, 0, &[], &[], interpolation)
+                    .unwrap();
+            assert_eq!(rewards, vec![0, 0]);
+        }
+    }
+
+    #[test]
+    fn include_blob_fees_defaults_to_enabled() {
+        let config: FeeHistoryCacheConfig =
+            serde_json::from_str(r#"{"maxBlocks":1124,"resolution":4}"#).unwrap();
+        assert!(config.include_blob_fees);
+        assert!(FeeHistoryCacheConfig::default().include_blob_fees);
+    }
+}
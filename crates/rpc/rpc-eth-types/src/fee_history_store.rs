@@ -0,0 +1,122 @@
+//! Persistence of [`FeeHistoryCache`](crate::FeeHistoryCache) entries in an external store.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use tracing::warn;
+
+use crate::FeeHistoryEntry;
+
+/// The error returned by a [`FeeHistoryStore`].
+pub type FeeHistoryStoreError = Box<dyn Error + Send + Sync>;
+
+/// A store for the entries of the [`FeeHistoryCache`](crate::FeeHistoryCache), e.g. a file or a
+/// key-value store shared by several RPC nodes.
+///
+/// The cache loads the stored entries once its task starts, and flushes its changes to the store
+/// after every update. Loaded entries are only used if they match the local canonical chain, and
+/// if their rewards were computed with the same resolution. Nodes that share a store should use
+/// the same [`FeeHistoryCacheConfig`](crate::FeeHistoryCacheConfig).
+///
+/// Note: calls are made from a blocking task, so implementations are free to do blocking IO.
+pub trait FeeHistoryStore: Send + Sync + 'static {
+    /// Loads all stored entries, by block number.
+    fn load(&self) -> Result<BTreeMap<u64, FeeHistoryEntry>, FeeHistoryStoreError>;
+
+    /// Applies the changes of a cache update to the store.
+    fn flush(&self, changes: FeeHistoryChanges) -> Result<(), FeeHistoryStoreError>;
+}
+
+/// The changes of a single [`FeeHistoryCache`](crate::FeeHistoryCache) update, see
+/// [`FeeHistoryStore::flush`].
+///
+/// A block number is never both inserted and removed, so changes can be applied in any order.
+#[derive(Debug, Clone, Default)]
+pub struct FeeHistoryChanges {
+    /// The new or replaced entries, by block number.
+    pub inserted: Vec<(u64, FeeHistoryEntry)>,
+    /// The block numbers of the removed entries, e.g. evicted or reverted by a reorg.
+    pub removed: Vec<u64>,
+}
+
+impl FeeHistoryChanges {
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Shareable handle to a [`FeeHistoryStore`] that can be configured with
+/// [`EthConfig::fee_history_store`](crate::EthConfig::fee_history_store).
+///
+/// The default handle is a no-op store, i.e. entries are only kept in memory by the cache.
+#[derive(Clone, Default)]
+pub struct FeeHistoryStoreHandle {
+    /// The store, `None` for the no-op store.
+    inner: Option<Arc<dyn FeeHistoryStore>>,
+}
+
+impl FeeHistoryStoreHandle {
+    /// Creates a new handle for the given [`FeeHistoryStore`].
+    pub fn new<S: FeeHistoryStore>(store: S) -> Self {
+        Self { inner: Some(Arc::new(store)) }
+    }
+
+    /// Returns `true` if this is the no-op store.
+    pub const fn is_noop(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /// Loads all stored entries on a blocking task.
+    ///
+    /// Failures are logged, and treated like an empty store.
+    pub(crate) async fn load(&self) -> BTreeMap<u64, FeeHistoryEntry> {
+        let Some(store) = self.inner.clone() else { return BTreeMap::new() };
+        match tokio::task::spawn_blocking(move || store.load()).await {
+            Ok(Ok(entries)) => entries,
+            Ok(Err(err)) => {
+                warn!(target: "rpc::fee", %err, "Failed to load fee history entries from store");
+                BTreeMap::new()
+            }
+            Err(_) => BTreeMap::new(),
+        }
+    }
+
+    /// Flushes the changes to the store on a blocking task.
+    ///
+    /// Failures are logged, the cache itself is unaffected.
+    pub(crate) async fn flush(&self, changes: FeeHistoryChanges) {
+        let Some(store) = self.inner.clone() else { return };
+        if changes.is_empty() {
+            return
+        }
+
+        match tokio::task::spawn_blocking(move || store.flush(changes)).await {
+            Ok(Ok(())) | Err(_) => {}
+            Ok(Err(err)) => {
+                warn!(target: "rpc::fee", %err, "Failed to flush fee history entries to store");
+            }
+        }
+    }
+}
+
+impl Debug for FeeHistoryStoreHandle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FeeHistoryStoreHandle").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for FeeHistoryStoreHandle {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.inner, &other.inner) {
+            (Some(this), Some(other)) => Arc::ptr_eq(this, other),
+            (this, other) => this.is_none() && other.is_none(),
+        }
+    }
+}
+
+impl Eq for FeeHistoryStoreHandle {}
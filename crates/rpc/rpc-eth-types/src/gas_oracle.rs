@@ -1,9 +1,14 @@
 //! An implementation of the eth gas price oracle, used for providing gas price estimates based on
 //! previous blocks.
 
-use std::fmt::{self, Debug, Formatter};
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
 
 use derive_more::{Deref, DerefMut, From, Into};
+use reth_metrics::{metrics::Counter, Metrics};
 use reth_primitives::{constants::GWEI_TO_WEI, BlockNumberOrTag, B256, U256};
 use reth_rpc_server_types::constants;
 use reth_storage_api::BlockReaderIdExt;
@@ -74,6 +79,10 @@ pub struct GasPriceOracle<Provider> {
     oracle_config: GasPriceOracleConfig,
     /// The price under which the sample will be ignored.
     ignore_price: Option<u128>,
+    /// The lower bound that suggestions are clamped to.
+    price_floor: Option<U256>,
+    /// The upper bound that suggestions are clamped to.
+    price_ceiling: Option<U256>,
     /// Stores the latest calculated price and its block hash and Cache stores the lowest effective
     /// tip values of recent blocks
     inner: Mutex<GasPriceOracleInner>,
@@ -105,7 +114,24 @@ where
             ))),
         });
 
-        Self { provider, oracle_config, cache, ignore_price, inner }
+        Self {
+            provider,
+            oracle_config,
+            cache,
+            ignore_price,
+            price_floor: None,
+            price_ceiling: None,
+            inner,
+        }
+    }
+
+    /// Configures the band that gas price and priority fee suggestions are clamped to.
+    ///
+    /// If both bounds are set and the floor exceeds the ceiling, the ceiling takes precedence.
+    pub const fn with_price_bounds(mut self, floor: Option<U256>, ceiling: Option<U256>) -> Self {
+        self.price_floor = floor;
+        self.price_ceiling = ceiling;
+        self
     }
 
     /// Returns the configuration of the gas price oracle.
@@ -113,6 +139,22 @@ where
         &self.oracle_config
     }
 
+    /// Clears the last suggested price and the cached tips of recent blocks, so that the next
+    /// suggestion is computed from freshly loaded blocks.
+    ///
+    /// This is safe to call concurrently with suggestions: it waits until an in-progress
+    /// suggestion released the oracle's state, and suggestions that start afterwards recompute.
+    pub async fn clear_cache(&self) {
+        let mut inner = self.inner.lock().await;
+        inner.last_price = Default::default();
+        inner.lowest_effective_tip_cache.clear();
+    }
+
+    /// Clamps the given suggestion into the configured floor and ceiling.
+    pub fn clamp_price(&self, price: U256) -> U256 {
+        clamp_price(price, self.price_floor, self.price_ceiling)
+    }
+
     /// Suggests a gas price estimate based on recent blocks, using the configured percentile.
     pub async fn suggest_tip_cap(&self) -> EthResult<U256> {
         let header = self
@@ -259,6 +301,12 @@ where
     }
 }
 
+/// Clamps the price into `[floor, ceiling]`, the ceiling taking precedence over the floor.
+fn clamp_price(price: U256, floor: Option<U256>, ceiling: Option<U256>) -> U256 {
+    let price = floor.map_or(price, |floor| price.max(floor));
+    ceiling.map_or(price, |ceiling| price.min(ceiling))
+}
+
 /// Container type for mutable inner state of the [`GasPriceOracle`]
 #[derive(Debug)]
 struct GasPriceOracleInner {
@@ -304,6 +352,33 @@ impl Default for GasCap {
     }
 }
 
+/// Shareable metrics for call and tracing requests that exceed the [`GasCap`], labeled by RPC
+/// method.
+#[derive(Debug, Clone, Default)]
+pub struct GasCapMetrics {
+    methods: Arc<parking_lot::Mutex<HashMap<&'static str, GasCapMethodMetrics>>>,
+}
+
+impl GasCapMetrics {
+    /// Records a request of the given RPC method that exceeded the gas cap.
+    pub fn increment(&self, method: &'static str) {
+        self.methods
+            .lock()
+            .entry(method)
+            .or_insert_with(|| GasCapMethodMetrics::new_with_labels(&[("method", method)]))
+            .exceeded_total
+            .increment(1);
+    }
+}
+
+/// Gas cap metrics of a single RPC method.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.gas_cap")]
+struct GasCapMethodMetrics {
+    /// The number of requests that asked for more gas than the gas cap
+    exceeded_total: Counter,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -318,4 +393,17 @@ mod tests {
     fn ignore_price_sanity() {
         assert_eq!(DEFAULT_IGNORE_GAS_PRICE, U256::from(2u64));
     }
+
+    #[test]
+    fn clamp_price_bounds() {
+        let floor = Some(U256::from(10));
+        let ceiling = Some(U256::from(100));
+
+        assert_eq!(clamp_price(U256::from(50), None, None), U256::from(50));
+        assert_eq!(clamp_price(U256::from(5), floor, ceiling), U256::from(10));
+        assert_eq!(clamp_price(U256::from(500), floor, ceiling), U256::from(100));
+        assert_eq!(clamp_price(U256::from(50), floor, ceiling), U256::from(50));
+        // ceiling takes precedence over a misconfigured floor
+        assert_eq!(clamp_price(U256::from(50), Some(U256::from(200)), ceiling), U256::from(100));
+    }
 }
@@ -10,32 +10,53 @@
 
 pub mod builder;
 pub mod cache;
+pub mod cancellation;
 pub mod error;
 pub mod fee_history;
+pub mod fee_history_store;
 pub mod gas_oracle;
 pub mod id_provider;
 pub mod logs_utils;
 pub mod pending_block;
+pub mod proof_permits;
 pub mod receipt;
 pub mod revm_utils;
+pub mod state_changes;
 pub mod transaction;
 pub mod utils;
 
 pub use builder::{
     config::{EthConfig, EthFilterConfig},
-    ctx::EthApiBuilderCtx,
+    ctx::{CtxDescription, EthApiBuilderCtx},
+    middleware::{
+        DecimalQuantities, DynEthResponseMiddleware, EthResponseMiddleware, NumberEncoding,
+    },
+    rate_limit::{DynRateLimiter, RateLimit, RateLimiter},
+    response_cache::{ResponseCache, ResponseCacheConfig, ResponseCacheKey},
 };
 pub use cache::{
-    config::EthStateCacheConfig, db::StateCacheDb, multi_consumer::MultiConsumerLruCache,
-    EthStateCache,
+    config::EthStateCacheConfig,
+    db::StateCacheDb,
+    multi_consumer::MultiConsumerLruCache,
+    remote::{RemoteBlockSource, RemoteStateSource},
+    CacheCoalescingStats, CacheEvictionStats, EthStateCache,
 };
+pub use cancellation::{CancelOnDrop, CancellableInspector, Cancellation};
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
-pub use fee_history::{FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry};
+pub use fee_history::{
+    FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry, PercentileInterpolation,
+};
+pub use fee_history_store::{
+    FeeHistoryChanges, FeeHistoryStore, FeeHistoryStoreError, FeeHistoryStoreHandle,
+};
 pub use gas_oracle::{
-    GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult, RPC_DEFAULT_GAS_CAP,
+    GasCap, GasCapMetrics, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult,
+    RPC_DEFAULT_GAS_CAP,
 };
 pub use id_provider::EthSubscriptionIdProvider;
 pub use logs_utils::EthFilterError;
 pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
+pub use proof_permits::{ProofPermit, ProofPermitMetrics};
 pub use receipt::ReceiptBuilder;
+pub use state_changes::{BlockStateChanges, BlockStorageChanges, StateChangeNotification};
 pub use transaction::TransactionSource;
@@ -2,7 +2,10 @@
 //!
 //! Types used in block building.
 
-use std::{fmt, time::Instant};
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
 
 use derive_more::Constructor;
 use reth_chainspec::ChainSpec;
@@ -16,6 +19,9 @@ use revm_primitives::{
 
 use super::{EthApiError, EthResult};
 
+/// The default duration a locally assembled pending block is served before it is assembled again.
+pub const DEFAULT_PENDING_BLOCK_CACHE_TTL: Duration = Duration::from_secs(1);
+
 /// Configured [`BlockEnv`] and [`CfgEnvWithHandlerCfg`] for a pending block
 #[derive(Debug, Clone, Constructor)]
 pub struct PendingBlockEnv {
@@ -0,0 +1,136 @@
+//! Metrics for the permits that bound concurrent `eth_getProof` calls.
+
+use std::sync::{Arc, OnceLock};
+
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_tasks::pool::BlockingTaskGuard;
+use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+
+/// Shareable metrics for the permits of `eth_getProof` calls, see
+/// [`EthConfig::proof_permits`](crate::EthConfig::proof_permits).
+///
+/// If handlers with separate permit pools share the metrics, e.g. handlers that rely on
+/// [`ProofPermitMetrics::shared`], the gauge reflects the pool that changed last.
+#[derive(Debug, Clone, Default)]
+pub struct ProofPermitMetrics {
+    metrics: Arc<ProofPermitMetricsInner>,
+}
+
+impl ProofPermitMetrics {
+    /// Returns the metrics shared by all handlers that don't track their own.
+    pub fn shared() -> &'static Self {
+        static METRICS: OnceLock<ProofPermitMetrics> = OnceLock::new();
+        METRICS.get_or_init(Self::default)
+    }
+
+    /// Acquires a permit from the guard.
+    ///
+    /// Records a wait if no permit is available, i.e. if the call has to queue.
+    pub async fn acquire(&self, guard: &BlockingTaskGuard) -> Result<ProofPermit, AcquireError> {
+        if guard.available_permits() == 0 {
+            self.metrics.permit_waits_total.increment(1);
+        }
+
+        let permit = guard.clone().acquire_owned().await?;
+        self.metrics.available_permits.set(guard.available_permits() as f64);
+
+        Ok(ProofPermit { permit: Some(permit), guard: guard.clone(), metrics: self.clone() })
+    }
+}
+
+/// A permit for an `eth_getProof` call, acquired with [`ProofPermitMetrics::acquire`].
+///
+/// Updates the available permits once dropped.
+#[derive(Debug)]
+pub struct ProofPermit {
+    permit: Option<OwnedSemaphorePermit>,
+    guard: BlockingTaskGuard,
+    metrics: ProofPermitMetrics,
+}
+
+impl Drop for ProofPermit {
+    fn drop(&mut self) {
+        // release the permit before reading the available permits
+        drop(self.permit.take());
+        self.metrics.metrics.available_permits.set(self.guard.available_permits() as f64);
+    }
+}
+
+/// Metrics for the permits of `eth_getProof` calls.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.eth.proof_permits")]
+struct ProofPermitMetricsInner {
+    /// The number of permits that are currently available
+    available_permits: Gauge,
+    /// The number of calls that had to wait for a permit because all permits were in use
+    permit_waits_total: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
+
+    /// Returns the value of the metric with the given name.
+    fn metric(snapshotter: &Snapshotter, name: &str) -> DebugValue {
+        snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .find(|(key, ..)| key.key().name() == name)
+            .map(|(.., value)| value)
+            .unwrap()
+    }
+
+    fn available_permits(snapshotter: &Snapshotter) -> f64 {
+        match metric(snapshotter, "rpc.eth.proof_permits.available_permits") {
+            DebugValue::Gauge(value) => value.into_inner(),
+            value => panic!("unexpected value {value:?}"),
+        }
+    }
+
+    fn permit_waits(snapshotter: &Snapshotter) -> u64 {
+        match metric(snapshotter, "rpc.eth.proof_permits.permit_waits_total") {
+            DebugValue::Counter(value) => value,
+            value => panic!("unexpected value {value:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn records_waits_and_available_permits() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let metrics = metrics::with_local_recorder(&recorder, ProofPermitMetrics::default);
+        let guard = BlockingTaskGuard::new(2);
+
+        let first = metrics.acquire(&guard).await.unwrap();
+        assert_eq!(available_permits(&snapshotter), 1.0);
+        let second = metrics.acquire(&guard).await.unwrap();
+        assert_eq!(available_permits(&snapshotter), 0.0);
+        assert_eq!(permit_waits(&snapshotter), 0);
+
+        // all permits are in use, so the third call has to wait
+        let third = tokio::spawn({
+            let metrics = metrics.clone();
+            let guard = guard.clone();
+            async move { metrics.acquire(&guard).await.unwrap() }
+        });
+        while permit_waits(&snapshotter) == 0 {
+            tokio::task::yield_now().await;
+        }
+        assert!(!third.is_finished());
+
+        drop(first);
+        let third = third.await.unwrap();
+        assert_eq!(available_permits(&snapshotter), 0.0);
+        assert_eq!(permit_waits(&snapshotter), 1);
+
+        drop(second);
+        assert_eq!(available_permits(&snapshotter), 1.0);
+        drop(third);
+        assert_eq!(available_permits(&snapshotter), 2.0);
+    }
+}
@@ -1,18 +1,23 @@
 //! utilities for working with revm
 
 use reth_primitives::{Address, B256, U256};
+use reth_rpc_server_types::constants::gas_oracle::CALL_STIPEND_GAS;
 use reth_rpc_types::{
     state::{AccountOverride, StateOverride},
     BlockOverrides,
 };
 use revm::{
     db::CacheDB,
+    interpreter::{InstructionResult, Interpreter},
     precompile::{PrecompileSpecId, Precompiles},
     primitives::{db::DatabaseRef, Bytecode, SpecId, TxEnv},
-    Database,
+    Database, EvmContext, Inspector,
 };
 use revm_primitives::BlockEnv;
-use std::cmp::min;
+use std::{
+    cmp::min,
+    time::{Duration, Instant},
+};
 
 use super::{EthApiError, EthResult, RpcInvalidTransactionError};
 
@@ -63,6 +68,24 @@ where
         .unwrap_or_default())
 }
 
+/// Returns the gas limit that a transaction that used `gas_used` gas and was refunded
+/// `gas_refund` gas will likely succeed with.
+///
+/// `(gas_used + gas_refund + CALL_STIPEND_GAS) * 64 / 63`, saturating at `u64::MAX` so that gas
+/// limits close to an unlimited gas cap can't overflow.
+pub fn optimistic_gas_limit(gas_used: u64, gas_refund: u64) -> u64 {
+    let gas = (gas_used as u128 + gas_refund as u128 + CALL_STIPEND_GAS as u128) * 64 / 63;
+    gas.try_into().unwrap_or(u64::MAX)
+}
+
+/// Returns the gas limit increased by `buffer_percent` percent, but at most `max_gas_limit`.
+///
+/// Saturates at `u64::MAX` instead of overflowing.
+pub fn buffered_gas_limit(gas_limit: u64, buffer_percent: u64, max_gas_limit: u64) -> u64 {
+    let gas = gas_limit as u128 * (100 + buffer_percent as u128) / 100;
+    gas.try_into().unwrap_or(u64::MAX).min(max_gas_limit.max(gas_limit))
+}
+
 /// Helper type for representing the fees of a [`reth_rpc_types::TransactionRequest`]
 #[derive(Debug)]
 pub struct CallFees {
@@ -309,10 +332,79 @@ where
     Ok(())
 }
 
+/// An [`Inspector`] that halts execution once the configured timeout has elapsed.
+///
+/// The clock starts when the inspector is created and is checked every
+/// [`CallTimeoutInspector::CHECK_INTERVAL`] steps. Once the timeout has elapsed, every frame is
+/// halted, so the result of the execution must be discarded if [`CallTimeoutInspector::timed_out`]
+/// returns `true`.
+#[derive(Debug, Clone, Copy)]
+pub struct CallTimeoutInspector {
+    timeout: Duration,
+    deadline: Instant,
+    steps: u64,
+    timed_out: bool,
+}
+
+impl CallTimeoutInspector {
+    /// Number of steps between two checks of the deadline.
+    pub const CHECK_INTERVAL: u64 = 1024;
+
+    /// Creates a new inspector that halts execution after the given timeout.
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout, deadline: Instant::now() + timeout, steps: 0, timed_out: false }
+    }
+
+    /// Returns `true` if the execution was halted because the timeout elapsed.
+    pub const fn timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// Returns [`EthApiError::ExecutionTimedOut`] if the execution was halted because the timeout
+    /// elapsed.
+    pub const fn ensure_not_timed_out(&self) -> EthResult<()> {
+        if self.timed_out {
+            return Err(EthApiError::ExecutionTimedOut(self.timeout))
+        }
+        Ok(())
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CallTimeoutInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if !self.timed_out {
+            self.steps += 1;
+            if self.steps % Self::CHECK_INTERVAL != 0 || Instant::now() < self.deadline {
+                return
+            }
+            self.timed_out = true;
+        }
+        interp.instruction_result = InstructionResult::OutOfGas;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reth_primitives::constants::GWEI_TO_WEI;
+    use reth_primitives::{constants::GWEI_TO_WEI, TxKind};
+    use revm::{db::EmptyDB, inspector_handle_register, primitives::AccountInfo, Evm};
+
+    #[test]
+    fn test_optimistic_gas_limit_near_unlimited_cap() {
+        assert_eq!(optimistic_gas_limit(u64::MAX, u64::MAX), u64::MAX);
+        assert_eq!(optimistic_gas_limit(u64::MAX - 1, 0), u64::MAX);
+        assert_eq!(optimistic_gas_limit(21_000, 0), (21_000 + CALL_STIPEND_GAS) * 64 / 63);
+    }
+
+    #[test]
+    fn test_buffered_gas_limit() {
+        assert_eq!(buffered_gas_limit(100_000, 0, 30_000_000), 100_000);
+        assert_eq!(buffered_gas_limit(100_000, 10, 30_000_000), 110_000);
+        // capped by the maximum gas limit, but never below the unbuffered gas limit
+        assert_eq!(buffered_gas_limit(100_000, 50, 120_000), 120_000);
+        assert_eq!(buffered_gas_limit(100_000, 50, 90_000), 100_000);
+        assert_eq!(buffered_gas_limit(u64::MAX, u64::MAX, u64::MAX), u64::MAX);
+    }
 
     #[test]
     fn test_ensure_0_fallback() {
@@ -423,4 +515,44 @@ mod tests {
         );
         assert!(call_fees.is_err());
     }
+
+    #[test]
+    fn call_timeout_halts_slow_call() {
+        // JUMPDEST PUSH1 0 JUMP: loops until the gas is exhausted
+        let code = Bytecode::new_raw(vec![0x5b, 0x60, 0x00, 0x56].into());
+        let contract = Address::with_last_byte(1);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+        );
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(CallTimeoutInspector::new(Duration::ZERO))
+            .append_handler_register(inspector_handle_register)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = u64::MAX / 2;
+            })
+            .modify_block_env(|block| block.gas_limit = U256::MAX)
+            .build();
+
+        let res = evm.transact().unwrap();
+        assert!(!res.result.is_success());
+
+        let inspector = evm.context.external;
+        assert!(inspector.timed_out());
+        assert!(matches!(
+            inspector.ensure_not_timed_out(),
+            Err(EthApiError::ExecutionTimedOut(timeout)) if timeout == Duration::ZERO
+        ));
+    }
+
+    #[test]
+    fn call_timeout_not_reached() {
+        let inspector = CallTimeoutInspector::new(Duration::from_secs(60));
+        assert!(!inspector.timed_out());
+        assert!(inspector.ensure_not_timed_out().is_ok());
+    }
 }
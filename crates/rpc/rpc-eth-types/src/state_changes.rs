@@ -0,0 +1,250 @@
+//! Forwarding of per block state changes to external consumers, e.g. embedded indexers.
+
+use futures::{Stream, StreamExt};
+use reth_chain_state::CanonStateNotification;
+use reth_execution_types::Chain;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives::{Account, Address, BlockNumHash, StorageEntry, B256};
+use revm::db::states::PlainStorageRevert;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tracing::{trace, warn};
+
+/// A notification sent to the sink of [`state_changes_task`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateChangeNotification {
+    /// The state changes of a committed canonical block.
+    Block(BlockStateChanges),
+    /// Canonical state notifications were dropped because the task lagged behind, e.g. because
+    /// the sink was full for too long, so the changes of an unknown number of blocks are missing.
+    ///
+    /// Consumers can't tell which blocks were missed, or whether a reorg was among them, so they
+    /// need to resync, e.g. from the changesets in the database.
+    Lagged {
+        /// The number of dropped canonical state notifications, each of which may have held
+        /// several blocks.
+        skipped: u64,
+    },
+}
+
+/// The accounts and storage slots changed by a canonical block.
+///
+/// Like the changesets in the database, changes hold the values _before_ the block was executed.
+/// The values after the block are the values before the next block, or the latest state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockStateChanges {
+    /// Number and hash of the block.
+    pub block: BlockNumHash,
+    /// The changed accounts and their info before the block, sorted by address.
+    ///
+    /// If the info is `None` then the account did not exist before the block.
+    pub accounts: Vec<(Address, Option<Account>)>,
+    /// The changed storage, sorted by address.
+    pub storage: Vec<BlockStorageChanges>,
+}
+
+/// The storage slots of an account changed by a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockStorageChanges {
+    /// Address of the account.
+    pub address: Address,
+    /// Whether the storage of the account was wiped by the block, e.g. by a selfdestruct.
+    ///
+    /// The slots cleared by a wipe are not part of [`Self::slots`] unless the block also wrote
+    /// them before the wipe.
+    pub wiped: bool,
+    /// The changed slots and their values before the block, sorted by key.
+    pub slots: Vec<StorageEntry>,
+}
+
+impl BlockStateChanges {
+    /// Returns the state changes of all blocks of the chain, in ascending block order.
+    pub fn from_chain(chain: &Chain) -> Vec<Self> {
+        let reverts = chain.execution_outcome().bundle.reverts.clone().into_plain_state_reverts();
+
+        chain
+            .blocks()
+            .values()
+            .zip(reverts.accounts.into_iter().zip(reverts.storage))
+            .map(|(block, (mut accounts, storage))| {
+                accounts.sort_unstable_by_key(|(address, _)| *address);
+                let accounts = accounts
+                    .into_iter()
+                    .map(|(address, info)| (address, info.map(Into::into)))
+                    .collect();
+
+                let mut storage = storage
+                    .into_iter()
+                    .map(|PlainStorageRevert { address, wiped, storage_revert }| {
+                        let mut slots = storage_revert
+                            .into_iter()
+                            .map(|(key, value)| StorageEntry {
+                                key: B256::new(key.to_be_bytes()),
+                                value: value.to_previous_value(),
+                            })
+                            .collect::<Vec<_>>();
+                        slots.sort_unstable_by_key(|entry| entry.key);
+                        BlockStorageChanges { address, wiped, slots }
+                    })
+                    .collect::<Vec<_>>();
+                storage.sort_unstable_by_key(|changes| changes.address);
+
+                Self { block: block.num_hash(), accounts, storage }
+            })
+            .collect()
+    }
+}
+
+/// Awaits canonical state notifications from a broadcast channel and forwards the
+/// [`BlockStateChanges`] of every committed block to the sink.
+///
+/// On reorgs only the blocks of the new chain are forwarded, consumers detect a reorg by a block
+/// number that does not follow the previous one. The task waits for the sink if it is full, so a
+/// slow consumer makes the task lag behind the broadcast channel. Notifications that are dropped
+/// because of that are logged, counted in metrics and reported to the consumer with
+/// [`StateChangeNotification::Lagged`], so that a gap is not mistaken for a reorg.
+///
+/// The task ends if the stream ends or the receiver is dropped.
+pub async fn state_changes_task<St>(mut events: St, sink: mpsc::Sender<StateChangeNotification>)
+where
+    St: Stream<Item = Result<CanonStateNotification, BroadcastStreamRecvError>> + Unpin + 'static,
+{
+    let metrics = StateChangesMetrics::default();
+
+    while let Some(event) = events.next().await {
+        let notifications = match event {
+            Ok(event) => BlockStateChanges::from_chain(&event.committed())
+                .into_iter()
+                .map(StateChangeNotification::Block)
+                .collect(),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                warn!(target: "rpc::eth", skipped, "state change sink can't keep up with canonical state notifications, dropped notifications");
+                metrics.lagged_notifications_total.increment(skipped);
+                vec![StateChangeNotification::Lagged { skipped }]
+            }
+        };
+
+        for notification in notifications {
+            if sink.send(notification).await.is_err() {
+                trace!(target: "rpc::eth", "state change sink closed");
+                return
+            }
+        }
+    }
+}
+
+/// Metrics for [`state_changes_task`].
+#[derive(Metrics)]
+#[metrics(scope = "rpc.eth_state_changes")]
+struct StateChangesMetrics {
+    /// The number of canonical state notifications that were dropped because the state change
+    /// sink lagged behind.
+    lagged_notifications_total: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_execution_types::ExecutionOutcome;
+    use reth_primitives::{
+        Header, Receipts, SealedBlock, SealedBlockWithSenders, KECCAK_EMPTY, U256,
+    };
+    use revm::{db::BundleState, primitives::AccountInfo};
+    use std::sync::Arc;
+    use tokio::sync::broadcast;
+    use tokio_stream::wrappers::BroadcastStream;
+
+    type AccountReverts = Vec<(Address, Option<Option<AccountInfo>>, Vec<(U256, U256)>)>;
+
+    fn block(number: u64) -> SealedBlockWithSenders {
+        let header = Header { number, ..Default::default() }.seal_slow();
+        SealedBlockWithSenders::new(SealedBlock { header, ..Default::default() }, Vec::new())
+            .unwrap()
+    }
+
+    /// Returns a chain of consecutive blocks starting at `first`, one for each entry of
+    /// `reverts`.
+    fn chain(first: u64, reverts: Vec<AccountReverts>) -> Chain {
+        let blocks = (first..first + reverts.len() as u64).map(block).collect::<Vec<_>>();
+        let receipts = Receipts { receipt_vec: vec![Vec::new(); blocks.len()] };
+        let bundle = BundleState::new(Vec::new(), reverts, Vec::new());
+        Chain::new(blocks, ExecutionOutcome::new(bundle, receipts, first, Vec::new()), None)
+    }
+
+    #[test]
+    fn state_changes_of_chain() {
+        let created = Address::with_last_byte(1);
+        let changed = Address::with_last_byte(2);
+        let info =
+            AccountInfo { balance: U256::from(10), nonce: 1, code_hash: KECCAK_EMPTY, code: None };
+
+        let mut chain = chain(
+            1,
+            vec![
+                vec![
+                    (changed, Some(Some(info.clone())), vec![(U256::from(1), U256::from(5))]),
+                    (created, Some(None), vec![]),
+                ],
+                vec![(
+                    changed,
+                    None,
+                    vec![(U256::from(2), U256::from(7)), (U256::from(1), U256::ZERO)],
+                )],
+            ],
+        );
+        // the second block selfdestructs the account, which wipes its storage
+        chain.execution_outcome_mut().bundle.reverts[1][0].1.wipe_storage = true;
+
+        let changes = BlockStateChanges::from_chain(&chain);
+        assert_eq!(
+            changes,
+            vec![
+                BlockStateChanges {
+                    block: chain.blocks()[&1].num_hash(),
+                    accounts: vec![(created, None), (changed, Some(info.into()))],
+                    storage: vec![BlockStorageChanges {
+                        address: changed,
+                        wiped: false,
+                        slots: vec![StorageEntry {
+                            key: B256::with_last_byte(1),
+                            value: U256::from(5)
+                        }],
+                    }],
+                },
+                BlockStateChanges {
+                    block: chain.blocks()[&2].num_hash(),
+                    accounts: vec![],
+                    storage: vec![BlockStorageChanges {
+                        address: changed,
+                        wiped: true,
+                        slots: vec![
+                            StorageEntry { key: B256::with_last_byte(1), value: U256::ZERO },
+                            StorageEntry { key: B256::with_last_byte(2), value: U256::from(7) },
+                        ],
+                    }],
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_lagged_notifications() {
+        let (tx, rx) = broadcast::channel(1);
+        for number in 1..=3 {
+            let new = Arc::new(chain(number, vec![vec![]]));
+            tx.send(CanonStateNotification::Commit { new }).unwrap();
+        }
+        drop(tx);
+
+        let (sink, mut changes) = mpsc::channel(16);
+        state_changes_task(BroadcastStream::new(rx), sink).await;
+
+        // only the last notification is still in the channel, the first two were dropped
+        assert_eq!(changes.recv().await, Some(StateChangeNotification::Lagged { skipped: 2 }));
+        let Some(StateChangeNotification::Block(block)) = changes.recv().await else {
+            panic!("expected the changes of a block")
+        };
+        assert_eq!(block.block.number, 3);
+        assert_eq!(changes.recv().await, None);
+    }
+}
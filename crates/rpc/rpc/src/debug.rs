@@ -22,7 +22,8 @@ use reth_rpc_types::{
     state::EvmOverrides,
     trace::geth::{
         BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
-        GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
+        GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, StructLog,
+        TraceResult,
     },
     BlockError, Bundle, RichBlock, StateContext, TransactionRequest,
 };
@@ -30,8 +31,12 @@ use reth_tasks::pool::BlockingTaskGuard;
 use reth_trie::{HashedPostState, HashedStorage};
 use revm::{
     db::{states::bundle_state::BundleRetention, CacheDB},
-    primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
-    StateBuilder,
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, InstructionResult,
+        Interpreter,
+    },
+    primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg, Log},
+    Database, EvmContext, Inspector, StateBuilder,
 };
 use revm_inspectors::tracing::{
     js::{JsInspector, TransactionContext},
@@ -46,6 +51,8 @@ use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 /// This type provides the functionality for handling `debug` related requests.
 pub struct DebugApi<Provider, Eth> {
     inner: Arc<DebugApiInner<Provider, Eth>>,
+    /// Maximum memory in bytes used by the struct logs of a single request, if any.
+    max_trace_memory_bytes: Option<usize>,
 }
 
 // === impl DebugApi ===
@@ -54,13 +61,28 @@ impl<Provider, Eth> DebugApi<Provider, Eth> {
     /// Create a new instance of the [`DebugApi`]
     pub fn new(provider: Provider, eth: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
         let inner = Arc::new(DebugApiInner { provider, eth_api: eth, blocking_task_guard });
-        Self { inner }
+        Self { inner, max_trace_memory_bytes: None }
+    }
+
+    /// Limits the memory used by the struct logs of a single trace request.
+    ///
+    /// The struct logs of all transactions traced by a request count towards the limit, a request
+    /// is aborted once they exceed it. The limit is enforced while tracing, i.e. the execution is
+    /// halted as soon as the estimated size of the recorded struct logs exceeds it.
+    pub const fn with_max_trace_memory(mut self, max_bytes: Option<usize>) -> Self {
+        self.max_trace_memory_bytes = max_bytes;
+        self
     }
 
     /// Access the underlying `Eth` API.
     pub fn eth_api(&self) -> &Eth {
         &self.inner.eth_api
     }
+
+    /// Returns a new memory budget for the struct logs of a single request.
+    const fn trace_memory_budget(&self) -> TraceMemoryBudget {
+        TraceMemoryBudget { max_bytes: self.max_trace_memory_bytes, used: 0 }
+    }
 }
 
 // === impl DebugApi ===
@@ -99,6 +121,7 @@ where
         self.eth_api()
             .spawn_with_state_at_block(at, move |state| {
                 let block_hash = at.as_block_hash();
+                let mut memory_budget = this.trace_memory_budget();
                 let mut results = Vec::with_capacity(transactions.len());
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
                 let mut transactions = transactions.into_iter().enumerate().peekable();
@@ -122,6 +145,7 @@ where
                             tx_hash: Some(tx_hash),
                             tx_index: Some(index),
                         }),
+                        &mut memory_budget,
                     )?;
 
                     results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
@@ -150,6 +174,7 @@ where
         let block = Block::decode(&mut rlp_block.as_ref())
             .map_err(BlockError::RlpDecodeRawBlock)
             .map_err(Eth::Error::from_eth_err)?;
+        self.eth_api().ensure_within_trace_window(block.number.into())?;
 
         let (cfg, block_env) = self.eth_api().evm_env_for_raw_block(&block.header).await?;
         // we trace on top the block's parent block
@@ -188,6 +213,7 @@ where
         block_id: BlockId,
         opts: GethDebugTracingOptions,
     ) -> Result<Vec<TraceResult>, Eth::Error> {
+        self.eth_api().ensure_within_trace_window(block_id)?;
         let block_hash = self
             .inner
             .provider
@@ -196,8 +222,8 @@ where
             .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
 
         let ((cfg, block_env, _), block) = futures::try_join!(
-            self.inner.eth_api.evm_env_at(block_hash.into()),
-            self.inner.eth_api.block_with_senders(block_id),
+            self.inner.eth_api.trace_evm_env_at(block_hash.into()),
+            self.inner.eth_api.trace_block_with_senders(block_id),
         )?;
 
         let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
@@ -227,7 +253,8 @@ where
             None => return Err(EthApiError::TransactionNotFound.into()),
             Some(res) => res,
         };
-        let (cfg, block_env, _) = self.inner.eth_api.evm_env_at(block.hash().into()).await?;
+        self.eth_api().ensure_within_trace_window(block.number.into())?;
+        let (cfg, block_env, _) = self.inner.eth_api.trace_evm_env_at(block.hash().into()).await?;
 
         // we need to get the state of the parent block because we're essentially replaying the
         // block the transaction is included in
@@ -270,6 +297,7 @@ where
                         tx_index: Some(index),
                         tx_hash: Some(tx.hash),
                     }),
+                    &mut this.trace_memory_budget(),
                 )
                 .map(|(trace, _)| trace)
             })
@@ -283,11 +311,14 @@ where
     ///  - `debug_traceCall` executes with __enabled__ basefee check, `eth_call` does not: <https://github.com/paradigmxyz/reth/issues/6240>
     pub async fn debug_trace_call(
         &self,
-        call: TransactionRequest,
+        mut call: TransactionRequest,
         block_id: Option<BlockId>,
         opts: GethDebugTracingCallOptions,
     ) -> Result<GethTrace, Eth::Error> {
+        self.inner.eth_api.check_gas_cap("debug_traceCall", &call);
+        self.inner.eth_api.apply_method_gas_cap("debug_traceCall", &mut call);
         let at = block_id.unwrap_or_default();
+        self.eth_api().ensure_within_trace_window(at)?;
         let GethDebugTracingCallOptions { tracing_options, state_overrides, block_overrides } =
             opts;
         let overrides = EvmOverrides::new(state_overrides, block_overrides.map(Box::new));
@@ -417,7 +448,7 @@ where
         // default structlog tracer
         let inspector_config = TracingInspectorConfig::from_geth_config(&config);
 
-        let mut inspector = TracingInspector::new(inspector_config);
+        let mut inspector = TraceMemoryInspector::new(inspector_config, self.trace_memory_budget());
 
         let (res, tx_gas_limit, inspector) = self
             .inner
@@ -427,6 +458,8 @@ where
                 Ok((res, env.tx.gas_limit, inspector))
             })
             .await?;
+        inspector.ensure_within_budget().map_err(Eth::Error::from_eth_err)?;
+        let (inspector, _) = inspector.into_parts();
         let gas_used = res.result.gas_used();
         let return_value = res.result.into_output().unwrap_or_default();
         let frame = inspector
@@ -449,20 +482,24 @@ where
         if bundles.is_empty() {
             return Err(EthApiError::InvalidParams(String::from("bundles are empty.")).into())
         }
+        for tx in bundles.iter().flat_map(|bundle| &bundle.transactions) {
+            self.inner.eth_api.check_gas_cap("debug_traceCallMany", tx);
+        }
 
         let StateContext { transaction_index, block_number } = state_context.unwrap_or_default();
         let transaction_index = transaction_index.unwrap_or_default();
 
         let target_block = block_number.unwrap_or_default();
+        self.eth_api().ensure_within_trace_window(target_block)?;
         let ((cfg, mut block_env, _), block) = futures::try_join!(
-            self.inner.eth_api.evm_env_at(target_block),
-            self.inner.eth_api.block_with_senders(target_block),
+            self.inner.eth_api.trace_evm_env_at(target_block),
+            self.inner.eth_api.trace_block_with_senders(target_block),
         )?;
 
         let opts = opts.unwrap_or_default();
         let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
         let GethDebugTracingCallOptions { tracing_options, mut state_overrides, .. } = opts;
-        let gas_limit = self.inner.eth_api.call_gas_limit();
+        let gas_limit = self.inner.eth_api.method_gas_limit("debug_traceCallMany");
 
         // we're essentially replaying the transactions in the block here, hence we need the state
         // that points to the beginning of the block, which is the state at the parent block
@@ -487,6 +524,7 @@ where
                 // the outer vec for the bundles
                 let mut all_bundles = Vec::with_capacity(bundles.len());
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                let mut memory_budget = this.trace_memory_budget();
 
                 if replay_block_txs {
                     // only need to replay the transactions in the block if not all transactions are
@@ -531,8 +569,13 @@ where
                             overrides,
                         )?;
 
-                        let (trace, state) =
-                            this.trace_transaction(tracing_options.clone(), env, &mut db, None)?;
+                        let (trace, state) = this.trace_transaction(
+                            tracing_options.clone(),
+                            env,
+                            &mut db,
+                            None,
+                            &mut memory_budget,
+                        )?;
 
                         // If there is more transactions, commit the database
                         // If there is no transactions, but more bundles, commit to the database too
@@ -560,9 +603,10 @@ where
         &self,
         block_id: BlockNumberOrTag,
     ) -> Result<HashMap<B256, Bytes>, Eth::Error> {
+        self.eth_api().ensure_within_trace_window(block_id.into())?;
         let ((cfg, block_env, _), maybe_block) = futures::try_join!(
-            self.inner.eth_api.evm_env_at(block_id.into()),
-            self.inner.eth_api.block_with_senders(block_id.into()),
+            self.inner.eth_api.trace_evm_env_at(block_id.into()),
+            self.inner.eth_api.trace_block_with_senders(block_id.into()),
         )?;
         let block = maybe_block.ok_or(EthApiError::UnknownBlockNumber)?;
 
@@ -662,6 +706,7 @@ where
         env: EnvWithHandlerCfg,
         db: &mut StateCacheDb<'_>,
         transaction_context: Option<TransactionContext>,
+        memory_budget: &mut TraceMemoryBudget,
     ) -> Result<(GethTrace, revm_primitives::EvmState), Eth::Error> {
         let GethDebugTracingOptions { config, tracer, tracer_config, .. } = opts;
 
@@ -748,9 +793,12 @@ where
         // default structlog tracer
         let inspector_config = TracingInspectorConfig::from_geth_config(&config);
 
-        let mut inspector = TracingInspector::new(inspector_config);
+        let mut inspector = TraceMemoryInspector::new(inspector_config, *memory_budget);
 
         let (res, env) = self.eth_api().inspect(db, env, &mut inspector)?;
+        inspector.ensure_within_budget().map_err(Eth::Error::from_eth_err)?;
+        let (inspector, budget) = inspector.into_parts();
+        *memory_budget = budget;
         let gas_used = res.result.gas_used();
         let return_value = res.result.into_output().unwrap_or_default();
         let frame = inspector
@@ -1157,7 +1205,170 @@ impl<Provider, Eth> std::fmt::Debug for DebugApi<Provider, Eth> {
 
 impl<Provider, Eth> Clone for DebugApi<Provider, Eth> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self { inner: Arc::clone(&self.inner), max_trace_memory_bytes: self.max_trace_memory_bytes }
+    }
+}
+
+/// Tracks the memory used by the struct logs of a single trace request, see
+/// [`DebugApi::with_max_trace_memory`].
+#[derive(Debug, Clone, Copy)]
+struct TraceMemoryBudget {
+    max_bytes: Option<usize>,
+    used: usize,
+}
+
+impl TraceMemoryBudget {
+    /// Adds the given number of bytes to the used memory.
+    ///
+    /// Returns `false` if the used memory exceeds the budget.
+    fn record(&mut self, bytes: usize) -> bool {
+        let Some(max_bytes) = self.max_bytes else { return true };
+        self.used = self.used.saturating_add(bytes);
+        self.used <= max_bytes
+    }
+}
+
+/// A [`TracingInspector`] that records the estimated memory of the struct logs of every step in a
+/// [`TraceMemoryBudget`] and halts execution once the budget is exceeded.
+///
+/// Once exceeded, every frame is halted, so the result of the execution must be discarded if
+/// [`TraceMemoryInspector::ensure_within_budget`] returns an error.
+#[derive(Debug)]
+struct TraceMemoryInspector {
+    inner: TracingInspector,
+    config: TracingInspectorConfig,
+    budget: TraceMemoryBudget,
+    exceeded: bool,
+}
+
+impl TraceMemoryInspector {
+    /// Creates a new inspector with the given config that records into the given budget.
+    fn new(config: TracingInspectorConfig, budget: TraceMemoryBudget) -> Self {
+        Self { inner: TracingInspector::new(config), config, budget, exceeded: false }
+    }
+
+    /// Returns an estimate of the heap memory used by the struct log of the current step.
+    fn step_size(&self, interp: &Interpreter) -> usize {
+        let mut size = std::mem::size_of::<StructLog>();
+        if self.config.record_stack_snapshots.is_full() {
+            size += interp.stack.len() * std::mem::size_of::<U256>();
+        }
+        if self.config.record_memory_snapshots {
+            // memory is returned as hex encoded words
+            size += interp.shared_memory.len() * 2;
+        }
+        if self.config.record_returndata_snapshots {
+            size += interp.return_data_buffer.len();
+        }
+        size
+    }
+
+    /// Returns [`EthApiError::TraceMemoryLimitExceeded`] if the execution was halted because the
+    /// budget was exceeded.
+    fn ensure_within_budget(&self) -> Result<(), EthApiError> {
+        match self.budget.max_bytes {
+            Some(max_bytes) if self.exceeded => {
+                Err(EthApiError::TraceMemoryLimitExceeded(max_bytes))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the recording inspector and the budget, including the memory of the struct logs it
+    /// recorded.
+    fn into_parts(self) -> (TracingInspector, TraceMemoryBudget) {
+        (self.inner, self.budget)
+    }
+}
+
+impl<DB: Database> Inspector<DB> for TraceMemoryInspector {
+    #[inline]
+    fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.inner.initialize_interp(interp, context)
+    }
+
+    #[inline]
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        if !self.exceeded {
+            self.inner.step(interp, context);
+            if !self.config.record_steps || self.budget.record(self.step_size(interp)) {
+                return
+            }
+            self.exceeded = true;
+        }
+        interp.instruction_result = InstructionResult::OutOfGas;
+    }
+
+    #[inline]
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.inner.step_end(interp, context)
+    }
+
+    #[inline]
+    fn log(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>, log: &Log) {
+        self.inner.log(interp, context, log)
+    }
+
+    #[inline]
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.inner.call(context, inputs)
+    }
+
+    #[inline]
+    fn call_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.inner.call_end(context, inputs, outcome)
+    }
+
+    #[inline]
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.inner.create(context, inputs)
+    }
+
+    #[inline]
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.inner.create_end(context, inputs, outcome)
+    }
+
+    #[inline]
+    fn eofcreate(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.inner.eofcreate(context, inputs)
+    }
+
+    #[inline]
+    fn eofcreate_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &EOFCreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.inner.eofcreate_end(context, inputs, outcome)
+    }
+
+    #[inline]
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        Inspector::<DB>::selfdestruct(&mut self.inner, contract, target, value)
     }
 }
 
@@ -1169,3 +1380,65 @@ struct DebugApiInner<Provider, Eth> {
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::TxKind;
+    use revm::{
+        db::EmptyDB,
+        inspector_handle_register,
+        primitives::{AccountInfo, Bytecode},
+        Evm,
+    };
+
+    /// Traces a call to a contract that loops until the gas is exhausted.
+    fn trace_loop(budget: TraceMemoryBudget, gas_limit: u64) -> TraceMemoryInspector {
+        // JUMPDEST PUSH1 0 JUMP
+        let code = Bytecode::new_raw(vec![0x5b, 0x60, 0x00, 0x56].into());
+        let contract = Address::with_last_byte(1);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+        );
+
+        let inspector = TraceMemoryInspector::new(TracingInspectorConfig::default_geth(), budget);
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(inspector)
+            .append_handler_register(inspector_handle_register)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = gas_limit;
+            })
+            .modify_block_env(|block| block.gas_limit = U256::MAX)
+            .build();
+        evm.transact().unwrap();
+        evm.context.external
+    }
+
+    #[test]
+    fn trace_memory_budget_halts_execution() {
+        let max_bytes = 10 * 1024;
+        let inspector =
+            trace_loop(TraceMemoryBudget { max_bytes: Some(max_bytes), used: 0 }, 10_000_000);
+        assert!(matches!(
+            inspector.ensure_within_budget(),
+            Err(EthApiError::TraceMemoryLimitExceeded(max)) if max == max_bytes
+        ));
+
+        // execution was halted at the step that exceeded the budget
+        let (inner, budget) = inspector.into_parts();
+        let steps = inner.traces().nodes()[0].trace.steps.len();
+        assert!(budget.used > max_bytes);
+        assert!(steps < 1_000);
+    }
+
+    #[test]
+    fn trace_memory_budget_unlimited() {
+        let inspector = trace_loop(TraceMemoryBudget { max_bytes: None, used: 0 }, 100_000);
+        assert!(inspector.ensure_within_budget().is_ok());
+        assert!(inspector.into_parts().0.traces().nodes()[0].trace.steps.len() > 1_000);
+    }
+}
@@ -1,4 +1,4 @@
-use jsonrpsee::core::RpcResult as Result;
+use jsonrpsee::{core::RpcResult as Result, RpcModule};
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256, U256, U64};
 use reth_rpc_api::{EngineEthApiServer, EthApiServer, EthFilterApiServer};
 /// Re-export for convenience
@@ -9,6 +9,17 @@ use reth_rpc_types::{
 };
 use tracing_futures::Instrument;
 
+/// The `eth_` methods registered by [`EngineEthApi::into_minimal_rpc`].
+///
+/// These are the methods a consensus client needs to follow the chain of the execution client.
+pub const MINIMAL_ENGINE_ETH_METHODS: &[&str] = &[
+    "eth_syncing",
+    "eth_chainId",
+    "eth_blockNumber",
+    "eth_getBlockByHash",
+    "eth_getBlockByNumber",
+];
+
 macro_rules! engine_span {
     () => {
         tracing::trace_span!(target: "rpc", "engine")
@@ -30,6 +41,26 @@ impl<Eth, EthFilter> EngineEthApi<Eth, EthFilter> {
     }
 }
 
+impl<Eth, EthFilter> EngineEthApi<Eth, EthFilter>
+where
+    Eth: EthApiServer,
+    EthFilter: EthFilterApiServer,
+{
+    /// Converts the handlers into an [`RpcModule`] that only registers the
+    /// [`MINIMAL_ENGINE_ETH_METHODS`], e.g. to reduce the surface of the auth server.
+    pub fn into_minimal_rpc(self) -> RpcModule<Self> {
+        let mut module = self.into_rpc();
+        let excluded = module
+            .method_names()
+            .filter(|method| !MINIMAL_ENGINE_ETH_METHODS.contains(method))
+            .collect::<Vec<_>>();
+        for method in excluded {
+            module.remove_method(method);
+        }
+        module
+    }
+}
+
 #[async_trait::async_trait]
 impl<Eth, EthFilter> EngineEthApiServer for EngineEthApi<Eth, EthFilter>
 where
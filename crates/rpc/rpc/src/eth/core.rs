@@ -1,7 +1,7 @@
 //! Implementation of the [`jsonrpsee`] generated [`EthApiServer`](crate::EthApi) trait
 //! Handles RPC requests for the `eth_` namespace.
 
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
 
 use derive_more::Deref;
 use reth_node_api::{BuilderProvider, FullNodeComponents};
@@ -12,8 +12,8 @@ use reth_rpc_eth_api::{
     EthApiTypes, RawTransactionForwarder,
 };
 use reth_rpc_eth_types::{
-    EthApiBuilderCtx, EthApiError, EthStateCache, FeeHistoryCache, GasCap, GasPriceOracle,
-    PendingBlock,
+    EthApiBuilderCtx, EthApiError, EthStateCache, FeeHistoryCache, GasCap, GasCapMetrics,
+    GasPriceOracle, PendingBlock, ProofPermitMetrics,
 };
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
@@ -91,8 +91,7 @@ where
         Tasks: TaskSpawner + Clone + 'static,
         Events: CanonStateSubscriptions,
     {
-        let blocking_task_pool =
-            BlockingTaskPool::build().expect("failed to build blocking task pool");
+        let blocking_task_pool = ctx.blocking_task_pool();
 
         let inner = EthApiInner::new(
             ctx.provider.clone(),
@@ -108,7 +107,17 @@ where
             ctx.executor.clone(),
             None,
             ctx.config.proof_permits,
-        );
+        )
+        .with_gas_cap_metrics(ctx.gas_cap_metrics.clone())
+        .with_proof_permit_metrics(ctx.proof_permit_metrics.clone())
+        .with_max_fee_history_blocks(ctx.config.max_fee_history_blocks)
+        .with_call_timeout(ctx.config.call_timeout)
+        .with_estimate_gas_buffer_percent(ctx.config.estimate_gas_buffer_percent)
+        .with_pending_block_cache_ttl(ctx.config.pending_block_cache_ttl)
+        .with_chain_id_override(ctx.config.chain_id_override)
+        .with_trace_block_window(ctx.config.trace_block_window)
+        .with_method_gas_caps(ctx.config.method_gas_caps.clone())
+        .with_trace_cache(ctx.trace_cache.clone());
 
         Self { inner: Arc::new(inner) }
     }
@@ -182,12 +191,33 @@ pub struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     signers: parking_lot::RwLock<Vec<Box<dyn EthSigner>>>,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
+    /// Separate cache for tracing calls, if configured.
+    trace_cache: Option<EthStateCache>,
     /// The async gas oracle frontend for gas price suggestions
     gas_oracle: GasPriceOracle<Provider>,
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     gas_cap: u64,
+    /// Gas limits of specific RPC methods that override `gas_cap`, by method name.
+    method_gas_caps: BTreeMap<String, u64>,
+    /// Maximum duration of a single `eth_call` execution.
+    call_timeout: Option<Duration>,
+    /// Percentage added to the gas limit found by `eth_estimateGas`.
+    estimate_gas_buffer_percent: u64,
+    /// How long an assembled pending block is served before it is assembled again.
+    pending_block_cache_ttl: Option<Duration>,
+    /// Chain id reported instead of the network's chain id, if set.
+    chain_id_override: Option<u64>,
+    /// The maximum number of blocks into the past that can be traced, if limited.
+    trace_block_window: Option<u64>,
+    /// Metrics for requests that exceed the gas cap.
+    gas_cap_metrics: GasCapMetrics,
+    /// Metrics for the permits of getproof calls.
+    proof_permit_metrics: ProofPermitMetrics,
     /// The maximum number of blocks into the past for generating state proofs.
     eth_proof_window: u64,
+    /// The maximum number of blocks that can be requested in a single fee history call, if
+    /// enforced.
+    max_fee_history_blocks: Option<u64>,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
@@ -246,7 +276,17 @@ where
             eth_cache,
             gas_oracle,
             gas_cap: gas_cap.into().into(),
+            method_gas_caps: BTreeMap::new(),
+            gas_cap_metrics: Default::default(),
+            proof_permit_metrics: Default::default(),
+            call_timeout: None,
+            estimate_gas_buffer_percent: 0,
+            pending_block_cache_ttl: None,
+            chain_id_override: None,
+            trace_block_window: None,
+            trace_cache: None,
             eth_proof_window,
+            max_fee_history_blocks: None,
             starting_block,
             task_spawner: Box::new(task_spawner),
             pending_block: Default::default(),
@@ -260,6 +300,68 @@ where
 }
 
 impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, EvmConfig> {
+    /// Sets the metrics that record requests exceeding the gas cap.
+    pub fn with_gas_cap_metrics(mut self, gas_cap_metrics: GasCapMetrics) -> Self {
+        self.gas_cap_metrics = gas_cap_metrics;
+        self
+    }
+
+    /// Sets the metrics for the permits of getproof calls.
+    pub fn with_proof_permit_metrics(mut self, proof_permit_metrics: ProofPermitMetrics) -> Self {
+        self.proof_permit_metrics = proof_permit_metrics;
+        self
+    }
+
+    /// Sets the maximum number of blocks that can be requested in a single fee history call.
+    ///
+    /// If `None` then the block count of larger requests is truncated instead.
+    pub const fn with_max_fee_history_blocks(mut self, max_blocks: Option<u64>) -> Self {
+        self.max_fee_history_blocks = max_blocks;
+        self
+    }
+
+    /// Sets the maximum duration of a single `eth_call` execution.
+    pub const fn with_call_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.call_timeout = timeout;
+        self
+    }
+
+    /// Sets the percentage that is added to the gas limit found by `eth_estimateGas`.
+    pub const fn with_estimate_gas_buffer_percent(mut self, buffer_percent: u64) -> Self {
+        self.estimate_gas_buffer_percent = buffer_percent;
+        self
+    }
+
+    /// Sets how long an assembled pending block is served before it is assembled again.
+    pub const fn with_pending_block_cache_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.pending_block_cache_ttl = ttl;
+        self
+    }
+
+    /// Sets the chain id that is reported instead of the network's chain id.
+    pub const fn with_chain_id_override(mut self, chain_id: Option<u64>) -> Self {
+        self.chain_id_override = chain_id;
+        self
+    }
+
+    /// Sets the gas limits of specific RPC methods that override the gas cap, by method name.
+    pub fn with_method_gas_caps(mut self, method_gas_caps: BTreeMap<String, u64>) -> Self {
+        self.method_gas_caps = method_gas_caps;
+        self
+    }
+
+    /// Sets the maximum number of blocks into the past that can be traced.
+    pub const fn with_trace_block_window(mut self, window: Option<u64>) -> Self {
+        self.trace_block_window = window;
+        self
+    }
+
+    /// Sets a separate cache for tracing calls. If `None`, tracing calls use the regular cache.
+    pub fn with_trace_cache(mut self, trace_cache: Option<EthStateCache>) -> Self {
+        self.trace_cache = trace_cache;
+        self
+    }
+
     /// Returns a handle to data on disk.
     #[inline]
     pub const fn provider(&self) -> &Provider {
@@ -272,6 +374,13 @@ impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, Ev
         &self.eth_cache
     }
 
+    /// Returns the cache for tracing calls, which is the regular cache unless a separate one is
+    /// configured.
+    #[inline]
+    pub fn trace_cache(&self) -> &EthStateCache {
+        self.trace_cache.as_ref().unwrap_or(&self.eth_cache)
+    }
+
     /// Returns a handle to the pending block.
     #[inline]
     pub const fn pending_block(&self) -> &Mutex<Option<PendingBlock>> {
@@ -314,6 +423,18 @@ impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, Ev
         self.gas_cap
     }
 
+    /// Returns a handle to the gas cap metrics.
+    #[inline]
+    pub const fn gas_cap_metrics(&self) -> &GasCapMetrics {
+        &self.gas_cap_metrics
+    }
+
+    /// Returns a handle to the metrics for the permits of getproof calls.
+    #[inline]
+    pub const fn proof_permit_metrics(&self) -> &ProofPermitMetrics {
+        &self.proof_permit_metrics
+    }
+
     /// Returns a handle to the gas oracle.
     #[inline]
     pub const fn gas_oracle(&self) -> &GasPriceOracle<Provider> {
@@ -350,6 +471,49 @@ impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, Ev
         self.eth_proof_window
     }
 
+    /// The maximum duration of a single `eth_call` execution.
+    #[inline]
+    pub const fn call_timeout(&self) -> Option<Duration> {
+        self.call_timeout
+    }
+
+    /// The percentage that is added to the gas limit found by `eth_estimateGas`.
+    #[inline]
+    pub const fn estimate_gas_buffer_percent(&self) -> u64 {
+        self.estimate_gas_buffer_percent
+    }
+
+    /// How long an assembled pending block is served before it is assembled again.
+    #[inline]
+    pub const fn pending_block_cache_ttl(&self) -> Option<Duration> {
+        self.pending_block_cache_ttl
+    }
+
+    /// The chain id reported instead of the network's chain id, if set.
+    #[inline]
+    pub const fn chain_id_override(&self) -> Option<u64> {
+        self.chain_id_override
+    }
+
+    /// Returns the gas limit of the given RPC method, if it overrides the gas cap.
+    #[inline]
+    pub fn method_gas_cap(&self, method: &str) -> Option<u64> {
+        self.method_gas_caps.get(method).copied()
+    }
+
+    /// The maximum number of blocks into the past that can be traced, if limited.
+    #[inline]
+    pub const fn trace_block_window(&self) -> Option<u64> {
+        self.trace_block_window
+    }
+
+    /// The maximum number of blocks that can be requested in a single fee history call, if
+    /// enforced.
+    #[inline]
+    pub const fn max_fee_history_blocks(&self) -> Option<u64> {
+        self.max_fee_history_blocks
+    }
+
     /// Returns reference to [`BlockingTaskGuard`].
     #[inline]
     pub const fn blocking_task_guard(&self) -> &BlockingTaskGuard {
@@ -367,18 +531,24 @@ impl<Provider, Pool, Network, EvmConfig> UpdateRawTxForwarder
 
 #[cfg(test)]
 mod tests {
+    use std::{sync::Arc, time::Duration};
+
     use jsonrpsee_types::error::INVALID_PARAMS_CODE;
     use reth_chainspec::BaseFeeParams;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
-    use reth_primitives::{Block, BlockNumberOrTag, Header, TransactionSigned, B256, U64};
+    use reth_primitives::{Block, BlockId, BlockNumberOrTag, Header, TransactionSigned, B256, U64};
     use reth_provider::{
         test_utils::{MockEthProvider, NoopProvider},
         BlockReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory,
     };
-    use reth_rpc_eth_api::EthApiServer;
+    use reth_rpc_eth_api::{
+        helpers::{LoadBlock, SpawnBlocking, Trace},
+        EthApiServer,
+    };
     use reth_rpc_eth_types::{
-        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+        Cancellation, EthApiError, EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig,
+        GasPriceOracle,
     };
     use reth_rpc_server_types::constants::{DEFAULT_ETH_PROOF_WINDOW, DEFAULT_PROOF_PERMITS};
     use reth_rpc_types::FeeHistory;
@@ -662,4 +832,122 @@ mod tests {
             "all: no percentiles were requested, so there should be no rewards result"
         );
     }
+
+    /// Requests for more blocks than the configured maximum should be rejected
+    #[tokio::test]
+    async fn test_fee_history_max_blocks() {
+        let block_count = 10;
+        let newest_block = 1337;
+        let oldest_block = None;
+
+        let (eth_api, _, _) =
+            prepare_eth_api(newest_block, oldest_block, block_count, MockEthProvider::default());
+        // without a maximum, the block count is only truncated
+        let response = <EthApi<_, _, _, _> as EthApiServer>::fee_history(
+            &eth_api,
+            U64::from(6),
+            newest_block.into(),
+            None,
+        )
+        .await;
+        assert!(response.is_ok(), "max: requests are not rejected by default");
+
+        let inner = Arc::into_inner(eth_api.inner).unwrap().with_max_fee_history_blocks(Some(5));
+        let eth_api = EthApi { inner: Arc::new(inner) };
+
+        let fee_history =
+            eth_api.fee_history(U64::from(5), newest_block.into(), None).await.unwrap();
+        assert_eq!(fee_history.gas_used_ratio.len(), 5, "max: should return all requested blocks");
+
+        let response = <EthApi<_, _, _, _> as EthApiServer>::fee_history(
+            &eth_api,
+            U64::from(6),
+            newest_block.into(),
+            None,
+        )
+        .await;
+        assert!(response.is_err());
+        let error_object = response.unwrap_err();
+        assert_eq!(error_object.code(), INVALID_PARAMS_CODE);
+    }
+
+    /// Requests the receipts of a block by number and by hash
+    #[tokio::test]
+    async fn test_block_receipts() {
+        let mock_provider = MockEthProvider::default();
+        let hash = B256::with_last_byte(1);
+        let block =
+            Block { header: Header { number: 1, ..Default::default() }, ..Default::default() };
+        mock_provider.add_block(hash, block);
+        mock_provider.add_receipts(hash, Vec::new());
+
+        let eth_api = build_test_eth_api(mock_provider);
+
+        for block_id in [BlockId::from(1u64), BlockId::from(hash)] {
+            let receipts = <EthApi<_, _, _, _> as EthApiServer>::block_receipts(&eth_api, block_id)
+                .await
+                .unwrap();
+            assert!(receipts.expect("block exists").is_empty(), "receipts of {block_id:?}");
+        }
+
+        let receipts =
+            <EthApi<_, _, _, _> as EthApiServer>::block_receipts(&eth_api, BlockId::from(2u64))
+                .await
+                .unwrap();
+        assert!(receipts.is_none());
+    }
+
+    /// Tracing calls load blocks and EVM environments from the separate trace cache
+    #[tokio::test]
+    async fn test_trace_cache() {
+        let hash = B256::with_last_byte(1);
+        let block =
+            Block { header: Header { number: 1, ..Default::default() }, ..Default::default() };
+        // only the provider of the trace cache knows the block
+        let trace_provider = MockEthProvider::default();
+        trace_provider.add_block(hash, block);
+
+        let eth_api = build_test_eth_api(MockEthProvider::default());
+        assert!(eth_api.trace_block_with_senders(hash.into()).await.unwrap().is_none());
+
+        let trace_cache =
+            EthStateCache::spawn(trace_provider, Default::default(), EthEvmConfig::default());
+        let inner = Arc::into_inner(eth_api.inner).unwrap().with_trace_cache(Some(trace_cache));
+        let eth_api = EthApi { inner: Arc::new(inner) };
+
+        let block = eth_api.trace_block_with_senders(hash.into()).await.unwrap();
+        assert_eq!(block.expect("block is in the trace cache").number, 1);
+        assert!(eth_api.block_with_senders(hash.into()).await.unwrap().is_none());
+
+        let (_, _, block_id) = eth_api.trace_evm_env_at(hash.into()).await.unwrap();
+        assert_eq!(block_id, BlockId::from(hash));
+        assert!(matches!(
+            eth_api.trace_evm_env_at(BlockId::from(2u64)).await,
+            Err(EthApiError::UnknownBlockNumber)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_tracing_cancelled_on_drop() {
+        let eth_api = build_test_eth_api(NoopProvider::default());
+
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+        let (result_tx, result_rx) = std::sync::mpsc::channel();
+        let fut = eth_api.spawn_tracing(move |_| {
+            let _ = started_tx.send(());
+            let err = loop {
+                if let Err(err) = Cancellation::ensure_current_not_cancelled() {
+                    break err
+                }
+                std::thread::sleep(Duration::from_millis(1));
+            };
+            let _ = result_tx.send(err);
+            Ok(())
+        });
+
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        drop(fut);
+        let err = result_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(matches!(err, EthApiError::Cancelled));
+    }
 }
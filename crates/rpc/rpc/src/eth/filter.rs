@@ -12,8 +12,10 @@ use std::{
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
 use reth_chainspec::ChainInfo;
-use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
+use reth_primitives::{Bloom, IntoRecoveredTransaction, Receipt, StaticFileSegment, TxHash};
+use reth_provider::{
+    providers::StaticFileProvider, BlockIdReader, BlockReader, EvmEnvProvider, ProviderError,
+};
 use reth_rpc_eth_api::EthFilterApiServer;
 use reth_rpc_eth_types::{
     logs_utils::{self, append_matching_block_logs},
@@ -21,8 +23,8 @@ use reth_rpc_eth_types::{
 };
 use reth_rpc_server_types::ToRpcResult;
 use reth_rpc_types::{
-    BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log,
-    PendingTransactionFilterKind,
+    BlockNumHash, BloomFilter, Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams,
+    Log, PendingTransactionFilterKind,
 };
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
@@ -36,6 +38,9 @@ use tracing::trace;
 const MAX_HEADERS_RANGE: u64 = 1_000; // with ~530bytes per header this is ~500kb
 
 /// `Eth` filter RPC implementation.
+///
+/// This type is a cheaply cloneable handle, all clones share the same installed filters and
+/// stale filter cleanup task.
 pub struct EthFilter<Provider, Pool> {
     /// All nested fields bundled together
     inner: Arc<EthFilterInner<Provider, Pool>>,
@@ -53,6 +58,9 @@ where
     ///
     /// See also [`EthFilterConfig`].
     ///
+    /// If a [`StaticFileProvider`] is given, receipts of blocks that were moved to static files
+    /// are read from it directly, instead of going through the cache.
+    ///
     /// This also spawns a task that periodically clears stale filters.
     pub fn new(
         provider: Provider,
@@ -60,9 +68,16 @@ where
         eth_cache: EthStateCache,
         config: EthFilterConfig,
         task_spawner: Box<dyn TaskSpawner>,
+        static_file_provider: Option<StaticFileProvider>,
     ) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            max_changes_per_poll,
+            use_bloom_prefilter,
+            stale_filter_ttl,
+            reap_interval,
+        } = config;
         let inner = EthFilterInner {
             provider,
             active_filters: Default::default(),
@@ -72,9 +87,14 @@ where
             max_headers_range: MAX_HEADERS_RANGE,
             task_spawner,
             stale_filter_ttl,
+            reap_interval,
             // if not set, use the max value, which is effectively no limit
             max_blocks_per_filter: max_blocks_per_filter.unwrap_or(u64::MAX),
             max_logs_per_response: max_logs_per_response.unwrap_or(usize::MAX),
+            // a poll always returns at least one change, so that polling makes progress
+            max_changes_per_poll: max_changes_per_poll.unwrap_or(usize::MAX).max(1),
+            use_bloom_prefilter,
+            static_file_provider,
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -95,10 +115,16 @@ where
         &self.inner.active_filters
     }
 
-    /// Endless future that [`Self::clear_stale_filters`] every `stale_filter_ttl` interval.
+    /// Returns a [`FilterSummary`] of every currently active filter, see
+    /// [`ActiveFilters::summaries`].
+    pub async fn filter_summaries(&self) -> Vec<FilterSummary> {
+        self.active_filters().summaries(Instant::now()).await
+    }
+
+    /// Endless future that [`Self::clear_stale_filters`] every `reap_interval`.
     /// Nonetheless, this endless future frees the thread at every await point.
     async fn watch_and_clear_stale_filters(&self) {
-        let mut interval = tokio::time::interval(self.inner.stale_filter_ttl);
+        let mut interval = tokio::time::interval(self.inner.reap_interval);
         interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
         loop {
             interval.tick().await;
@@ -129,13 +155,17 @@ where
     <Pool as TransactionPool>::Transaction: 'static,
 {
     /// Returns all the filter changes for the given id, if any
+    ///
+    /// At most `max_changes_per_poll` changes are returned, the remaining changes are retained by
+    /// the filter and returned by the next poll.
     pub async fn filter_changes(&self, id: FilterId) -> Result<FilterChanges, EthFilterError> {
         let info = self.inner.provider.chain_info()?;
         let best_number = info.best_number;
 
         // start_block is the block from which we should start fetching changes, the next block from
         // the last time changes were polled, in other words the best block at last poll + 1
-        let (start_block, kind) = {
+        let limit = self.inner.max_changes_per_poll;
+        let (start_block, next_block, kind) = {
             let mut filters = self.inner.active_filters.inner.lock().await;
             let filter = filters.get_mut(&id).ok_or(EthFilterError::FilterNotFound(id))?;
 
@@ -146,24 +176,31 @@ where
 
             // update filter
             // we fetch all changes from [filter.block..best_block], so we advance the filter's
-            // block to `best_block +1`, the next from which we should start fetching changes again
-            let mut block = best_number + 1;
-            std::mem::swap(&mut filter.block, &mut block);
+            // block to `best_block +1`, the next from which we should start fetching changes again.
+            // Block filters return at most `limit` hashes, so their block is only advanced past the
+            // returned blocks
+            let next_block = match filter.kind {
+                FilterKind::Block => {
+                    let max_blocks = u64::try_from(limit).unwrap_or(u64::MAX);
+                    best_number.min(filter.block.saturating_add(max_blocks - 1)) + 1
+                }
+                _ => best_number + 1,
+            };
+            let block = std::mem::replace(&mut filter.block, next_block);
             filter.last_poll_timestamp = Instant::now();
 
-            (block, filter.kind.clone())
+            (block, next_block, filter.kind.clone())
         };
 
         match kind {
-            FilterKind::PendingTransaction(filter) => Ok(filter.drain().await),
+            FilterKind::PendingTransaction(filter) => Ok(filter.drain(limit).await),
             FilterKind::Block => {
                 // Note: we need to fetch the block hashes from inclusive range
-                // [start_block..best_block]
-                let end_block = best_number + 1;
+                // [start_block..next_block - 1]
                 let block_hashes = self
                     .inner
                     .provider
-                    .canonical_hashes_range(start_block, end_block)
+                    .canonical_hashes_range(start_block, next_block)
                     .map_err(|_| EthApiError::UnknownBlockNumber)?;
                 Ok(FilterChanges::Hashes(block_hashes))
             }
@@ -178,7 +215,12 @@ where
                             .map(|num| self.inner.provider.convert_block_number(num))
                             .transpose()?
                             .flatten();
-                        logs_utils::get_filter_block_range(from, to, start_block, info)
+                        let (from, to) =
+                            logs_utils::get_filter_block_range(from, to, start_block, info);
+                        // only the changes since the last poll are returned, so an earlier
+                        // `fromBlock` must not rewind the filter, e.g. to the first page of a poll
+                        // that was cut short
+                        (from.max(start_block), to)
                     }
                     FilterBlockOption::AtBlockHash(_) => {
                         // blockHash is equivalent to fromBlock = toBlock = the block number with
@@ -187,10 +229,32 @@ where
                         (start_block, best_number)
                     }
                 };
-                let logs = self
+                if from_block_number > to_block_number {
+                    // the `toBlock` of the filter was reached by an earlier poll
+                    return Ok(FilterChanges::Empty)
+                }
+                let (logs, remaining_from) = self
                     .inner
-                    .get_logs_in_block_range(&filter, from_block_number, to_block_number, info)
+                    .get_logs_page_in_block_range(
+                        &filter,
+                        from_block_number,
+                        to_block_number,
+                        info,
+                        limit,
+                    )
                     .await?;
+
+                if let Some(remaining_from) = remaining_from {
+                    // the page is full, so the next poll continues at the first block that was
+                    // not scanned, unless the filter was polled concurrently
+                    let mut filters = self.inner.active_filters.inner.lock().await;
+                    if let Some(filter) =
+                        filters.get_mut(&id).filter(|filter| filter.block == next_block)
+                    {
+                        filter.block = remaining_from;
+                    }
+                }
+
                 Ok(FilterChanges::Logs(logs))
             }
         }
@@ -328,6 +392,10 @@ struct EthFilterInner<Provider, Pool> {
     max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a response
     max_logs_per_response: usize,
+    /// Maximum number of changes returned by a single filter poll
+    max_changes_per_poll: usize,
+    /// Whether blocks are skipped if their logs bloom can't match the filter
+    use_bloom_prefilter: bool,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
     /// maximum number of headers to read at once for range filter
@@ -336,6 +404,10 @@ struct EthFilterInner<Provider, Pool> {
     task_spawner: Box<dyn TaskSpawner>,
     /// Duration since the last filter poll, after which the filter is considered stale
     stale_filter_ttl: Duration,
+    /// Interval at which stale filters are reaped
+    reap_interval: Duration,
+    /// Provider to read receipts of blocks that were moved to static files from, if set
+    static_file_provider: Option<StaticFileProvider>,
 }
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
@@ -403,11 +475,13 @@ where
         let last_poll_block_number = self.provider.best_block_number().to_rpc_result()?;
         let id = FilterId::from(self.id_provider.next_id());
         let mut filters = self.active_filters.inner.lock().await;
+        let now = Instant::now();
         filters.insert(
             id.clone(),
             ActiveFilter {
                 block: last_poll_block_number,
-                last_poll_timestamp: Instant::now(),
+                installed_at: now,
+                last_poll_timestamp: now,
                 kind,
             },
         );
@@ -426,6 +500,30 @@ where
         to_block: u64,
         chain_info: ChainInfo,
     ) -> Result<Vec<Log>, EthFilterError> {
+        let (logs, _) = self
+            .get_logs_page_in_block_range(filter, from_block, to_block, chain_info, usize::MAX)
+            .await?;
+        Ok(logs)
+    }
+
+    /// Returns the logs in the given _inclusive_ range that match the filter, up to `page_size`
+    /// logs.
+    ///
+    /// The logs of a block are never split, so blocks are scanned until the logs of the next block
+    /// would exceed the page size, in which case the number of that block is returned as well.
+    /// The first block with matching logs is always included, even if it exceeds the page size.
+    ///
+    /// Returns an error if:
+    ///  - underlying database error
+    ///  - amount of matches exceeds configured limit
+    async fn get_logs_page_in_block_range(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+        chain_info: ChainInfo,
+        page_size: usize,
+    ) -> Result<(Vec<Log>, Option<u64>), EthFilterError> {
         trace!(target: "rpc::eth::filter", from=from_block, to=to_block, ?filter, "finding logs in range");
         let best_number = chain_info.best_number;
 
@@ -458,7 +556,7 @@ where
                     block.header.timestamp,
                 )?;
             }
-            return Ok(all_logs)
+            return Ok((all_logs, None))
         }
 
         // derive bloom filters from filter input, so we can check headers for matching logs
@@ -466,7 +564,7 @@ where
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
 
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
-        // filter
+        // filter, or of every block if the bloom pre-filter is disabled
         for (from, to) in
             BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
         {
@@ -474,8 +572,8 @@ where
 
             for (idx, header) in headers.iter().enumerate() {
                 // only if filter matches
-                if FilteredParams::matches_address(header.logs_bloom, &address_filter) &&
-                    FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
+                if !self.use_bloom_prefilter ||
+                    bloom_may_match(header.logs_bloom, &address_filter, &topics_filter)
                 {
                     // these are consecutive headers, so we can use the parent hash of the next
                     // block to get the current header's hash
@@ -487,9 +585,12 @@ where
                             .ok_or(ProviderError::HeaderNotFound(header.number.into()))?,
                     };
 
-                    if let Some(receipts) = self.eth_cache.get_receipts(block_hash).await? {
+                    if let Some(receipts) =
+                        self.block_receipts(BlockNumHash::new(header.number, block_hash)).await?
+                    {
+                        let mut block_logs = Vec::new();
                         append_matching_block_logs(
-                            &mut all_logs,
+                            &mut block_logs,
                             &self.provider,
                             &filter_params,
                             BlockNumHash::new(header.number, block_hash),
@@ -498,6 +599,11 @@ where
                             header.timestamp,
                         )?;
 
+                        if !all_logs.is_empty() && all_logs.len() + block_logs.len() > page_size {
+                            return Ok((all_logs, Some(header.number)))
+                        }
+                        all_logs.append(&mut block_logs);
+
                         // size check but only if range is multiple blocks, so we always return all
                         // logs of a single block
                         let is_multi_block_range = from_block != to_block;
@@ -511,7 +617,30 @@ where
             }
         }
 
-        Ok(all_logs)
+        Ok((all_logs, None))
+    }
+
+    /// Returns the receipts of the given block.
+    ///
+    /// If a static file provider is set, receipts of blocks that were moved to static files are
+    /// read from there directly. Otherwise, they are fetched through the cache.
+    async fn block_receipts(
+        &self,
+        block: BlockNumHash,
+    ) -> Result<Option<Arc<Vec<Receipt>>>, EthFilterError> {
+        if let Some(static_file_provider) = self.static_file_provider.as_ref().filter(|provider| {
+            provider
+                .get_highest_static_file_block(StaticFileSegment::Receipts)
+                .is_some_and(|highest| block.number <= highest)
+        }) {
+            let Some(body_indices) = self.provider.block_body_indices(block.number)? else {
+                return Ok(None)
+            };
+            let receipts = static_file_provider.read_receipts(body_indices.tx_num_range())?;
+            return Ok(Some(Arc::new(receipts)))
+        }
+
+        Ok(self.eth_cache.get_receipts(block.hash).await?)
     }
 }
 
@@ -521,11 +650,62 @@ pub struct ActiveFilters {
     inner: Arc<Mutex<HashMap<FilterId, ActiveFilter>>>,
 }
 
+impl ActiveFilters {
+    /// Returns a [`FilterSummary`] of every filter, with durations measured up to the given
+    /// instant.
+    ///
+    /// Filters that haven't been polled for a long time, relative to the configured
+    /// `stale_filter_ttl`, point to clients that install filters and never poll them.
+    pub async fn summaries(&self, now: Instant) -> Vec<FilterSummary> {
+        self.inner
+            .lock()
+            .await
+            .iter()
+            .map(|(id, filter)| FilterSummary {
+                id: id.clone(),
+                kind: match filter.kind {
+                    FilterKind::Log(_) => FilterSummaryKind::Log,
+                    FilterKind::Block => FilterSummaryKind::Block,
+                    FilterKind::PendingTransaction(_) => FilterSummaryKind::PendingTransaction,
+                },
+                age: now.saturating_duration_since(filter.installed_at),
+                since_last_poll: now.saturating_duration_since(filter.last_poll_timestamp),
+            })
+            .collect()
+    }
+}
+
+/// Summary of an active filter, see [`ActiveFilters::summaries`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterSummary {
+    /// The identifier of the filter.
+    pub id: FilterId,
+    /// What kind of filter it is.
+    pub kind: FilterSummaryKind,
+    /// Time since the filter was installed.
+    pub age: Duration,
+    /// Time since the filter was polled last, or installed if it was never polled.
+    pub since_last_poll: Duration,
+}
+
+/// The kind of an active filter, see [`FilterSummary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterSummaryKind {
+    /// A log filter, installed with `eth_newFilter`.
+    Log,
+    /// A new block filter, installed with `eth_newBlockFilter`.
+    Block,
+    /// A pending transaction filter, installed with `eth_newPendingTransactionFilter`.
+    PendingTransaction,
+}
+
 /// An installed filter
 #[derive(Debug)]
 struct ActiveFilter {
     /// At which block the filter was polled last.
     block: u64,
+    /// When the filter was installed.
+    installed_at: Instant,
     /// Last time this filter was polled.
     last_poll_timestamp: Instant,
     /// What kind of filter it is.
@@ -543,12 +723,14 @@ impl PendingTransactionsReceiver {
         Self { txs_receiver: Arc::new(Mutex::new(receiver)) }
     }
 
-    /// Returns all new pending transactions received since the last poll.
-    async fn drain(&self) -> FilterChanges {
+    /// Returns up to `limit` new pending transactions received since the last poll, the remaining
+    /// transactions are returned by the next poll.
+    async fn drain(&self, limit: usize) -> FilterChanges {
         let mut pending_txs = Vec::new();
         let mut prepared_stream = self.txs_receiver.lock().await;
 
-        while let Ok(tx_hash) = prepared_stream.try_recv() {
+        while pending_txs.len() < limit {
+            let Ok(tx_hash) = prepared_stream.try_recv() else { break };
             pending_txs.push(tx_hash);
         }
 
@@ -572,12 +754,14 @@ where
         Self { txs_stream: Arc::new(Mutex::new(stream)) }
     }
 
-    /// Returns all new pending transactions received since the last poll.
-    async fn drain(&self) -> FilterChanges {
+    /// Returns up to `limit` new pending transactions received since the last poll, the remaining
+    /// transactions are returned by the next poll.
+    async fn drain(&self, limit: usize) -> FilterChanges {
         let mut pending_txs = Vec::new();
         let mut prepared_stream = self.txs_stream.lock().await;
 
-        while let Ok(tx) = prepared_stream.try_recv() {
+        while pending_txs.len() < limit {
+            let Ok(tx) = prepared_stream.try_recv() else { break };
             pending_txs.push(reth_rpc_types_compat::transaction::from_recovered(
                 tx.transaction.to_recovered_transaction(),
             ))
@@ -589,7 +773,7 @@ where
 /// Helper trait for [FullTransactionsReceiver] to erase the `Transaction` type.
 #[async_trait]
 trait FullTransactionsFilter: fmt::Debug + Send + Sync + Unpin + 'static {
-    async fn drain(&self) -> FilterChanges;
+    async fn drain(&self, limit: usize) -> FilterChanges;
 }
 
 #[async_trait]
@@ -597,8 +781,8 @@ impl<T> FullTransactionsFilter for FullTransactionsReceiver<T>
 where
     T: PoolTransaction + 'static,
 {
-    async fn drain(&self) -> FilterChanges {
-        Self::drain(self).await
+    async fn drain(&self, limit: usize) -> FilterChanges {
+        Self::drain(self, limit).await
     }
 }
 
@@ -614,10 +798,10 @@ enum PendingTransactionKind {
 }
 
 impl PendingTransactionKind {
-    async fn drain(&self) -> FilterChanges {
+    async fn drain(&self, limit: usize) -> FilterChanges {
         match self {
-            Self::Hashes(receiver) => receiver.drain().await,
-            Self::FullTransaction(receiver) => receiver.drain().await,
+            Self::Hashes(receiver) => receiver.drain(limit).await,
+            Self::FullTransaction(receiver) => receiver.drain(limit).await,
         }
     }
 }
@@ -629,6 +813,20 @@ enum FilterKind {
     PendingTransaction(PendingTransactionKind),
 }
 
+/// Returns `false` if a block with the given logs bloom can't contain logs that match the bloom
+/// filters of a query's addresses and topics.
+///
+/// A bloom only yields false positives, so `true` does not guarantee that the block contains
+/// matching logs.
+fn bloom_may_match(
+    bloom: Bloom,
+    address_filter: &BloomFilter,
+    topics_filter: &[BloomFilter],
+) -> bool {
+    FilteredParams::matches_address(bloom, address_filter) &&
+        FilteredParams::matches_topics(bloom, topics_filter)
+}
+
 /// An iterator that yields _inclusive_ block ranges of a given step size
 #[derive(Debug)]
 struct BlockRangeInclusiveIter {
@@ -660,6 +858,15 @@ impl Iterator for BlockRangeInclusiveIter {
 mod tests {
     use super::*;
     use rand::{thread_rng, Rng};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{Address, SealedBlockWithSenders, B256};
+    use reth_provider::{
+        providers::BlockchainProvider2,
+        test_utils::{blocks::BlockchainTestData, create_test_provider_factory},
+        BlockHashReader, BlockWriter, CanonChainTracker,
+    };
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
 
     #[test]
     fn test_block_range_iter() {
@@ -683,4 +890,180 @@ mod tests {
             assert_eq!(end, *range.end());
         }
     }
+
+    #[test]
+    fn bloom_prefilter_never_skips_matching_logs() {
+        let mut rng = thread_rng();
+        let addresses: Vec<Address> = (0..4).map(|_| Address::random()).collect();
+        let topics: Vec<B256> = (0..6).map(|_| B256::random()).collect();
+
+        for _ in 0..1000 {
+            // a block with a few random logs
+            let logs: Vec<(Address, Vec<B256>)> = (0..rng.gen_range(1..4))
+                .map(|_| {
+                    let address = addresses[rng.gen_range(0..addresses.len())];
+                    let log_topics = (0..rng.gen_range(0..=4))
+                        .map(|_| topics[rng.gen_range(0..topics.len())])
+                        .collect();
+                    (address, log_topics)
+                })
+                .collect();
+            let mut bloom = Bloom::default();
+            for (address, log_topics) in &logs {
+                bloom.accrue_raw_log(*address, log_topics);
+            }
+
+            // a query for random subsets of the addresses and topics, with wildcard positions
+            let mut filter = Filter::new();
+            if rng.gen_bool(0.5) {
+                filter.address = addresses.iter().copied().filter(|_| rng.gen_bool(0.5)).collect();
+            }
+            for position in 0..4 {
+                if rng.gen_bool(0.5) {
+                    filter.topics[position] =
+                        topics.iter().copied().filter(|_| rng.gen_bool(0.5)).collect();
+                }
+            }
+
+            let params = FilteredParams::new(Some(filter.clone()));
+            let has_match = logs.iter().any(|(address, log_topics)| {
+                params.filter_address(address) && params.filter_topics(log_topics)
+            });
+            if has_match {
+                assert!(bloom_may_match(
+                    bloom,
+                    &FilteredParams::address_filter(&filter.address),
+                    &FilteredParams::topics_filter(&filter.topics),
+                ));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_filter_summaries() {
+        let filters = ActiveFilters::default();
+        let installed_at = Instant::now();
+        let id = FilterId::Num(1);
+        filters.inner.lock().await.insert(
+            id.clone(),
+            ActiveFilter {
+                block: 0,
+                installed_at,
+                last_poll_timestamp: installed_at + Duration::from_secs(10),
+                kind: FilterKind::Block,
+            },
+        );
+
+        let summaries = filters.summaries(installed_at + Duration::from_secs(15)).await;
+        assert_eq!(
+            summaries,
+            vec![FilterSummary {
+                id,
+                kind: FilterSummaryKind::Block,
+                age: Duration::from_secs(15),
+                since_last_poll: Duration::from_secs(5),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn pending_transactions_drain_retains_remainder() {
+        let (tx, rx) = tokio::sync::mpsc::channel(8);
+        let hashes: Vec<TxHash> = (0..3).map(|_| B256::random()).collect();
+        for hash in &hashes {
+            tx.send(*hash).await.unwrap();
+        }
+
+        let receiver = PendingTransactionsReceiver::new(rx);
+        assert_eq!(receiver.drain(2).await, FilterChanges::Hashes(hashes[..2].to_vec()));
+        assert_eq!(receiver.drain(2).await, FilterChanges::Hashes(hashes[2..].to_vec()));
+        assert_eq!(receiver.drain(2).await, FilterChanges::Hashes(Vec::new()));
+    }
+
+    /// Returns a filter handler that serves the [`BlockchainTestData`], with the canonical head at
+    /// genesis, along with its provider and the blocks after genesis.
+    fn paged_filter(
+        max_changes_per_poll: usize,
+    ) -> (
+        EthFilter<impl BlockReader + BlockIdReader + EvmEnvProvider + 'static, TestPool>,
+        impl BlockHashReader + CanonChainTracker,
+        Vec<SealedBlockWithSenders>,
+    ) {
+        let provider_factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        provider_rw
+            .insert_historical_block(data.genesis.clone().try_seal_with_senders().unwrap())
+            .unwrap();
+        let mut outcome = data.blocks[0].1.clone();
+        for (_, block_outcome) in &data.blocks[1..] {
+            outcome.extend(block_outcome.clone());
+        }
+        let hashed_state = outcome.hash_state_slow().into_sorted();
+        let blocks: Vec<_> = data.blocks.into_iter().map(|(block, _)| block).collect();
+        provider_rw
+            .append_blocks_with_state(blocks.clone(), outcome, hashed_state, Default::default())
+            .unwrap();
+        provider_rw
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = BlockchainProvider2::new(provider_factory).unwrap();
+        let cache =
+            EthStateCache::spawn(provider.clone(), Default::default(), EthEvmConfig::default());
+        let filter = EthFilter::new(
+            provider.clone(),
+            testing_pool(),
+            cache,
+            // the headers of the test blocks have no logs bloom
+            EthFilterConfig::default()
+                .max_changes_per_poll(Some(max_changes_per_poll))
+                .use_bloom_prefilter(false),
+            Box::new(TokioTaskExecutor::default()),
+            None,
+        );
+        (filter, provider, blocks)
+    }
+
+    #[tokio::test]
+    async fn block_filter_changes_are_paged() {
+        let (filter, provider, blocks) = paged_filter(2);
+        let id = filter.new_block_filter().await.unwrap();
+        provider.set_canonical_head(blocks.last().unwrap().header.clone());
+
+        let genesis_hash = provider.block_hash(0).unwrap().unwrap();
+        let hashes = |numbers: [usize; 2]| {
+            FilterChanges::Hashes(numbers.map(|number| blocks[number - 1].hash()).to_vec())
+        };
+        assert_eq!(
+            filter.filter_changes(id.clone()).await.unwrap(),
+            FilterChanges::Hashes(vec![genesis_hash, blocks[0].hash()])
+        );
+        assert_eq!(filter.filter_changes(id.clone()).await.unwrap(), hashes([2, 3]));
+        assert_eq!(filter.filter_changes(id.clone()).await.unwrap(), hashes([4, 5]));
+        assert_eq!(filter.filter_changes(id).await.unwrap(), FilterChanges::Empty);
+    }
+
+    #[tokio::test]
+    async fn log_filter_changes_are_paged() {
+        let (filter, provider, blocks) = paged_filter(2);
+        // blocks 2 to 5 have a log of this address, the filter range starts before the cursor
+        let log_filter = Filter::new().address(Address::new([0x61; 20])).from_block(0u64);
+        let id = filter.new_filter(log_filter).await.unwrap();
+        provider.set_canonical_head(blocks.last().unwrap().header.clone());
+
+        let log_blocks = |changes: FilterChanges| match changes {
+            FilterChanges::Logs(logs) => {
+                logs.into_iter().map(|log| log.block_number.unwrap()).collect::<Vec<_>>()
+            }
+            changes => panic!("expected logs, got {changes:?}"),
+        };
+        assert_eq!(log_blocks(filter.filter_changes(id.clone()).await.unwrap()), vec![2, 3]);
+        assert_eq!(log_blocks(filter.filter_changes(id.clone()).await.unwrap()), vec![4, 5]);
+        assert_eq!(filter.filter_changes(id).await.unwrap(), FilterChanges::Empty);
+    }
 }
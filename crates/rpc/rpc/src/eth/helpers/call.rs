@@ -1,5 +1,7 @@
 //! Contains RPC handler implementations specific to endpoints that call/execute within evm.
 
+use std::time::Duration;
+
 use reth_evm::ConfigureEvm;
 use reth_rpc_eth_api::helpers::{Call, EthCall, LoadPendingBlock, LoadState, SpawnBlocking};
 
@@ -20,8 +22,115 @@ where
         self.inner.gas_cap()
     }
 
+    #[inline]
+    fn call_timeout(&self) -> Option<Duration> {
+        self.inner.call_timeout()
+    }
+
+    #[inline]
+    fn method_gas_limit_override(&self, method: &str) -> Option<u64> {
+        self.inner.method_gas_cap(method)
+    }
+
+    #[inline]
+    fn estimate_gas_buffer_percent(&self) -> u64 {
+        self.inner.estimate_gas_buffer_percent()
+    }
+
+    #[inline]
+    fn on_gas_cap_exceeded(&self, method: &'static str) {
+        self.inner.gas_cap_metrics().increment(method)
+    }
+
     #[inline]
     fn evm_config(&self) -> &impl ConfigureEvm {
         self.inner.evm_config()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{Address, Block, Bytes, Header, U256};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_eth_types::{
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{DEFAULT_ETH_PROOF_WINDOW, DEFAULT_PROOF_PERMITS};
+    use reth_rpc_types::TransactionRequest;
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    use super::*;
+
+    /// Returns an [`EthApi`] whose gas limits are only bounded by `u64::MAX`, along with a
+    /// contract that stops right away, so its estimate goes through the binary search.
+    fn eth_api_with_stop_contract() -> (EthApi<MockEthProvider, TestPool, (), EthEvmConfig>, Address)
+    {
+        let chain_spec =
+            ChainSpec { max_gas_limit: u64::MAX, ..ChainSpecBuilder::mainnet().build() };
+        let provider = MockEthProvider { chain_spec: Arc::new(chain_spec), ..Default::default() };
+        let header = Header { gas_limit: u64::MAX, ..Default::default() };
+        provider.add_block(header.hash_slow(), Block { header, ..Default::default() });
+
+        let contract = Address::random();
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&[0x00])),
+        );
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache.clone()),
+            u64::MAX,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+            DEFAULT_PROOF_PERMITS,
+        );
+        (eth_api, contract)
+    }
+
+    #[tokio::test]
+    async fn estimate_gas_with_unlimited_gas_cap() {
+        let (eth_api, contract) = eth_api_with_stop_contract();
+
+        let request = TransactionRequest::default()
+            .from(Address::random())
+            .to(contract)
+            .gas_limit(u64::MAX as u128);
+        let estimate =
+            EthCall::estimate_gas_at(&eth_api, request, Default::default(), None).await.unwrap();
+        assert!(estimate >= U256::from(21_000) && estimate < U256::from(u64::MAX));
+    }
+
+    #[tokio::test]
+    async fn estimate_gas_buffer_replaces_search_tolerance() {
+        let (eth_api, contract) = eth_api_with_stop_contract();
+        let request = TransactionRequest::default().from(Address::random()).to(contract);
+
+        // the search stops within 1.5% above the 21000 gas the call requires
+        let estimate =
+            EthCall::estimate_gas_at(&eth_api, request.clone(), Default::default(), None)
+                .await
+                .unwrap();
+        assert!(estimate > U256::from(21_000) && estimate <= U256::from(21_315));
+
+        // with a buffer, the search finds the required gas, which is then buffered
+        let inner = Arc::into_inner(eth_api.inner).unwrap().with_estimate_gas_buffer_percent(10);
+        let eth_api = EthApi { inner: Arc::new(inner) };
+        let estimate =
+            EthCall::estimate_gas_at(&eth_api, request, Default::default(), None).await.unwrap();
+        assert_eq!(estimate, U256::from(23_100));
+    }
+}
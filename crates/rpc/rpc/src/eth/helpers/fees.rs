@@ -7,9 +7,14 @@ use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle};
 
 use crate::EthApi;
 
-impl<Provider, Pool, Network, EvmConfig> EthFees for EthApi<Provider, Pool, Network, EvmConfig> where
-    Self: LoadFee
+impl<Provider, Pool, Network, EvmConfig> EthFees for EthApi<Provider, Pool, Network, EvmConfig>
+where
+    Self: LoadFee,
 {
+    #[inline]
+    fn max_fee_history_blocks(&self) -> Option<u64> {
+        self.inner.max_fee_history_blocks()
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> LoadFee for EthApi<Provider, Pool, Network, EvmConfig>
@@ -1,5 +1,7 @@
 //! Support for building a pending block with transactions from local view of mempool.
 
+use std::time::Duration;
+
 use reth_evm::ConfigureEvm;
 use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
 use reth_rpc_eth_api::helpers::{LoadPendingBlock, SpawnBlocking};
@@ -37,4 +39,9 @@ where
     fn evm_config(&self) -> &impl ConfigureEvm {
         self.inner.evm_config()
     }
+
+    #[inline]
+    fn pending_block_cache_ttl(&self) -> Option<Duration> {
+        self.inner.pending_block_cache_ttl()
+    }
 }
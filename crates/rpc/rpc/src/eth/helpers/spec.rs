@@ -1,5 +1,5 @@
 use reth_network_api::NetworkInfo;
-use reth_primitives::U256;
+use reth_primitives::{U256, U64};
 use reth_provider::{BlockNumReader, ChainSpecProvider, StageCheckpointReader};
 use reth_rpc_eth_api::helpers::EthApiSpec;
 use reth_transaction_pool::TransactionPool;
@@ -28,4 +28,8 @@ where
     fn signers(&self) -> &parking_lot::RwLock<Vec<Box<dyn reth_rpc_eth_api::helpers::EthSigner>>> {
         self.inner.signers()
     }
+
+    fn chain_id(&self) -> U64 {
+        U64::from(self.inner.chain_id_override().unwrap_or_else(|| self.network().chain_id()))
+    }
 }
@@ -4,7 +4,7 @@ use reth_provider::{ChainSpecProvider, StateProviderFactory};
 use reth_transaction_pool::TransactionPool;
 
 use reth_rpc_eth_api::helpers::{EthState, LoadState, SpawnBlocking};
-use reth_rpc_eth_types::EthStateCache;
+use reth_rpc_eth_types::{EthStateCache, ProofPermitMetrics};
 
 use crate::EthApi;
 
@@ -15,6 +15,10 @@ where
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_proof_window()
     }
+
+    fn proof_permit_metrics(&self) -> &ProofPermitMetrics {
+        self.inner.proof_permit_metrics()
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> LoadState for EthApi<Provider, Pool, Network, EvmConfig>
@@ -2,6 +2,7 @@
 
 use reth_evm::ConfigureEvm;
 use reth_rpc_eth_api::helpers::{LoadState, Trace};
+use reth_rpc_eth_types::EthStateCache;
 
 use crate::EthApi;
 
@@ -14,4 +15,14 @@ where
     fn evm_config(&self) -> &impl ConfigureEvm {
         self.inner.evm_config()
     }
+
+    #[inline]
+    fn max_trace_window(&self) -> Option<u64> {
+        self.inner.trace_block_window()
+    }
+
+    #[inline]
+    fn trace_cache(&self) -> &EthStateCache {
+        self.inner.trace_cache()
+    }
 }
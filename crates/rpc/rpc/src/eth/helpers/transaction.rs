@@ -66,30 +66,48 @@ mod tests {
     use reth_network_api::noop::NoopNetwork;
     use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex, Bytes};
     use reth_provider::test_utils::NoopProvider;
-    use reth_rpc_eth_api::helpers::EthTransactions;
+    use reth_rpc_eth_api::{helpers::EthTransactions, MapForwardError};
     use reth_rpc_eth_types::{
-        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+        EthApiError, EthResult, EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig,
+        GasPriceOracle,
     };
     use reth_rpc_server_types::constants::{DEFAULT_ETH_PROOF_WINDOW, DEFAULT_PROOF_PERMITS};
     use reth_tasks::pool::BlockingTaskPool;
-    use reth_transaction_pool::{test_utils::testing_pool, TransactionPool};
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, TestPool},
+        TransactionPool,
+    };
 
     use super::*;
 
-    #[tokio::test]
-    async fn send_raw_transaction() {
+    // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
+    const RAW_TX: [u8; 116] = hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3");
+
+    /// A forwarder whose upstream is unreachable.
+    #[derive(Debug)]
+    struct FailingForwarder;
+
+    #[async_trait::async_trait]
+    impl RawTransactionForwarder for FailingForwarder {
+        async fn forward_raw_transaction(&self, _raw: &[u8]) -> EthResult<()> {
+            Err(EthApiError::InternalEthError)
+        }
+    }
+
+    fn eth_api(
+        pool: TestPool,
+        raw_transaction_forwarder: Option<Arc<dyn RawTransactionForwarder>>,
+    ) -> EthApi<NoopProvider, TestPool, NoopNetwork, EthEvmConfig> {
         let noop_provider = NoopProvider::default();
         let noop_network_provider = NoopNetwork::default();
 
-        let pool = testing_pool();
-
         let evm_config = EthEvmConfig::default();
         let cache = EthStateCache::spawn(noop_provider, Default::default(), evm_config);
         let fee_history_cache =
             FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
-        let eth_api = EthApi::new(
+        EthApi::new(
             noop_provider,
-            pool.clone(),
+            pool,
             noop_network_provider,
             cache.clone(),
             GasPriceOracle::new(noop_provider, Default::default(), cache.clone()),
@@ -98,12 +116,17 @@ mod tests {
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             fee_history_cache,
             evm_config,
-            None,
+            raw_transaction_forwarder,
             DEFAULT_PROOF_PERMITS,
-        );
+        )
+    }
 
-        // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
-        let tx_1 = Bytes::from(hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3"));
+    #[tokio::test]
+    async fn send_raw_transaction() {
+        let pool = testing_pool();
+        let eth_api = eth_api(pool.clone(), None);
+
+        let tx_1 = Bytes::from(RAW_TX);
 
         let tx_1_result = eth_api.send_raw_transaction(tx_1).await.unwrap();
         assert_eq!(
@@ -127,4 +150,22 @@ mod tests {
         assert!(pool.get(&tx_1_result).is_some(), "tx1 not found in the pool");
         assert!(pool.get(&tx_2_result).is_some(), "tx2 not found in the pool");
     }
+
+    #[tokio::test]
+    async fn send_raw_transaction_with_failing_forwarder() {
+        // by default, forwarding errors are only logged
+        let pool = testing_pool();
+        let eth_api = eth_api(pool.clone(), Some(Arc::new(FailingForwarder)));
+
+        let hash = eth_api.send_raw_transaction(Bytes::from(RAW_TX)).await.unwrap();
+        assert!(pool.get(&hash).is_some(), "tx not found in the pool");
+
+        // mapped errors are returned, and the transaction is not submitted to the pool
+        let pool = testing_pool();
+        let forwarder = MapForwardError::new(FailingForwarder, Some);
+        let eth_api = eth_api(pool.clone(), Some(Arc::new(forwarder)));
+
+        assert!(eth_api.send_raw_transaction(Bytes::from(RAW_TX)).await.is_err());
+        assert!(pool.is_empty());
+    }
 }
@@ -5,12 +5,14 @@ pub mod core;
 pub mod filter;
 pub mod helpers;
 pub mod pubsub;
+pub mod sim_bundle;
 
 /// Implementation of `eth` namespace API.
 pub use bundle::EthBundle;
 pub use core::EthApi;
-pub use filter::EthFilter;
-pub use pubsub::EthPubSub;
+pub use filter::{EthFilter, FilterSummary, FilterSummaryKind};
+pub use pubsub::{EthPubSub, PubSubStats};
+pub use sim_bundle::EthSimulateBundle;
 
 pub use helpers::signer::DevSigner;
 
@@ -1,17 +1,32 @@
 //! `eth_` `PubSub` RPC handler implementation
 
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 use futures::StreamExt;
 use jsonrpsee::{
-    server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
+    server::SubscriptionMessage,
+    types::{error::METHOD_NOT_FOUND_CODE, ErrorObject},
+    ConnectionId, PendingSubscriptionSink, SubscriptionSink,
+};
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider};
+use reth_primitives::{BlockNumHash, IntoRecoveredTransaction, TxHash};
+use reth_provider::{BlockReader, CanonStateNotification, CanonStateSubscriptions, EvmEnvProvider};
 use reth_rpc_eth_api::pubsub::EthPubSubApiServer;
-use reth_rpc_eth_types::logs_utils;
-use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_rpc_eth_types::{builder::rate_limit::LIMIT_EXCEEDED_CODE, logs_utils, EthFilterError};
+use reth_rpc_server_types::result::{
+    internal_rpc_err, invalid_params_rpc_err, rpc_error_with_code,
+};
 use reth_rpc_types::{
     pubsub::{
         Params, PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
@@ -22,20 +37,33 @@ use reth_rpc_types::{
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{NewTransactionEvent, TransactionPool};
 use serde::Serialize;
+use tokio::sync::oneshot;
 use tokio_stream::{
-    wrappers::{BroadcastStream, ReceiverStream},
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream},
     Stream,
 };
 
 /// `Eth` pubsub RPC implementation.
 ///
 /// This handles `eth_subscribe` RPC calls.
+///
+/// The handler is lazy: it spawns no tasks and subscribes to no event streams until a
+/// subscription is created. Every accepted subscription spawns its own task, which subscribes to
+/// the canonical state notifications or the pool events it needs and ends with the subscription,
+/// so an instance without subscriptions only holds handles.
 #[derive(Clone)]
 pub struct EthPubSub<Provider, Pool, Events, Network> {
     /// All nested fields bundled together.
     inner: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
     /// The type that's used to spawn subscription tasks.
     subscription_task_spawner: Box<dyn TaskSpawner>,
+    /// Limits of the historical logs served to a `logs` subscription before it switches to live
+    /// logs, if backfilling is enabled.
+    log_backfill: Option<LogBackfillLimits>,
+    /// Tracks the active subscriptions and enforces the subscription limits.
+    subscriptions: Arc<SubscriptionTracker>,
+    /// New subscriptions are rejected while this is unset, if configured.
+    gate: Option<Arc<AtomicBool>>,
 }
 
 // === impl EthPubSub ===
@@ -62,8 +90,61 @@ impl<Provider, Pool, Events, Network> EthPubSub<Provider, Pool, Events, Network>
         network: Network,
         subscription_task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
-        let inner = EthPubSubInner { provider, pool, chain_events, network };
-        Self { inner: Arc::new(inner), subscription_task_spawner }
+        let inner = EthPubSubInner {
+            provider,
+            pool,
+            chain_events,
+            network,
+            channel_stats: Default::default(),
+        };
+        Self {
+            inner: Arc::new(inner),
+            subscription_task_spawner,
+            log_backfill: None,
+            subscriptions: Default::default(),
+            gate: None,
+        }
+    }
+
+    /// Limits the number of active subscriptions per connection and across all connections.
+    ///
+    /// New subscriptions beyond either limit are rejected. `None` disables the respective limit.
+    pub fn with_subscription_limits(
+        mut self,
+        max_per_connection: Option<usize>,
+        max_total: Option<usize>,
+    ) -> Self {
+        self.subscriptions =
+            Arc::new(SubscriptionTracker { max_per_connection, max_total, ..Default::default() });
+        self
+    }
+
+    /// Enables backfilling of `logs` subscriptions: if the filter's `fromBlock` is in the past,
+    /// all matching historical logs are sent first, before the subscription switches to live logs.
+    ///
+    /// Subscriptions that would backfill more than `max_logs` logs or scan more than `max_blocks`
+    /// blocks are rejected. Backfilling is disabled by default.
+    pub fn with_log_backfill(mut self, max_logs: usize, max_blocks: u64) -> Self {
+        self.log_backfill = Some(LogBackfillLimits { max_logs, max_blocks });
+        self
+    }
+
+    /// Rejects new subscriptions with a `Method not found` error while the given flag is unset,
+    /// e.g. while the node is on standby.
+    ///
+    /// Active subscriptions are not affected. Subscriptions are accepted regardless of any flag by
+    /// default.
+    pub fn with_subscription_gate(mut self, open: Arc<AtomicBool>) -> Self {
+        self.gate = Some(open);
+        self
+    }
+
+    /// Returns the current [`PubSubStats`] of the active subscriptions.
+    ///
+    /// A growing number of lagged notifications indicates subscribers that consume their
+    /// subscriptions too slowly, and therefore skip blocks.
+    pub fn channel_stats(&self) -> PubSubStats {
+        self.inner.channel_stats.snapshot()
     }
 }
 
@@ -83,9 +164,27 @@ where
         kind: SubscriptionKind,
         params: Option<Params>,
     ) -> jsonrpsee::core::SubscriptionResult {
+        if self.gate.as_ref().is_some_and(|open| !open.load(Ordering::Relaxed)) {
+            pending.reject(rpc_error_with_code(METHOD_NOT_FOUND_CODE, "Method not found")).await;
+            return Ok(())
+        }
+
+        let guard = match self.subscriptions.try_acquire(pending.connection_id()) {
+            Ok(guard) => guard,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        };
+
+        if let (SubscriptionKind::Logs, Some(limits)) = (kind, self.log_backfill) {
+            return self.subscribe_logs_with_backfill(pending, params, limits, guard).await
+        }
+
         let sink = pending.accept().await?;
         let pubsub = self.inner.clone();
         self.subscription_task_spawner.spawn(Box::pin(async move {
+            let _guard = guard;
             let _ = handle_accepted(pubsub, sink, kind, params).await;
         }));
 
@@ -93,6 +192,174 @@ where
     }
 }
 
+impl<Provider, Pool, Events, Network> EthPubSub<Provider, Pool, Events, Network>
+where
+    Provider: BlockReader + EvmEnvProvider + Clone + 'static,
+    Pool: TransactionPool + 'static,
+    Events: CanonStateSubscriptions + Clone + 'static,
+    Network: NetworkInfo + Clone + 'static,
+{
+    /// Handler for `logs` subscriptions if backfilling is enabled, see
+    /// [`EthPubSub::with_log_backfill`].
+    ///
+    /// The subscription is rejected if the backfill exceeds the given limits. The historical logs
+    /// are read on a blocking task.
+    async fn subscribe_logs_with_backfill(
+        &self,
+        pending: PendingSubscriptionSink,
+        params: Option<Params>,
+        limits: LogBackfillLimits,
+        guard: SubscriptionGuard,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let filter = match params {
+            Some(Params::Logs(filter)) => Some(*filter),
+            Some(Params::Bool(_)) => {
+                pending.reject(invalid_params_rpc_err("Invalid params for logs")).await;
+                return Ok(())
+            }
+            _ => None,
+        };
+
+        // subscribe to live logs first, so that no block is missed between backfill and live logs
+        let live = self.inner.log_stream(FilteredParams::new(filter.clone()));
+
+        let (tx, rx) = oneshot::channel();
+        let pubsub = self.inner.clone();
+        self.subscription_task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(pubsub.backfill_logs(&FilteredParams::new(filter), limits));
+        }));
+        let backfill = match rx.await.map_err(|_| EthFilterError::InternalError).and_then(|res| res)
+        {
+            Ok(backfill) => backfill,
+            Err(err) => {
+                pending.reject(ErrorObject::from(err)).await;
+                return Ok(())
+            }
+        };
+
+        let sink = pending.accept().await?;
+        let pubsub = self.inner.clone();
+        self.subscription_task_spawner.spawn(Box::pin(async move {
+            let _guard = guard;
+            let (backfilled_logs, backfilled_to) = backfill.unwrap_or_default();
+            // skip live logs of blocks that were already backfilled
+            let live = live.filter(move |log| {
+                futures::future::ready(
+                    log.block_number.map_or(true, |number| number > backfilled_to),
+                )
+            });
+            let stream = futures::stream::iter(backfilled_logs)
+                .chain(live)
+                .map(|log| EthSubscriptionResult::Log(Box::new(log)));
+            let _ = pipe_from_stream(sink, stream, &pubsub.channel_stats).await;
+        }));
+
+        Ok(())
+    }
+}
+
+/// Limits of the backfill of `logs` subscriptions, see [`EthPubSub::with_log_backfill`].
+#[derive(Debug, Clone, Copy)]
+struct LogBackfillLimits {
+    /// Maximum number of historical logs.
+    max_logs: usize,
+    /// Maximum number of historical blocks that are scanned.
+    max_blocks: u64,
+}
+
+/// Tracks the active subscriptions of an [`EthPubSub`] and enforces its subscription limits.
+#[derive(Debug, Default)]
+struct SubscriptionTracker {
+    /// Maximum number of active subscriptions per connection, if any.
+    max_per_connection: Option<usize>,
+    /// Maximum number of active subscriptions across all connections, if any.
+    max_total: Option<usize>,
+    /// The number of active subscriptions, in total and per connection.
+    active: Mutex<ActiveSubscriptions>,
+    /// Subscription metrics.
+    metrics: EthPubSubMetrics,
+}
+
+/// The number of active subscriptions, see [`SubscriptionTracker`].
+#[derive(Debug, Default)]
+struct ActiveSubscriptions {
+    total: usize,
+    per_connection: HashMap<ConnectionId, usize>,
+}
+
+impl SubscriptionTracker {
+    /// Registers a new subscription of the given connection, if it doesn't exceed the limits.
+    ///
+    /// The subscription is active until the returned guard is dropped.
+    fn try_acquire(
+        self: &Arc<Self>,
+        connection_id: ConnectionId,
+    ) -> Result<SubscriptionGuard, ErrorObject<'static>> {
+        let mut active = self.active.lock();
+        let connection_subscriptions = active.per_connection.get(&connection_id).copied();
+
+        let exceeded = if self.max_total.is_some_and(|max| active.total >= max) {
+            Some("total")
+        } else if self
+            .max_per_connection
+            .is_some_and(|max| connection_subscriptions.unwrap_or_default() >= max)
+        {
+            Some("per connection")
+        } else {
+            None
+        };
+        if let Some(limit) = exceeded {
+            self.metrics.rejected_subscriptions_total.increment(1);
+            return Err(rpc_error_with_code(
+                LIMIT_EXCEEDED_CODE,
+                format!("subscription limit exceeded ({limit})"),
+            ))
+        }
+
+        active.total += 1;
+        *active.per_connection.entry(connection_id).or_default() += 1;
+        self.metrics.active_subscriptions.set(active.total as f64);
+
+        Ok(SubscriptionGuard { tracker: self.clone(), connection_id })
+    }
+
+    /// Unregisters a subscription of the given connection.
+    fn release(&self, connection_id: ConnectionId) {
+        let mut active = self.active.lock();
+        active.total -= 1;
+        if let Some(count) = active.per_connection.get_mut(&connection_id) {
+            *count -= 1;
+            if *count == 0 {
+                active.per_connection.remove(&connection_id);
+            }
+        }
+        self.metrics.active_subscriptions.set(active.total as f64);
+    }
+}
+
+/// Keeps a subscription registered with its [`SubscriptionTracker`] until dropped.
+#[derive(Debug)]
+struct SubscriptionGuard {
+    tracker: Arc<SubscriptionTracker>,
+    connection_id: ConnectionId,
+}
+
+impl Drop for SubscriptionGuard {
+    fn drop(&mut self) {
+        self.tracker.release(self.connection_id);
+    }
+}
+
+/// Metrics for `eth_subscribe` subscriptions.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.eth_pubsub")]
+struct EthPubSubMetrics {
+    /// The number of active subscriptions.
+    active_subscriptions: Gauge,
+    /// The number of subscriptions that were rejected because they exceeded a limit.
+    rejected_subscriptions_total: Counter,
+}
+
 /// The actual handler for an accepted [`EthPubSub::subscribe`] call.
 async fn handle_accepted<Provider, Pool, Events, Network>(
     pubsub: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
@@ -111,7 +378,7 @@ where
             let stream = pubsub
                 .new_headers_stream()
                 .map(|block| EthSubscriptionResult::Header(Box::new(block.into())));
-            pipe_from_stream(accepted_sink, stream).await
+            pipe_from_stream(accepted_sink, stream, &pubsub.channel_stats).await
         }
         SubscriptionKind::Logs => {
             // if no params are provided, used default filter params
@@ -124,7 +391,7 @@ where
             };
             let stream =
                 pubsub.log_stream(filter).map(|log| EthSubscriptionResult::Log(Box::new(log)));
-            pipe_from_stream(accepted_sink, stream).await
+            pipe_from_stream(accepted_sink, stream, &pubsub.channel_stats).await
         }
         SubscriptionKind::NewPendingTransactions => {
             if let Some(params) = params {
@@ -138,7 +405,7 @@ where
                                 ),
                             ))
                         });
-                        return pipe_from_stream(accepted_sink, stream).await
+                        return pipe_from_stream(accepted_sink, stream, &pubsub.channel_stats).await
                     }
                     Params::Bool(false) | Params::None => {
                         // only hashes requested
@@ -154,12 +421,11 @@ where
             let stream = pubsub
                 .pending_transaction_hashes_stream()
                 .map(EthSubscriptionResult::TransactionHash);
-            pipe_from_stream(accepted_sink, stream).await
+            pipe_from_stream(accepted_sink, stream, &pubsub.channel_stats).await
         }
         SubscriptionKind::Syncing => {
             // get new block subscription
-            let mut canon_state =
-                BroadcastStream::new(pubsub.chain_events.subscribe_to_canonical_state());
+            let mut canon_state = pubsub.canonical_state_stream();
             // get current sync status
             let mut initial_sync_status = pubsub.network.is_syncing();
             let current_sub_res = pubsub.sync_status(initial_sync_status);
@@ -211,14 +477,18 @@ impl From<SubscriptionSerializeError> for ErrorObject<'static> {
 }
 
 /// Pipes all stream items to the subscription sink.
+///
+/// The messages buffered for the sink are tracked in the given [`ChannelStats`].
 async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
     mut stream: St,
+    channel_stats: &ChannelStats,
 ) -> Result<(), ErrorObject<'static>>
 where
     St: Stream<Item = T> + Unpin,
     T: Serialize,
 {
+    let mut buffer = channel_stats.track_buffer(&sink);
     loop {
         tokio::select! {
             _ = sink.closed() => {
@@ -237,6 +507,7 @@ where
                 if sink.send(msg).await.is_err() {
                     break Ok(());
                 }
+                buffer.update(&sink);
             }
         }
     }
@@ -250,6 +521,78 @@ impl<Provider, Pool, Events, Network> std::fmt::Debug
     }
 }
 
+/// Stats of the notifications delivered to the subscriptions of an [`EthPubSub`], see
+/// [`EthPubSub::channel_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PubSubStats {
+    /// The number of messages that can be buffered for all active subscriptions, before sending
+    /// to a subscription waits for its subscriber.
+    pub buffer_capacity: usize,
+    /// The number of messages that are buffered for all active subscriptions, as of the last
+    /// message sent to each subscription.
+    pub buffer_depth: usize,
+    /// The total number of canonical state notifications that subscriptions skipped because
+    /// they fell too far behind the broadcast channel.
+    pub lagged_notifications: u64,
+}
+
+/// Tracks the [`PubSubStats`] of an [`EthPubSub`].
+#[derive(Debug, Default)]
+struct ChannelStats {
+    buffer_capacity: AtomicUsize,
+    buffer_depth: AtomicUsize,
+    lagged_notifications: AtomicU64,
+}
+
+impl ChannelStats {
+    fn snapshot(&self) -> PubSubStats {
+        PubSubStats {
+            buffer_capacity: self.buffer_capacity.load(Ordering::Relaxed),
+            buffer_depth: self.buffer_depth.load(Ordering::Relaxed),
+            lagged_notifications: self.lagged_notifications.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Adds the buffer of the given sink to the stats, until the returned guard is dropped.
+    fn track_buffer(&self, sink: &SubscriptionSink) -> SinkBufferGuard<'_> {
+        let capacity = sink.max_capacity();
+        self.buffer_capacity.fetch_add(capacity, Ordering::Relaxed);
+        SinkBufferGuard { stats: self, capacity, depth: 0 }
+    }
+
+    fn record_lagged(&self, skipped: u64) {
+        self.lagged_notifications.fetch_add(skipped, Ordering::Relaxed);
+    }
+}
+
+/// Keeps the buffer of a subscription sink tracked in its [`ChannelStats`] until dropped.
+#[derive(Debug)]
+struct SinkBufferGuard<'a> {
+    stats: &'a ChannelStats,
+    capacity: usize,
+    depth: usize,
+}
+
+impl SinkBufferGuard<'_> {
+    /// Updates the number of messages buffered for the given sink.
+    fn update(&mut self, sink: &SubscriptionSink) {
+        let depth = sink.max_capacity().saturating_sub(sink.capacity());
+        if depth > self.depth {
+            self.stats.buffer_depth.fetch_add(depth - self.depth, Ordering::Relaxed);
+        } else {
+            self.stats.buffer_depth.fetch_sub(self.depth - depth, Ordering::Relaxed);
+        }
+        self.depth = depth;
+    }
+}
+
+impl Drop for SinkBufferGuard<'_> {
+    fn drop(&mut self) {
+        self.stats.buffer_capacity.fetch_sub(self.capacity, Ordering::Relaxed);
+        self.stats.buffer_depth.fetch_sub(self.depth, Ordering::Relaxed);
+    }
+}
+
 /// Container type `EthPubSub`
 #[derive(Clone)]
 struct EthPubSubInner<Provider, Pool, Events, Network> {
@@ -261,6 +604,8 @@ struct EthPubSubInner<Provider, Pool, Events, Network> {
     chain_events: Events,
     /// The network.
     network: Network,
+    /// Stats of the notifications delivered to subscriptions.
+    channel_stats: Arc<ChannelStats>,
 }
 
 // == impl EthPubSubInner ===
@@ -310,9 +655,29 @@ where
     Network: NetworkInfo + 'static,
     Pool: 'static,
 {
+    /// Returns a stream of canonical state notifications.
+    ///
+    /// Notifications that are skipped because the subscriber lagged behind are recorded in the
+    /// channel stats.
+    fn canonical_state_stream(&self) -> impl Stream<Item = CanonStateNotification> {
+        let channel_stats = self.channel_stats.clone();
+        BroadcastStream::new(self.chain_events.subscribe_to_canonical_state()).filter_map(
+            move |notification| {
+                let notification = match notification {
+                    Ok(notification) => Some(notification),
+                    Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                        channel_stats.record_lagged(skipped);
+                        None
+                    }
+                };
+                futures::future::ready(notification)
+            },
+        )
+    }
+
     /// Returns a stream that yields all new RPC blocks.
     fn new_headers_stream(&self) -> impl Stream<Item = Header> {
-        self.chain_events.canonical_state_stream().flat_map(|new_chain| {
+        self.canonical_state_stream().flat_map(|new_chain| {
             let headers = new_chain.committed().headers().collect::<Vec<_>>();
             futures::stream::iter(
                 headers.into_iter().map(reth_rpc_types_compat::block::from_primitive_with_hash),
@@ -320,12 +685,66 @@ where
         })
     }
 
+    /// Returns all historical logs that match the given filter, if the filter's `fromBlock` is in
+    /// the past, and the last block they were read from.
+    ///
+    /// Blocks whose logs bloom can't match the filter are skipped without reading their receipts.
+    /// Fails if the range spans more than `max_blocks` blocks or if there are more than `max_logs`
+    /// matching logs.
+    fn backfill_logs(
+        &self,
+        filter: &FilteredParams,
+        limits: LogBackfillLimits,
+    ) -> Result<Option<(Vec<Log>, u64)>, EthFilterError> {
+        let Some(inner_filter) = filter.filter.as_ref() else { return Ok(None) };
+        let Some(from_block) = inner_filter.get_from_block() else { return Ok(None) };
+        let best_number = self.provider.best_block_number()?;
+        if from_block > best_number {
+            return Ok(None)
+        }
+        let to_block =
+            inner_filter.get_to_block().map_or(best_number, |to_block| to_block.min(best_number));
+        if to_block.saturating_sub(from_block) > limits.max_blocks {
+            return Err(EthFilterError::QueryExceedsMaxBlocks(limits.max_blocks))
+        }
+
+        let address_filter = FilteredParams::address_filter(&inner_filter.address);
+        let topics_filter = FilteredParams::topics_filter(&inner_filter.topics);
+
+        let mut all_logs = Vec::new();
+        for block_number in from_block..=to_block {
+            let Some(header) = self.provider.sealed_header(block_number)? else { continue };
+            if !FilteredParams::matches_address(header.logs_bloom, &address_filter) ||
+                !FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
+            {
+                continue
+            }
+            let Some(receipts) = self.provider.receipts_by_block(block_number.into())? else {
+                continue
+            };
+
+            logs_utils::append_matching_block_logs(
+                &mut all_logs,
+                &self.provider,
+                filter,
+                BlockNumHash::new(block_number, header.hash()),
+                &receipts,
+                false,
+                header.timestamp,
+            )?;
+
+            if all_logs.len() > limits.max_logs {
+                return Err(EthFilterError::QueryExceedsMaxResults(limits.max_logs))
+            }
+        }
+
+        Ok(Some((all_logs, to_block)))
+    }
+
     /// Returns a stream that yields all logs that match the given filter.
     fn log_stream(&self, filter: FilteredParams) -> impl Stream<Item = Log> {
-        BroadcastStream::new(self.chain_events.subscribe_to_canonical_state())
-            .map(move |canon_state| {
-                canon_state.expect("new block subscription never ends").block_receipts()
-            })
+        self.canonical_state_stream()
+            .map(|canon_state| canon_state.block_receipts())
             .flat_map(futures::stream::iter)
             .flat_map(move |(block_receipts, removed)| {
                 let all_logs = logs_utils::matching_block_logs_with_tx_hashes(
@@ -338,3 +757,87 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{Header, B256};
+    use reth_provider::test_utils::{MockEthProvider, TestCanonStateSubscriptions};
+    use reth_rpc_types::Filter;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    fn test_pubsub_inner(
+        provider: MockEthProvider,
+    ) -> EthPubSubInner<MockEthProvider, TestPool, TestCanonStateSubscriptions, NoopNetwork> {
+        EthPubSubInner {
+            pool: testing_pool(),
+            provider,
+            chain_events: TestCanonStateSubscriptions::default(),
+            network: NoopNetwork::default(),
+            channel_stats: Default::default(),
+        }
+    }
+
+    #[test]
+    fn backfill_logs_limits_the_block_range() {
+        let provider = MockEthProvider::default();
+        for number in 0..=20 {
+            provider.add_header(
+                B256::with_last_byte(number as u8),
+                Header { number, ..Default::default() },
+            );
+        }
+        let pubsub = test_pubsub_inner(provider);
+        let limits = LogBackfillLimits { max_logs: 100, max_blocks: 10 };
+
+        let filter = FilteredParams::new(Some(Filter::new().from_block(5u64)));
+        assert!(matches!(
+            pubsub.backfill_logs(&filter, limits),
+            Err(EthFilterError::QueryExceedsMaxBlocks(10))
+        ));
+
+        let filter = FilteredParams::new(Some(Filter::new().from_block(10u64)));
+        let (logs, backfilled_to) = pubsub.backfill_logs(&filter, limits).unwrap().unwrap();
+        assert!(logs.is_empty());
+        assert_eq!(backfilled_to, 20);
+
+        // no backfill without a `fromBlock`
+        let filter = FilteredParams::new(Some(Filter::new()));
+        assert!(pubsub.backfill_logs(&filter, limits).unwrap().is_none());
+    }
+
+    #[test]
+    fn subscription_limits() {
+        let tracker = Arc::new(SubscriptionTracker {
+            max_per_connection: Some(2),
+            max_total: Some(3),
+            ..Default::default()
+        });
+        let (first, second) = (ConnectionId(1), ConnectionId(2));
+
+        let guard = tracker.try_acquire(first).unwrap();
+        let _second_guard = tracker.try_acquire(first).unwrap();
+        assert!(tracker.try_acquire(first).is_err(), "per connection limit");
+
+        let _third_guard = tracker.try_acquire(second).unwrap();
+        assert!(tracker.try_acquire(second).is_err(), "total limit");
+
+        // dropping a subscription frees a slot
+        drop(guard);
+        let _fourth_guard = tracker.try_acquire(second).unwrap();
+        assert_eq!(tracker.active.lock().total, 3);
+        assert_eq!(tracker.active.lock().per_connection[&first], 1);
+    }
+
+    #[test]
+    fn channel_stats_lagged() {
+        let stats = ChannelStats::default();
+        stats.record_lagged(2);
+        stats.record_lagged(3);
+        assert_eq!(
+            stats.snapshot(),
+            PubSubStats { buffer_capacity: 0, buffer_depth: 0, lagged_notifications: 5 }
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Bundle simulation on top of forked state.
+
+use std::sync::Arc;
+
+use jsonrpsee::core::RpcResult;
+use reth_rpc_eth_api::{
+    helpers::{EthCall, LoadBlock},
+    EthSimulateBundleApiServer, FromEthApiError,
+};
+use reth_rpc_eth_types::EthApiError;
+use reth_rpc_types::{BlockId, Bundle, EthCallResponse, StateContext, TransactionRequest};
+use reth_tasks::pool::BlockingTaskGuard;
+use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+
+/// Handler that simulates an ordered list of calls on top of the state of a block.
+///
+/// Every call is executed on the state left behind by the previous calls, without committing
+/// anything to the database. Calls are capped at the gas cap of the wrapped `eth` API.
+pub struct EthSimulateBundle<Eth> {
+    /// All nested fields bundled together.
+    inner: Arc<EthSimulateBundleInner<Eth>>,
+}
+
+impl<Eth> EthSimulateBundle<Eth> {
+    /// Create a new `EthSimulateBundle` instance.
+    pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
+        Self { inner: Arc::new(EthSimulateBundleInner { eth_api, blocking_task_guard }) }
+    }
+
+    /// Acquires a permit to execute a simulation.
+    async fn acquire_simulate_permit(&self) -> Result<OwnedSemaphorePermit, AcquireError> {
+        self.inner.blocking_task_guard.clone().acquire_owned().await
+    }
+}
+
+impl<Eth> EthSimulateBundle<Eth>
+where
+    Eth: EthCall + LoadBlock + 'static,
+{
+    /// Executes the given calls in order on top of the state of the given block, or the latest
+    /// block if unset, and returns the result of every call.
+    ///
+    /// Simulations share their permits with the tracing calls of the node.
+    pub async fn simulate(
+        &self,
+        calls: Vec<TransactionRequest>,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<EthCallResponse>, Eth::Error> {
+        let _permit = self
+            .acquire_simulate_permit()
+            .await
+            .map_err(|_| Eth::Error::from_eth_err(EthApiError::InternalBlockingTaskError))?;
+
+        for call in &calls {
+            self.inner.eth_api.check_gas_cap("simulate_bundle", call);
+        }
+
+        let bundle = Bundle { transactions: calls, block_override: None };
+        let state_context = StateContext { block_number: block_id, transaction_index: None };
+        self.inner.eth_api.call_many(bundle, Some(state_context), None).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthSimulateBundleApiServer for EthSimulateBundle<Eth>
+where
+    Eth: EthCall + LoadBlock + 'static,
+{
+    async fn simulate_bundle(
+        &self,
+        calls: Vec<TransactionRequest>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<EthCallResponse>> {
+        Self::simulate(self, calls, block_id).await.map_err(Into::into)
+    }
+}
+
+/// Container type for `EthSimulateBundle` internals
+#[derive(Debug)]
+struct EthSimulateBundleInner<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    // restrict the number of concurrent simulations, shared with tracing calls.
+    blocking_task_guard: BlockingTaskGuard,
+}
+
+impl<Eth> std::fmt::Debug for EthSimulateBundle<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthSimulateBundle").finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for EthSimulateBundle<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{Address, Block, Header, U256};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_eth_types::{
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{DEFAULT_ETH_PROOF_WINDOW, DEFAULT_PROOF_PERMITS};
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    use super::*;
+    use crate::EthApi;
+
+    const ETHER: u128 = 1_000_000_000_000_000_000;
+
+    type TestEthApi = EthApi<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig>;
+
+    /// Returns a provider with a single block, in which `sender` holds one ether.
+    fn provider_with_funded_sender(sender: Address) -> MockEthProvider {
+        let provider = MockEthProvider::default();
+        let header = Header { gas_limit: 30_000_000, ..Default::default() };
+        let hash = header.hash_slow();
+        provider.add_block(hash, Block { header: header.clone(), ..Default::default() });
+        provider.add_header(hash, header);
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(ETHER)));
+        provider
+    }
+
+    fn build_simulate_bundle(
+        provider: MockEthProvider,
+        blocking_task_guard: BlockingTaskGuard,
+    ) -> EthSimulateBundle<TestEthApi> {
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let fee_history_cache =
+            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+            30_000_000,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            fee_history_cache,
+            evm_config,
+            None,
+            DEFAULT_PROOF_PERMITS,
+        );
+        EthSimulateBundle::new(eth_api, blocking_task_guard)
+    }
+
+    fn transfer(from: Address, to: Address) -> TransactionRequest {
+        TransactionRequest::default().from(from).to(to).value(U256::from(ETHER))
+    }
+
+    #[tokio::test]
+    async fn simulate_executes_calls_on_forked_state() {
+        let (alice, bob, carol) = (Address::random(), Address::random(), Address::random());
+        let simulate_bundle =
+            build_simulate_bundle(provider_with_funded_sender(alice), BlockingTaskGuard::new(1));
+
+        // bob can only forward the ether if he sees the state left behind by the first call
+        let results = simulate_bundle
+            .simulate(vec![transfer(alice, bob), transfer(bob, carol)], None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| result.error.is_none()));
+
+        // nothing is committed
+        assert!(simulate_bundle.simulate(vec![transfer(bob, carol)], None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn simulate_waits_for_shared_permit() {
+        let (alice, bob) = (Address::random(), Address::random());
+        let blocking_task_guard = BlockingTaskGuard::new(1);
+        let simulate_bundle =
+            build_simulate_bundle(provider_with_funded_sender(alice), blocking_task_guard.clone());
+
+        // e.g. held by a tracing call
+        let permit = blocking_task_guard.clone().acquire_owned().await.unwrap();
+        let simulation = simulate_bundle.simulate(vec![transfer(alice, bob)], None);
+        assert!(tokio::time::timeout(Duration::from_millis(100), simulation).await.is_err());
+
+        drop(permit);
+        assert!(simulate_bundle.simulate(vec![transfer(alice, bob)], None).await.is_ok());
+    }
+}
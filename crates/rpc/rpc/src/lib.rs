@@ -45,8 +45,8 @@ mod txpool;
 mod web3;
 pub use admin::AdminApi;
 pub use debug::DebugApi;
-pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{EthApi, EthBundle, EthFilter, EthPubSub};
+pub use engine::{EngineApi, EngineEthApi, MINIMAL_ENGINE_ETH_METHODS};
+pub use eth::{EthApi, EthBundle, EthFilter, EthPubSub, EthSimulateBundle};
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
 pub use reth::RethApi;
@@ -81,9 +81,12 @@ where
     /// Executes the given call and returns a number of possible traces for it.
     pub async fn trace_call(
         &self,
-        trace_request: TraceCallRequest,
+        mut trace_request: TraceCallRequest,
     ) -> Result<TraceResults, Eth::Error> {
+        self.eth_api().check_gas_cap("trace_call", &trace_request.call);
+        self.eth_api().apply_method_gas_cap("trace_call", &mut trace_request.call);
         let at = trace_request.block_id.unwrap_or_default();
+        self.eth_api().ensure_within_trace_window(at)?;
         let config = TracingInspectorConfig::from_parity_config(&trace_request.trace_types);
         let overrides =
             EvmOverrides::new(trace_request.state_overrides, trace_request.block_overrides);
@@ -144,10 +147,14 @@ where
         calls: Vec<(TransactionRequest, HashSet<TraceType>)>,
         block_id: Option<BlockId>,
     ) -> Result<Vec<TraceResults>, Eth::Error> {
+        for (call, _) in &calls {
+            self.eth_api().check_gas_cap("trace_callMany", call);
+        }
         let at = block_id.unwrap_or(BlockId::pending());
+        self.eth_api().ensure_within_trace_window(at)?;
         let (cfg, block_env, at) = self.inner.eth_api.evm_env_at(at).await?;
 
-        let gas_limit = self.inner.eth_api.call_gas_limit();
+        let gas_limit = self.inner.eth_api.method_gas_limit("trace_callMany");
         let this = self.clone();
         // execute all transactions on top of each other and record the traces
         self.eth_api()
@@ -184,6 +184,15 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Adds [`compression::Zstd`] compression without dictionaries, at the given compression
+    /// level.
+    pub fn with_zstd_level(mut self, level: i32) -> Self {
+        self.compressor = Some(Compressors::Zstd(
+            compression::Zstd::new(false, 0, self.columns).with_level(level),
+        ));
+        self
+    }
+
     /// Adds [`compression::Lz4`] compression.
     pub fn with_lz4(mut self) -> Self {
         self.compressor = Some(Compressors::Lz4(compression::Lz4::default()));
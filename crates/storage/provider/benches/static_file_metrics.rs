@@ -0,0 +1,34 @@
+#![allow(missing_docs)]
+use criterion::{criterion_group, criterion_main, Criterion};
+use reth_primitives::{Header, StaticFileSegment, B256, U256};
+use reth_provider::{providers::StaticFileProvider, StaticFileWriter};
+
+/// Benchmarks initializing a cursor on a headers static file, which records an operation through
+/// `StaticFileProviderMetrics::record_segment_operation` if metrics are enabled.
+pub fn record_segment_operation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Static File Metrics");
+
+    for metrics in [false, true] {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let mut provider =
+            StaticFileProvider::read_write(dir.path()).expect("failed to create provider");
+        if metrics {
+            provider = provider.with_metrics();
+        }
+
+        let mut writer = provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        writer.append_header(&Header::default(), U256::ZERO, &B256::ZERO).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        let jar = provider
+            .get_segment_provider_from_block(StaticFileSegment::Headers, 0, None)
+            .expect("headers static file should exist");
+
+        let name = if metrics { "init cursor with metrics" } else { "init cursor" };
+        group.bench_function(name, |b| b.iter(|| jar.cursor().unwrap()));
+    }
+}
+
+criterion_group!(benches, record_segment_operation);
+criterion_main!(benches);
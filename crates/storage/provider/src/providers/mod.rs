@@ -42,8 +42,9 @@ pub use database::*;
 
 mod static_file;
 pub use static_file::{
-    StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderRW,
-    StaticFileProviderRWRefMut, StaticFileWriter,
+    StaticFileAccess, StaticFileCompression, StaticFileJarProvider, StaticFileMetricsReport,
+    StaticFileOperationReport, StaticFileProvider, StaticFileProviderRW,
+    StaticFileProviderRWRefMut, StaticFileSegmentReport, StaticFileWriter, VerifyReport,
 };
 
 mod state;
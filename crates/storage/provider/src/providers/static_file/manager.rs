@@ -1,6 +1,7 @@
 use super::{
-    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
-    StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
+    metrics::{StaticFileMetricsReport, StaticFileProviderMetrics, StaticFileProviderOperation},
+    writer::StaticFileWriters,
+    LoadedJar, StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
     BLOCKS_PER_STATIC_FILE,
 };
 use crate::{
@@ -37,7 +38,9 @@ use std::{
     collections::{hash_map::Entry, BTreeMap, HashMap},
     ops::{Deref, Range, RangeBounds, RangeInclusive},
     path::{Path, PathBuf},
+    str::FromStr,
     sync::{mpsc, Arc},
+    time::Instant,
 };
 use strum::IntoEnumIterator;
 use tracing::{info, trace, warn};
@@ -69,6 +72,61 @@ impl StaticFileAccess {
     }
 }
 
+/// Compression of newly created static files, see [`StaticFileProvider::set_compression`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StaticFileCompression {
+    /// Per segment default: LZ4 for headers, no additional compression for transactions and
+    /// receipts, which are already compressed by their encoding.
+    #[default]
+    Default,
+    /// No compression for all segments.
+    Uncompressed,
+    /// LZ4 compression for all segments.
+    Lz4,
+    /// Zstd compression without dictionaries for all segments, at the given level.
+    ///
+    /// A level of `0` uses zstd's default level.
+    Zstd(i32),
+}
+
+impl FromStr for StaticFileCompression {
+    type Err = String;
+
+    /// Parses `default`, `uncompressed`, `lz4`, `zstd` or `zstd:<level>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "default" => Ok(Self::Default),
+            "uncompressed" => Ok(Self::Uncompressed),
+            "lz4" => Ok(Self::Lz4),
+            "zstd" => Ok(Self::Zstd(0)),
+            _ => s
+                .strip_prefix("zstd:")
+                .and_then(|level| level.parse().ok())
+                .map(Self::Zstd)
+                .ok_or_else(|| format!("invalid static file compression: {s}")),
+        }
+    }
+}
+
+/// Outcome of [`StaticFileProvider::verify_segment`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// The verified segment.
+    pub segment: StaticFileSegment,
+    /// Number of entries that were read and checked.
+    pub entries_checked: u64,
+    /// Block or transaction numbers, depending on the segment, of the entries that are missing or
+    /// don't match their expected hash.
+    pub mismatches: Vec<u64>,
+}
+
+impl VerifyReport {
+    /// Returns `true` if no mismatches were found.
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
 /// [`StaticFileProvider`] manages all existing [`StaticFileJarProvider`].
 #[derive(Debug, Default, Clone)]
 pub struct StaticFileProvider(pub(crate) Arc<StaticFileProviderInner>);
@@ -118,6 +176,8 @@ pub struct StaticFileProviderInner {
     /// Maintains a writer set of [`StaticFileSegment`].
     writers: StaticFileWriters,
     metrics: Option<Arc<StaticFileProviderMetrics>>,
+    /// Compression of newly created static files.
+    compression: RwLock<StaticFileCompression>,
     /// Access rights of the provider.
     access: StaticFileAccess,
     /// Write lock for when access is [`StaticFileAccess::RW`].
@@ -141,6 +201,7 @@ impl StaticFileProviderInner {
             path: path.as_ref().to_path_buf(),
             load_filters: false,
             metrics: None,
+            compression: Default::default(),
             access,
             _lock_file,
         };
@@ -151,6 +212,19 @@ impl StaticFileProviderInner {
     pub const fn is_read_only(&self) -> bool {
         self.access.is_read_only()
     }
+
+    /// Returns the compression of newly created static files.
+    pub fn compression(&self) -> StaticFileCompression {
+        *self.compression.read()
+    }
+
+    /// Sets the compression of newly created static files.
+    ///
+    /// Existing static files, including the ones that are currently appended to, keep their
+    /// compression, so this only applies from the next file range of each segment on.
+    pub fn set_compression(&self, compression: StaticFileCompression) {
+        *self.compression.write() = compression;
+    }
 }
 
 impl StaticFileProvider {
@@ -170,6 +244,26 @@ impl StaticFileProvider {
         Self(Arc::new(provider))
     }
 
+    /// Enables metrics on the [`StaticFileProvider`], reported under the given scope instead of
+    /// the default `static_files` one.
+    ///
+    /// This is useful if multiple providers run in the same process, e.g. one per chain.
+    pub fn with_metrics_scope(self, scope: &str) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.metrics = Some(Arc::new(StaticFileProviderMetrics::with_scope(scope)));
+        Self(Arc::new(provider))
+    }
+
+    /// Enables metrics on the [`StaticFileProvider`], with the given static labels in addition to
+    /// the `segment` and `operation` labels, see [`StaticFileProviderMetrics::new_with_labels`].
+    pub fn with_metrics_labels(self, extra: &[(&str, &str)]) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.metrics = Some(Arc::new(StaticFileProviderMetrics::new_with_labels(extra)));
+        Self(Arc::new(provider))
+    }
+
     /// Reports metrics for the static files.
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
@@ -210,6 +304,123 @@ impl StaticFileProvider {
         Ok(())
     }
 
+    /// Returns a one-off report of the current metric values, or `None` if metrics are disabled.
+    ///
+    /// Segment sizes are updated by [`Self::report_metrics`].
+    pub fn metrics_report(&self) -> Option<StaticFileMetricsReport> {
+        self.metrics.as_ref().map(|metrics| metrics.snapshot())
+    }
+
+    /// Returns the segments on which any operation was recorded, or `None` if metrics are
+    /// disabled.
+    ///
+    /// See [`StaticFileProviderMetrics::segments_with_activity`].
+    pub fn segments_with_activity(&self) -> Option<Vec<StaticFileSegment>> {
+        self.metrics.as_ref().map(|metrics| metrics.segments_with_activity())
+    }
+
+    /// Reads every entry of the given segment and checks it against its expected hash.
+    ///
+    /// Headers are rehashed and compared against the block hash stored alongside them.
+    /// Transactions and receipts don't store a hash, so these are only checked to be present and
+    /// decodable.
+    ///
+    /// Entries that can't be read or decoded, including every entry of a file that can't be
+    /// opened, are reported as mismatches instead of aborting the verification.
+    ///
+    /// This is meant to be run after an unclean shutdown, to confirm that the static files are
+    /// consistent before serving any requests from them.
+    pub fn verify_segment(&self, segment: StaticFileSegment) -> ProviderResult<VerifyReport> {
+        let start = Instant::now();
+        let mut report = VerifyReport { segment, entries_checked: 0, mismatches: Vec::new() };
+
+        let mut static_files =
+            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        for (block_range, tx_range) in static_files.remove(&segment).unwrap_or_default() {
+            let numbers = match segment {
+                StaticFileSegment::Headers => block_range.start()..=block_range.end(),
+                StaticFileSegment::Transactions | StaticFileSegment::Receipts => match tx_range {
+                    Some(range) => range.start()..=range.end(),
+                    None => continue,
+                },
+            };
+
+            // A jar that can't be opened is reported as a mismatch on every entry it should
+            // hold, so that a single corrupted file doesn't stop the rest from being verified.
+            let fixed_block_range = find_fixed_range(block_range.start());
+            let jar_provider =
+                self.get_segment_provider(segment, || Some(fixed_block_range), None).ok().flatten();
+            let Some(mut cursor) = jar_provider.as_ref().and_then(|jar| jar.cursor().ok()) else {
+                for number in numbers {
+                    report.entries_checked += 1;
+                    report.mismatches.push(number);
+                }
+                continue
+            };
+
+            for number in numbers {
+                report.entries_checked += 1;
+                // Rows that fail to decode count as mismatches rather than aborting verification.
+                let consistent = match segment {
+                    StaticFileSegment::Headers => matches!(
+                        cursor.get_two::<HeaderMask<Header, BlockHash>>(number.into()),
+                        Ok(Some((header, hash))) if header.hash_slow() == hash
+                    ),
+                    StaticFileSegment::Transactions => matches!(
+                        cursor.get_one::<TransactionMask<TransactionSignedNoHash>>(number.into()),
+                        Ok(Some(_))
+                    ),
+                    StaticFileSegment::Receipts => {
+                        matches!(cursor.get_one::<ReceiptMask<Receipt>>(number.into()), Ok(Some(_)))
+                    }
+                };
+                if !consistent {
+                    report.mismatches.push(number);
+                }
+            }
+        }
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                segment,
+                StaticFileProviderOperation::Verify,
+                Some(start.elapsed()),
+            );
+        }
+
+        if !report.is_consistent() {
+            warn!(
+                target: "provider::static_file",
+                ?segment,
+                entries_checked = report.entries_checked,
+                mismatches = report.mismatches.len(),
+                "Static file segment is inconsistent"
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Reads the receipts of the given transaction range directly from the static files.
+    ///
+    /// Unlike [`ReceiptProvider::receipts_by_tx_range`], this records the read in the
+    /// [`StaticFileSegment::Receipts`] segment metrics, so that reads served to external
+    /// consumers, like RPC, can be told apart from the ones done during sync.
+    pub fn read_receipts(&self, range: Range<TxNumber>) -> ProviderResult<Vec<Receipt>> {
+        let start = Instant::now();
+        let receipts = self.receipts_by_tx_range(range)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                StaticFileSegment::Receipts,
+                StaticFileProviderOperation::Read,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(receipts)
+    }
+
     /// Gets the [`StaticFileJarProvider`] of the requested segment and block.
     pub fn get_segment_provider_from_block(
         &self,
@@ -1038,6 +1249,16 @@ pub trait StaticFileWriter {
         segment: StaticFileSegment,
     ) -> ProviderResult<StaticFileProviderRWRefMut<'_>>;
 
+    /// Returns a mutable reference to a [`StaticFileProviderRW`] of the latest
+    /// [`StaticFileSegment`], without blocking.
+    ///
+    /// Returns `None` if the writer is currently busy, so that callers can apply backpressure
+    /// instead of waiting for it to be released.
+    fn try_latest_writer(
+        &self,
+        segment: StaticFileSegment,
+    ) -> ProviderResult<Option<StaticFileProviderRWRefMut<'_>>>;
+
     /// Commits all changes of all [`StaticFileProviderRW`] of all [`StaticFileSegment`].
     fn commit(&self) -> ProviderResult<()>;
 
@@ -1068,6 +1289,33 @@ impl StaticFileWriter for StaticFileProvider {
         self.get_writer(self.get_highest_static_file_block(segment).unwrap_or_default(), segment)
     }
 
+    fn try_latest_writer(
+        &self,
+        segment: StaticFileSegment,
+    ) -> ProviderResult<Option<StaticFileProviderRWRefMut<'_>>> {
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess)
+        }
+
+        let block = self.get_highest_static_file_block(segment).unwrap_or_default();
+        let writer = self.writers.try_get_or_create(segment, || {
+            StaticFileProviderRW::new(segment, block, Arc::downgrade(&self.0), self.metrics.clone())
+        })?;
+
+        if writer.is_none() {
+            trace!(target: "provider::static_file", ?segment, "Static file writer is busy.");
+            if let Some(metrics) = &self.metrics {
+                metrics.record_segment_operation(
+                    segment,
+                    StaticFileProviderOperation::AppendBackpressure,
+                    None,
+                );
+            }
+        }
+
+        Ok(writer)
+    }
+
     fn commit(&self) -> ProviderResult<()> {
         self.writers.commit()
     }
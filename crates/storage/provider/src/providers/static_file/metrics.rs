@@ -1,29 +1,75 @@
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use itertools::Itertools;
 use metrics::{Counter, Gauge, Histogram};
 use reth_metrics::Metrics;
 use reth_primitives::StaticFileSegment;
+use serde::Serialize;
 use strum::{EnumIter, IntoEnumIterator};
 
+/// The default scope of the static file provider metrics.
+pub(crate) const DEFAULT_STATIC_FILE_METRICS_SCOPE: &str = "static_files";
+
 /// Metrics for the static file provider.
 #[derive(Debug)]
 pub struct StaticFileProviderMetrics {
-    segments: HashMap<StaticFileSegment, StaticFileSegmentMetrics>,
-    segment_operations: HashMap<
-        (StaticFileSegment, StaticFileProviderOperation),
-        StaticFileProviderOperationMetrics,
-    >,
+    segments: HashMap<StaticFileSegment, SegmentMetrics>,
+    segment_operations: HashMap<(StaticFileSegment, StaticFileProviderOperation), OperationMetrics>,
 }
 
 impl Default for StaticFileProviderMetrics {
     fn default() -> Self {
+        Self::with_scope(DEFAULT_STATIC_FILE_METRICS_SCOPE)
+    }
+}
+
+impl StaticFileProviderMetrics {
+    /// Creates the metrics under the given scope, instead of the default `static_files` one.
+    ///
+    /// This allows telling apart the metrics of multiple static file providers in one process.
+    pub(crate) fn with_scope(scope: &str) -> Self {
+        Self::with_scope_and_labels(scope, &[])
+    }
+
+    /// Creates the metrics with the given static labels, e.g. the region or role of the node,
+    /// in addition to the `segment` and `operation` labels.
+    ///
+    /// The labels are attached to the metrics of every segment and of every operation.
+    pub fn new_with_labels(extra: &[(&str, &str)]) -> Self {
+        Self::with_scope_and_labels(DEFAULT_STATIC_FILE_METRICS_SCOPE, extra)
+    }
+
+    /// Creates the metrics under the given scope, with the given static labels in addition to the
+    /// `segment` and `operation` labels.
+    pub(crate) fn with_scope_and_labels(scope: &str, extra: &[(&str, &str)]) -> Self {
+        let segment_scope = format!("{scope}.segment");
+        let operation_scope = format!("{scope}.jar_provider");
+        let labels = |labels: &[(&'static str, &'static str)]| {
+            labels
+                .iter()
+                .map(|&(key, value)| (key.to_string(), value.to_string()))
+                .chain(extra.iter().map(|&(key, value)| (key.to_string(), value.to_string())))
+                .collect::<Vec<_>>()
+        };
         Self {
             segments: StaticFileSegment::iter()
                 .map(|segment| {
                     (
                         segment,
-                        StaticFileSegmentMetrics::new_with_labels(&[("segment", segment.as_str())]),
+                        SegmentMetrics {
+                            metrics: StaticFileSegmentMetrics::new_with_labels(
+                                &segment_scope,
+                                &labels(&[("segment", segment.as_str())]),
+                            ),
+                            stats: Default::default(),
+                        },
                     )
                 })
                 .collect(),
@@ -32,18 +78,22 @@ impl Default for StaticFileProviderMetrics {
                 .map(|(segment, operation)| {
                     (
                         (segment, operation),
-                        StaticFileProviderOperationMetrics::new_with_labels(&[
-                            ("segment", segment.as_str()),
-                            ("operation", operation.as_str()),
-                        ]),
+                        OperationMetrics {
+                            metrics: StaticFileProviderOperationMetrics::new_with_labels(
+                                &operation_scope,
+                                &labels(&[
+                                    ("segment", segment.as_str()),
+                                    ("operation", operation.as_str()),
+                                ]),
+                            ),
+                            stats: Default::default(),
+                        },
                     )
                 })
                 .collect(),
         }
     }
-}
 
-impl StaticFileProviderMetrics {
     pub(crate) fn record_segment(
         &self,
         segment: StaticFileSegment,
@@ -51,13 +101,14 @@ impl StaticFileProviderMetrics {
         files: usize,
         entries: usize,
     ) {
-        self.segments.get(&segment).expect("segment metrics should exist").size.set(size as f64);
-        self.segments.get(&segment).expect("segment metrics should exist").files.set(files as f64);
-        self.segments
-            .get(&segment)
-            .expect("segment metrics should exist")
-            .entries
-            .set(entries as f64);
+        let SegmentMetrics { metrics, stats } =
+            self.segments.get(&segment).expect("segment metrics should exist");
+        metrics.size.set(size as f64);
+        metrics.files.set(files as f64);
+        metrics.entries.set(entries as f64);
+        stats.size.store(size, Ordering::Relaxed);
+        stats.files.store(files as u64, Ordering::Relaxed);
+        stats.entries.store(entries as u64, Ordering::Relaxed);
     }
 
     pub(crate) fn record_segment_operation(
@@ -66,44 +117,210 @@ impl StaticFileProviderMetrics {
         operation: StaticFileProviderOperation,
         duration: Option<Duration>,
     ) {
-        self.segment_operations
-            .get(&(segment, operation))
-            .expect("segment operation metrics should exist")
-            .calls_total
-            .increment(1);
+        self.segment_operation(segment, operation).record(1, duration);
+    }
 
-        if let Some(duration) = duration {
-            self.segment_operations
-                .get(&(segment, operation))
-                .expect("segment operation metrics should exist")
-                .write_duration_seconds
-                .record(duration.as_secs_f64());
+    /// Returns a handle that records the operations of the given segment, without looking up the
+    /// metrics of the segment and operation on every call.
+    pub(crate) fn for_segment(&self, segment: StaticFileSegment) -> SegmentMetricsView {
+        SegmentMetricsView {
+            operations: StaticFileProviderOperation::iter()
+                .map(|operation| self.segment_operation(segment, operation).clone())
+                .collect(),
         }
     }
 
-    pub(crate) fn record_segment_operations(
+    /// Returns the current values of all metrics, e.g. to print them on machines without a
+    /// metrics scraper.
+    ///
+    /// Segment sizes are only known once [`StaticFileProvider::report_metrics`] ran, operations
+    /// are counted since the metrics were created.
+    ///
+    /// [`StaticFileProvider::report_metrics`]: crate::StaticFileProvider::report_metrics
+    pub fn snapshot(&self) -> StaticFileMetricsReport {
+        let segments = StaticFileSegment::iter()
+            .map(|segment| {
+                let stats =
+                    &self.segments.get(&segment).expect("segment metrics should exist").stats;
+                StaticFileSegmentReport {
+                    segment,
+                    size: stats.size.load(Ordering::Relaxed),
+                    files: stats.files.load(Ordering::Relaxed),
+                    entries: stats.entries.load(Ordering::Relaxed),
+                    operations: StaticFileProviderOperation::iter()
+                        .map(|operation| {
+                            self.segment_operation(segment, operation).report(operation)
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        StaticFileMetricsReport { segments }
+    }
+
+    /// Returns the segments on which any operation was recorded, i.e. the segments that are in use
+    /// on this node, in the order of [`StaticFileSegment`].
+    ///
+    /// Operations are counted since the metrics were created, as in the `calls_total` counters.
+    pub fn segments_with_activity(&self) -> Vec<StaticFileSegment> {
+        StaticFileSegment::iter()
+            .filter(|segment| {
+                StaticFileProviderOperation::iter().any(|operation| {
+                    self.segment_operation(*segment, operation).stats.calls.load(Ordering::Relaxed) >
+                        0
+                })
+            })
+            .collect()
+    }
+
+    fn segment_operation(
         &self,
         segment: StaticFileSegment,
         operation: StaticFileProviderOperation,
-        count: u64,
-        duration: Option<Duration>,
-    ) {
+    ) -> &OperationMetrics {
         self.segment_operations
             .get(&(segment, operation))
             .expect("segment operation metrics should exist")
-            .calls_total
-            .increment(count);
+    }
+}
+
+/// The values of the static file metrics at one point in time, see
+/// [`StaticFileProvider::metrics_report`].
+///
+/// [`StaticFileProvider::metrics_report`]: crate::StaticFileProvider::metrics_report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StaticFileMetricsReport {
+    /// The report of every segment.
+    pub segments: Vec<StaticFileSegmentReport>,
+}
+
+/// The metrics of a single static file segment, see [`StaticFileMetricsReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StaticFileSegmentReport {
+    /// The segment.
+    pub segment: StaticFileSegment,
+    /// The size of the segment in bytes.
+    pub size: u64,
+    /// The number of files of the segment.
+    pub files: u64,
+    /// The number of entries of the segment.
+    pub entries: u64,
+    /// The report of every operation on the segment.
+    pub operations: Vec<StaticFileOperationReport>,
+}
+
+/// The metrics of an operation on a static file segment, see [`StaticFileMetricsReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StaticFileOperationReport {
+    /// The name of the operation, as in the `operation` label of the exported metrics.
+    pub operation: &'static str,
+    /// The number of operations.
+    pub calls: u64,
+    /// The number of operations whose duration was recorded.
+    pub timed_calls: u64,
+    /// The total duration of all timed operations.
+    pub total_duration: Duration,
+    /// The longest average duration of a single recording, i.e. of a single operation or of a
+    /// batch of operations that was recorded at once.
+    pub max_duration: Duration,
+}
+
+impl StaticFileOperationReport {
+    /// Returns the mean duration of the timed operations, if any.
+    pub fn mean_duration(&self) -> Option<Duration> {
+        (self.timed_calls > 0).then(|| {
+            Duration::from_nanos((self.total_duration.as_nanos() / self.timed_calls as u128) as u64)
+        })
+    }
+}
+
+/// The metrics of a segment, along with a copy of their values for
+/// [`StaticFileProviderMetrics::snapshot`].
+#[derive(Debug)]
+struct SegmentMetrics {
+    metrics: StaticFileSegmentMetrics,
+    stats: SegmentStats,
+}
 
+#[derive(Debug, Default)]
+struct SegmentStats {
+    size: AtomicU64,
+    files: AtomicU64,
+    entries: AtomicU64,
+}
+
+/// The metrics of an operation on a segment, along with a copy of their values for
+/// [`StaticFileProviderMetrics::snapshot`].
+#[derive(Debug, Clone)]
+pub(crate) struct OperationMetrics {
+    metrics: StaticFileProviderOperationMetrics,
+    stats: Arc<OperationStats>,
+}
+
+#[derive(Debug, Default)]
+struct OperationStats {
+    calls: AtomicU64,
+    timed_calls: AtomicU64,
+    total_duration_nanos: AtomicU64,
+    max_duration_nanos: AtomicU64,
+}
+
+impl OperationMetrics {
+    /// Records `count` operations that took `duration` in total.
+    fn record(&self, count: u64, duration: Option<Duration>) {
+        self.metrics.record(count, duration);
+
+        self.stats.calls.fetch_add(count, Ordering::Relaxed);
         if let Some(duration) = duration {
-            self.segment_operations
-                .get(&(segment, operation))
-                .expect("segment operation metrics should exist")
-                .write_duration_seconds
-                .record(duration.as_secs_f64() / count as f64);
+            let nanos = duration.as_nanos() as u64;
+            self.stats.timed_calls.fetch_add(count, Ordering::Relaxed);
+            self.stats.total_duration_nanos.fetch_add(nanos, Ordering::Relaxed);
+            self.stats.max_duration_nanos.fetch_max(nanos / count.max(1), Ordering::Relaxed);
+        }
+    }
+
+    fn report(&self, operation: StaticFileProviderOperation) -> StaticFileOperationReport {
+        StaticFileOperationReport {
+            operation: operation.as_str(),
+            calls: self.stats.calls.load(Ordering::Relaxed),
+            timed_calls: self.stats.timed_calls.load(Ordering::Relaxed),
+            total_duration: Duration::from_nanos(
+                self.stats.total_duration_nanos.load(Ordering::Relaxed),
+            ),
+            max_duration: Duration::from_nanos(
+                self.stats.max_duration_nanos.load(Ordering::Relaxed),
+            ),
         }
     }
 }
 
+/// Operation metrics of a single static file segment, see
+/// [`StaticFileProviderMetrics::for_segment`].
+#[derive(Debug, Clone)]
+pub(crate) struct SegmentMetricsView {
+    /// The metrics of every [`StaticFileProviderOperation`], indexed by the operation.
+    operations: Vec<OperationMetrics>,
+}
+
+impl SegmentMetricsView {
+    pub(crate) fn record_operation(
+        &self,
+        operation: StaticFileProviderOperation,
+        duration: Option<Duration>,
+    ) {
+        self.operations[operation as usize].record(1, duration);
+    }
+
+    pub(crate) fn record_operations(
+        &self,
+        operation: StaticFileProviderOperation,
+        count: u64,
+        duration: Option<Duration>,
+    ) {
+        self.operations[operation as usize].record(count, duration);
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
 pub(crate) enum StaticFileProviderOperation {
     InitCursor,
@@ -112,6 +329,9 @@ pub(crate) enum StaticFileProviderOperation {
     Prune,
     IncrementBlock,
     CommitWriter,
+    Verify,
+    Read,
+    AppendBackpressure,
 }
 
 impl StaticFileProviderOperation {
@@ -123,13 +343,16 @@ impl StaticFileProviderOperation {
             Self::Prune => "prune",
             Self::IncrementBlock => "increment-block",
             Self::CommitWriter => "commit-writer",
+            Self::Verify => "verify",
+            Self::Read => "read",
+            Self::AppendBackpressure => "append-backpressure",
         }
     }
 }
 
 /// Metrics for a specific static file segment.
 #[derive(Metrics)]
-#[metrics(scope = "static_files.segment")]
+#[metrics(dynamic = true)]
 pub(crate) struct StaticFileSegmentMetrics {
     /// The size of a static file segment
     size: Gauge,
@@ -139,11 +362,50 @@ pub(crate) struct StaticFileSegmentMetrics {
     entries: Gauge,
 }
 
-#[derive(Metrics)]
-#[metrics(scope = "static_files.jar_provider")]
+#[derive(Clone, Metrics)]
+#[metrics(dynamic = true)]
 pub(crate) struct StaticFileProviderOperationMetrics {
     /// Total number of static file jar provider operations made.
     calls_total: Counter,
     /// The time it took to execute the static file jar provider operation that writes data.
     write_duration_seconds: Histogram,
 }
+
+impl StaticFileProviderOperationMetrics {
+    /// Records `count` operations that took `duration` in total.
+    fn record(&self, count: u64, duration: Option<Duration>) {
+        self.calls_total.increment(count);
+
+        if let Some(duration) = duration {
+            self.write_duration_seconds.record(duration.as_secs_f64() / count as f64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segments_with_activity() {
+        let metrics = StaticFileProviderMetrics::with_scope("segments_with_activity");
+        assert!(metrics.segments_with_activity().is_empty());
+
+        metrics.record_segment_operation(
+            StaticFileSegment::Receipts,
+            StaticFileProviderOperation::Append,
+            None,
+        );
+        metrics
+            .for_segment(StaticFileSegment::Headers)
+            .record_operation(StaticFileProviderOperation::Read, None);
+        assert_eq!(
+            metrics.segments_with_activity(),
+            vec![StaticFileSegment::Headers, StaticFileSegment::Receipts]
+        );
+
+        // recording segment sizes is not an operation
+        metrics.record_segment(StaticFileSegment::Transactions, 1, 1, 1);
+        assert_eq!(metrics.segments_with_activity().len(), 2);
+    }
+}
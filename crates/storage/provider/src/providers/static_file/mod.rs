@@ -1,5 +1,7 @@
 mod manager;
-pub use manager::{StaticFileAccess, StaticFileProvider, StaticFileWriter};
+pub use manager::{
+    StaticFileAccess, StaticFileCompression, StaticFileProvider, StaticFileWriter, VerifyReport,
+};
 
 mod jar;
 pub use jar::StaticFileJarProvider;
@@ -8,6 +10,7 @@ mod writer;
 pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
 mod metrics;
+pub use metrics::{StaticFileMetricsReport, StaticFileOperationReport, StaticFileSegmentReport};
 
 use reth_nippy_jar::NippyJar;
 use reth_primitives::{static_file::SegmentHeader, StaticFileSegment};
@@ -141,4 +144,82 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_verify_segment() {
+        let static_files_path = tempfile::tempdir().unwrap();
+        let headers = random_header_range(&mut generators::rng(), 0..10, B256::random());
+        let corrupted = headers[5].number;
+
+        {
+            let manager = StaticFileProvider::read_write(static_files_path.path()).unwrap();
+            let mut writer = manager.latest_writer(StaticFileSegment::Headers).unwrap();
+            for header in headers {
+                // store a wrong hash for one of the headers
+                let hash = if header.number == corrupted { B256::random() } else { header.hash() };
+                writer.append_header(&header.unseal(), U256::ZERO, &hash).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        let manager = StaticFileProvider::read_only(static_files_path.path()).unwrap();
+        let report = manager.verify_segment(StaticFileSegment::Headers).unwrap();
+        assert_eq!(report.entries_checked, 10);
+        assert_eq!(report.mismatches, vec![corrupted]);
+        assert!(!report.is_consistent());
+    }
+
+    #[test]
+    fn test_verify_segment_unreadable_jar() {
+        let static_files_path = tempfile::tempdir().unwrap();
+        let headers = random_header_range(&mut generators::rng(), 0..10, B256::random());
+
+        {
+            let manager = StaticFileProvider::read_write(static_files_path.path()).unwrap();
+            let mut writer = manager.latest_writer(StaticFileSegment::Headers).unwrap();
+            for header in headers {
+                let hash = header.hash();
+                writer.append_header(&header.unseal(), U256::ZERO, &hash).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        // remove the data file, leaving its configuration behind
+        std::fs::remove_file(
+            static_files_path
+                .path()
+                .join(StaticFileSegment::Headers.filename(&find_fixed_range(0))),
+        )
+        .unwrap();
+
+        let manager = StaticFileProvider::read_only(static_files_path.path()).unwrap();
+        let report = manager.verify_segment(StaticFileSegment::Headers).unwrap();
+        assert_eq!(report.entries_checked, 10);
+        assert_eq!(report.mismatches, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_try_latest_writer_busy() {
+        let static_files_path = tempfile::tempdir().unwrap();
+        let manager =
+            StaticFileProvider::read_write(static_files_path.path()).unwrap().with_metrics();
+
+        let writer = manager.latest_writer(StaticFileSegment::Headers).unwrap();
+        assert!(manager.try_latest_writer(StaticFileSegment::Headers).unwrap().is_none());
+        assert!(manager.try_latest_writer(StaticFileSegment::Receipts).unwrap().is_some());
+
+        drop(writer);
+        assert!(manager.try_latest_writer(StaticFileSegment::Headers).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_parse_compression() {
+        assert_eq!("default".parse(), Ok(StaticFileCompression::Default));
+        assert_eq!("uncompressed".parse(), Ok(StaticFileCompression::Uncompressed));
+        assert_eq!("lz4".parse(), Ok(StaticFileCompression::Lz4));
+        assert_eq!("zstd".parse(), Ok(StaticFileCompression::Zstd(0)));
+        assert_eq!("zstd:19".parse(), Ok(StaticFileCompression::Zstd(19)));
+        assert!("zstd:high".parse::<StaticFileCompression>().is_err());
+        assert!("brotli".parse::<StaticFileCompression>().is_err());
+    }
 }
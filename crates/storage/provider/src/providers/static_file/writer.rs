@@ -1,5 +1,7 @@
 use super::{
-    manager::StaticFileProviderInner, metrics::StaticFileProviderMetrics, StaticFileProvider,
+    manager::{StaticFileCompression, StaticFileProviderInner},
+    metrics::{SegmentMetricsView, StaticFileProviderMetrics},
+    StaticFileProvider,
 };
 use crate::providers::static_file::metrics::StaticFileProviderOperation;
 use parking_lot::{lock_api::RwLockWriteGuard, RawRwLock, RwLock};
@@ -50,6 +52,27 @@ impl StaticFileWriters {
         Ok(StaticFileProviderRWRefMut(write_guard))
     }
 
+    /// Same as [`Self::get_or_create`], but returns `None` instead of blocking if the writer of
+    /// the segment is currently held by someone else.
+    pub(crate) fn try_get_or_create(
+        &self,
+        segment: StaticFileSegment,
+        create_fn: impl FnOnce() -> ProviderResult<StaticFileProviderRW>,
+    ) -> ProviderResult<Option<StaticFileProviderRWRefMut<'_>>> {
+        let write_guard = match segment {
+            StaticFileSegment::Headers => self.headers.try_write(),
+            StaticFileSegment::Transactions => self.transactions.try_write(),
+            StaticFileSegment::Receipts => self.receipts.try_write(),
+        };
+        let Some(mut write_guard) = write_guard else { return Ok(None) };
+
+        if write_guard.is_none() {
+            *write_guard = Some(create_fn()?);
+        }
+
+        Ok(Some(StaticFileProviderRWRefMut(write_guard)))
+    }
+
     pub(crate) fn commit(&self) -> ProviderResult<()> {
         for writer_lock in [&self.headers, &self.transactions, &self.receipts] {
             let mut writer = writer_lock.write();
@@ -96,8 +119,8 @@ pub struct StaticFileProviderRW {
     data_path: PathBuf,
     /// Reusable buffer for encoding appended data.
     buf: Vec<u8>,
-    /// Metrics.
-    metrics: Option<Arc<StaticFileProviderMetrics>>,
+    /// Metrics of the segment.
+    metrics: Option<SegmentMetricsView>,
     /// On commit, does the instructed pruning: number of lines, and if it applies, the last block
     /// it ends at.
     prune_on_commit: Option<(u64, Option<BlockNumber>)>,
@@ -111,7 +134,8 @@ impl StaticFileProviderRW {
         reader: Weak<StaticFileProviderInner>,
         metrics: Option<Arc<StaticFileProviderMetrics>>,
     ) -> ProviderResult<Self> {
-        let (writer, data_path) = Self::open(segment, block, reader.clone(), metrics.clone())?;
+        let metrics = metrics.map(|metrics| metrics.for_segment(segment));
+        let (writer, data_path) = Self::open(segment, block, reader.clone(), metrics.as_ref())?;
         Ok(Self {
             writer,
             data_path,
@@ -126,7 +150,7 @@ impl StaticFileProviderRW {
         segment: StaticFileSegment,
         block: u64,
         reader: Weak<StaticFileProviderInner>,
-        metrics: Option<Arc<StaticFileProviderMetrics>>,
+        metrics: Option<&SegmentMetricsView>,
     ) -> ProviderResult<(NippyJarWriter<SegmentHeader>, PathBuf)> {
         let start = Instant::now();
 
@@ -145,7 +169,7 @@ impl StaticFileProviderRW {
             ),
             Err(ProviderError::MissingStaticFileBlock(_, _)) => {
                 let path = static_file_provider.directory().join(segment.filename(&block_range));
-                (create_jar(segment, &path, block_range), path)
+                (create_jar(segment, &path, block_range, static_file_provider.compression()), path)
             }
             Err(err) => return Err(err),
         };
@@ -166,12 +190,9 @@ impl StaticFileProviderRW {
             Err(e) => Err(ProviderError::NippyJar(e.to_string())),
         }?;
 
-        if let Some(metrics) = &metrics {
-            metrics.record_segment_operation(
-                segment,
-                StaticFileProviderOperation::OpenWriter,
-                Some(start.elapsed()),
-            );
+        if let Some(metrics) = metrics {
+            metrics
+                .record_operation(StaticFileProviderOperation::OpenWriter, Some(start.elapsed()));
         }
 
         Ok(result)
@@ -246,8 +267,7 @@ impl StaticFileProviderRW {
             self.writer.commit().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
 
             if let Some(metrics) = &self.metrics {
-                metrics.record_segment_operation(
-                    self.writer.user_header().segment(),
+                metrics.record_operation(
                     StaticFileProviderOperation::CommitWriter,
                     Some(start.elapsed()),
                 );
@@ -280,11 +300,8 @@ impl StaticFileProviderRW {
             .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
 
         if let Some(metrics) = &self.metrics {
-            metrics.record_segment_operation(
-                self.writer.user_header().segment(),
-                StaticFileProviderOperation::CommitWriter,
-                Some(start.elapsed()),
-            );
+            metrics
+                .record_operation(StaticFileProviderOperation::CommitWriter, Some(start.elapsed()));
         }
 
         debug!(
@@ -344,8 +361,12 @@ impl StaticFileProviderRW {
                 self.commit()?;
 
                 // Opens the new static file
-                let (writer, data_path) =
-                    Self::open(segment, last_block + 1, self.reader.clone(), self.metrics.clone())?;
+                let (writer, data_path) = Self::open(
+                    segment,
+                    last_block + 1,
+                    self.reader.clone(),
+                    self.metrics.as_ref(),
+                )?;
                 self.writer = writer;
                 self.data_path = data_path;
 
@@ -356,8 +377,7 @@ impl StaticFileProviderRW {
 
         let block = self.writer.user_header_mut().increment_block();
         if let Some(metrics) = &self.metrics {
-            metrics.record_segment_operation(
-                segment,
+            metrics.record_operation(
                 StaticFileProviderOperation::IncrementBlock,
                 Some(start.elapsed()),
             );
@@ -476,7 +496,7 @@ impl StaticFileProviderRW {
             self.user_header().segment(),
             self.writer.user_header().expected_block_start() - 1,
             self.reader.clone(),
-            self.metrics.clone(),
+            self.metrics.as_ref(),
         )?;
         self.writer = previous_writer;
         self.data_path = data_path;
@@ -544,11 +564,7 @@ impl StaticFileProviderRW {
         self.append_column(hash)?;
 
         if let Some(metrics) = &self.metrics {
-            metrics.record_segment_operation(
-                StaticFileSegment::Headers,
-                StaticFileProviderOperation::Append,
-                Some(start.elapsed()),
-            );
+            metrics.record_operation(StaticFileProviderOperation::Append, Some(start.elapsed()));
         }
 
         Ok(block_number)
@@ -571,11 +587,7 @@ impl StaticFileProviderRW {
         let result = self.append_with_tx_number(StaticFileSegment::Transactions, tx_num, tx)?;
 
         if let Some(metrics) = &self.metrics {
-            metrics.record_segment_operation(
-                StaticFileSegment::Transactions,
-                StaticFileProviderOperation::Append,
-                Some(start.elapsed()),
-            );
+            metrics.record_operation(StaticFileProviderOperation::Append, Some(start.elapsed()));
         }
 
         Ok(result)
@@ -598,11 +610,7 @@ impl StaticFileProviderRW {
         let result = self.append_with_tx_number(StaticFileSegment::Receipts, tx_num, receipt)?;
 
         if let Some(metrics) = &self.metrics {
-            metrics.record_segment_operation(
-                StaticFileSegment::Receipts,
-                StaticFileProviderOperation::Append,
-                Some(start.elapsed()),
-            );
+            metrics.record_operation(StaticFileProviderOperation::Append, Some(start.elapsed()));
         }
 
         Ok(result)
@@ -637,8 +645,7 @@ impl StaticFileProviderRW {
         }
 
         if let Some(metrics) = &self.metrics {
-            metrics.record_segment_operations(
-                StaticFileSegment::Receipts,
+            metrics.record_operations(
                 StaticFileProviderOperation::Append,
                 count,
                 Some(start.elapsed()),
@@ -716,11 +723,7 @@ impl StaticFileProviderRW {
         self.truncate(segment, to_delete, Some(last_block))?;
 
         if let Some(metrics) = &self.metrics {
-            metrics.record_segment_operation(
-                StaticFileSegment::Transactions,
-                StaticFileProviderOperation::Prune,
-                Some(start.elapsed()),
-            );
+            metrics.record_operation(StaticFileProviderOperation::Prune, Some(start.elapsed()));
         }
 
         Ok(())
@@ -740,11 +743,7 @@ impl StaticFileProviderRW {
         self.truncate(segment, to_delete, Some(last_block))?;
 
         if let Some(metrics) = &self.metrics {
-            metrics.record_segment_operation(
-                StaticFileSegment::Receipts,
-                StaticFileProviderOperation::Prune,
-                Some(start.elapsed()),
-            );
+            metrics.record_operation(StaticFileProviderOperation::Prune, Some(start.elapsed()));
         }
 
         Ok(())
@@ -760,11 +759,7 @@ impl StaticFileProviderRW {
         self.truncate(segment, to_delete, None)?;
 
         if let Some(metrics) = &self.metrics {
-            metrics.record_segment_operation(
-                StaticFileSegment::Headers,
-                StaticFileProviderOperation::Prune,
-                Some(start.elapsed()),
-            );
+            metrics.record_operation(StaticFileProviderOperation::Prune, Some(start.elapsed()));
         }
 
         Ok(())
@@ -815,18 +810,20 @@ fn create_jar(
     segment: StaticFileSegment,
     path: &Path,
     expected_block_range: SegmentRangeInclusive,
+    compression: StaticFileCompression,
 ) -> NippyJar<SegmentHeader> {
-    let mut jar = NippyJar::new(
+    let jar = NippyJar::new(
         segment.columns(),
         path,
         SegmentHeader::new(expected_block_range, None, None, segment),
     );
 
-    // Transaction and Receipt already have the compression scheme used natively in its encoding.
-    // (zstd-dictionary)
-    if segment.is_headers() {
-        jar = jar.with_lz4();
+    match compression {
+        // Transaction and Receipt already have the compression scheme used natively in its
+        // encoding. (zstd-dictionary)
+        StaticFileCompression::Default if segment.is_headers() => jar.with_lz4(),
+        StaticFileCompression::Default | StaticFileCompression::Uncompressed => jar,
+        StaticFileCompression::Lz4 => jar.with_lz4(),
+        StaticFileCompression::Zstd(level) => jar.with_zstd_level(level),
     }
-
-    jar
 }
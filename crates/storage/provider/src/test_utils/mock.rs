@@ -37,6 +37,8 @@ pub struct MockEthProvider {
     pub blocks: Arc<Mutex<HashMap<B256, Block>>>,
     /// Local header store
     pub headers: Arc<Mutex<HashMap<B256, Header>>>,
+    /// Local receipt store, keyed by block hash
+    pub receipts: Arc<Mutex<HashMap<B256, Vec<Receipt>>>>,
     /// Local account store
     pub accounts: Arc<Mutex<HashMap<Address, ExtendedAccount>>>,
     /// Local chain spec
@@ -50,6 +52,7 @@ impl Default for MockEthProvider {
         Self {
             blocks: Default::default(),
             headers: Default::default(),
+            receipts: Default::default(),
             accounts: Default::default(),
             chain_spec: Arc::new(reth_chainspec::ChainSpecBuilder::mainnet().build()),
             state_roots: Default::default(),
@@ -109,6 +112,11 @@ impl MockEthProvider {
         }
     }
 
+    /// Add the receipts of the block with the given hash to local receipt store
+    pub fn add_receipts(&self, hash: B256, receipts: Vec<Receipt>) {
+        self.receipts.lock().insert(hash, receipts);
+    }
+
     /// Add header to local header store
     pub fn add_header(&self, hash: B256, header: Header) {
         self.headers.lock().insert(hash, header);
@@ -357,8 +365,12 @@ impl ReceiptProvider for MockEthProvider {
         Ok(None)
     }
 
-    fn receipts_by_block(&self, _block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
-        Ok(None)
+    fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        let hash = match block {
+            BlockHashOrNumber::Hash(hash) => Some(hash),
+            BlockHashOrNumber::Number(number) => self.block_hash(number)?,
+        };
+        Ok(hash.and_then(|hash| self.receipts.lock().get(&hash).cloned()))
     }
 
     fn receipts_by_tx_range(
@@ -479,10 +491,10 @@ impl BlockReader for MockEthProvider {
 
     fn block_with_senders(
         &self,
-        _id: BlockHashOrNumber,
+        id: BlockHashOrNumber,
         _transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<BlockWithSenders>> {
-        Ok(None)
+        Ok(self.block(id)?.and_then(|block| block.with_recovered_senders()))
     }
 
     fn sealed_block_with_senders(
@@ -37,6 +37,11 @@ impl BlockingTaskGuard {
     pub async fn acquire_many_owned(self, n: u32) -> Result<OwnedSemaphorePermit, AcquireError> {
         self.0.acquire_many_owned(n).await
     }
+
+    /// See also [`Semaphore::available_permits`]
+    pub fn available_permits(&self) -> usize {
+        self.0.available_permits()
+    }
 }
 
 /// Used to execute blocking tasks on a rayon threadpool from within a tokio runtime.